@@ -0,0 +1,82 @@
+use anchor_lang::prelude::*;
+
+use crate::state::{TrustCredential, TrustScore, TrustTierView};
+
+/// Issue a soulbound trust credential mirroring the caller's trust score
+pub(crate) fn issue_trust_credential(ctx: Context<IssueTrustCredential>) -> Result<()> {
+    let credential = &mut ctx.accounts.trust_credential;
+    let trust_score = &ctx.accounts.trust_score;
+    let clock = Clock::get()?;
+
+    credential.schema_version = TrustCredential::SCHEMA_VERSION;
+    credential.authority = ctx.accounts.authority.key();
+    credential.issued_at = clock.unix_timestamp;
+    credential.sync(trust_score, clock.unix_timestamp);
+    credential.bump = ctx.bumps.trust_credential;
+
+    emit!(TrustCredentialSynced {
+        authority: credential.authority,
+        schema_version: credential.schema_version,
+        tier: credential.tier,
+        score: credential.score,
+        updated_at: credential.updated_at,
+    });
+
+    msg!("Trust credential issued for {}", credential.authority);
+    Ok(())
+}
+
+/// View instruction for partner protocols. The returned `TrustTierView` is
+/// written with `set_return_data`, so it can be read after a CPI. Tier and
+/// score come from the live `TrustScore`, so a credential that has not been
+/// synced since a penalty cannot report a stale, higher tier.
+pub(crate) fn get_trust_tier(ctx: Context<GetTrustTier>) -> Result<TrustTierView> {
+    Ok(ctx.accounts.trust_credential.view(&ctx.accounts.trust_score))
+}
+
+#[derive(Accounts)]
+pub struct IssueTrustCredential<'info> {
+    #[account(
+        init,
+        payer = authority,
+        space = TrustCredential::SPACE,
+        seeds = [b"trust_credential", authority.key().as_ref()],
+        bump
+    )]
+    pub trust_credential: Account<'info, TrustCredential>,
+
+    #[account(
+        seeds = [b"trust_score", authority.key().as_ref()],
+        bump = trust_score.bump
+    )]
+    pub trust_score: Account<'info, TrustScore>,
+
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct GetTrustTier<'info> {
+    #[account(
+        seeds = [b"trust_credential", trust_credential.authority.as_ref()],
+        bump = trust_credential.bump
+    )]
+    pub trust_credential: Account<'info, TrustCredential>,
+
+    #[account(
+        seeds = [b"trust_score", trust_credential.authority.as_ref()],
+        bump = trust_score.bump
+    )]
+    pub trust_score: Account<'info, TrustScore>,
+}
+
+#[event]
+pub struct TrustCredentialSynced {
+    pub authority: Pubkey,
+    pub schema_version: u8,
+    pub tier: u8,
+    pub score: u16,
+    pub updated_at: i64,
+}
@@ -0,0 +1,250 @@
+use anchor_lang::prelude::*;
+use anchor_spl::associated_token::get_associated_token_address;
+use anchor_spl::token::{self, TokenAccount};
+
+use crate::errors::HaloError;
+use crate::state::{
+    Circle, DelegatedPower, GovernanceProposal, Member, MemberStatus, TokenBalanceSnapshot, TrustScore,
+    VotingPowerSource,
+};
+
+/// A member's standing delegation of their circle voting power
+#[account]
+pub struct Delegation {
+    /// Circle the delegation applies to
+    pub circle: Pubkey,
+    /// Member giving away their voting power
+    pub delegator: Pubkey,
+    /// Member voting on the delegator's behalf
+    pub delegate: Pubkey,
+    /// Timestamp when the delegation was created
+    pub created_at: i64,
+    /// Bump seed for PDA
+    pub bump: u8,
+}
+
+impl Delegation {
+    pub fn space() -> usize {
+        8 + // discriminator
+        32 + // circle
+        32 + // delegator
+        32 + // delegate
+        8 + // created_at
+        1 + // bump
+        50 // extra space
+    }
+}
+
+/// Voting power of a member on a proposal under its configured source.
+/// Trust-weighted power uses the member's live trust score; token-balance
+/// power uses the balance snapshotted when the proposal was created.
+pub(crate) fn member_voting_power(
+    proposal: &GovernanceProposal,
+    member: &Member,
+    trust_score: Option<&TrustScore>,
+) -> Result<u64> {
+    let power = match proposal.voting_power_source {
+        VotingPowerSource::OneMemberOneVote => 1,
+        VotingPowerSource::StakeWeighted => member.stake_amount,
+        VotingPowerSource::TrustWeighted => {
+            let trust_score = trust_score.ok_or(HaloError::TrustScoreNotFound)?;
+            require!(trust_score.authority == member.authority, HaloError::TrustScoreNotFound);
+            trust_score.score as u64
+        },
+        VotingPowerSource::TokenBalance => proposal.snapshot_balance(&member.authority),
+    };
+    Ok(power)
+}
+
+/// Capture the governance token balance of every circle member. Accounts
+/// are each member's associated token account for `governance_mint`, in the
+/// order of `members`; an account that was never created counts as empty.
+pub(crate) fn snapshot_token_balances(
+    members: &[Pubkey],
+    governance_mint: Pubkey,
+    token_accounts: &[AccountInfo],
+) -> Result<Vec<TokenBalanceSnapshot>> {
+    require!(token_accounts.len() == members.len(), HaloError::GovernanceMintMismatch);
+
+    members
+        .iter()
+        .zip(token_accounts)
+        .map(|(member, account)| {
+            require!(
+                account.key() == get_associated_token_address(member, &governance_mint),
+                HaloError::GovernanceMintMismatch
+            );
+            let amount = if account.data_is_empty() {
+                0
+            } else {
+                require!(account.owner == &token::ID, HaloError::GovernanceMintMismatch);
+                let token_account = TokenAccount::try_deserialize(&mut &account.try_borrow_data()?[..])?;
+                require!(token_account.owner == *member, HaloError::GovernanceMintMismatch);
+                token_account.amount
+            };
+            Ok(TokenBalanceSnapshot { member: *member, amount })
+        })
+        .collect()
+}
+
+/// Count the power of members who delegated to `delegate` and have not voted
+/// on the proposal themselves; the others are skipped. Remaining accounts
+/// come in groups of `[delegation, delegator_member, delegator_vote]`,
+/// followed by the delegator's trust score for trust-weighted voting.
+/// `delegator_vote` is the delegator's vote PDA for this proposal.
+pub(crate) fn collect_delegated_power(
+    proposal: &mut GovernanceProposal,
+    proposal_key: Pubkey,
+    delegate: Pubkey,
+    remaining_accounts: &[AccountInfo],
+) -> Result<Vec<DelegatedPower>> {
+    let stride = if proposal.voting_power_source == VotingPowerSource::TrustWeighted { 4 } else { 3 };
+    let groups = remaining_accounts.chunks_exact(stride);
+    require!(groups.remainder().is_empty(), HaloError::InvalidDelegation);
+
+    let mut delegations = Vec::new();
+    for group in groups {
+        require!(
+            group[0].owner == &crate::ID && group[1].owner == &crate::ID,
+            HaloError::InvalidDelegation
+        );
+        let delegation = Delegation::try_deserialize(&mut &group[0].try_borrow_data()?[..])?;
+        let member = Member::try_deserialize(&mut &group[1].try_borrow_data()?[..])?;
+
+        require!(
+            delegation.circle == proposal.circle &&
+            delegation.delegate == delegate &&
+            member.circle == proposal.circle &&
+            member.authority == delegation.delegator,
+            HaloError::InvalidDelegation
+        );
+        require!(member.status == MemberStatus::Active, HaloError::InvalidDelegation);
+
+        // Skip delegators who voted directly or whose power was already cast
+        let (vote_key, _) = Pubkey::find_program_address(
+            &[b"vote", proposal_key.as_ref(), delegation.delegator.as_ref()],
+            &crate::ID,
+        );
+        require!(group[2].key() == vote_key, HaloError::InvalidDelegation);
+        if !group[2].data_is_empty() || proposal.delegated_voters.contains(&delegation.delegator) {
+            continue;
+        }
+
+        let trust_score = if stride == 4 {
+            require!(group[3].owner == &crate::ID, HaloError::TrustScoreNotFound);
+            Some(TrustScore::try_deserialize(&mut &group[3].try_borrow_data()?[..])?)
+        } else {
+            None
+        };
+        let power = member_voting_power(proposal, &member, trust_score.as_ref())?;
+
+        proposal.delegated_voters.push(delegation.delegator);
+        delegations.push(DelegatedPower {
+            delegator: delegation.delegator,
+            power,
+        });
+    }
+
+    Ok(delegations)
+}
+
+/// Let another member of the circle vote with this member's power
+pub(crate) fn delegate_vote(ctx: Context<DelegateVote>, delegate: Pubkey) -> Result<()> {
+    let delegator = ctx.accounts.delegator.key();
+    require!(delegate != delegator, HaloError::InvalidDelegation);
+    require!(
+        ctx.accounts.delegator_member.status == MemberStatus::Active &&
+        ctx.accounts.delegate_member.status == MemberStatus::Active,
+        HaloError::InvalidDelegation
+    );
+
+    let delegation = &mut ctx.accounts.delegation;
+    delegation.circle = ctx.accounts.circle.key();
+    delegation.delegator = delegator;
+    delegation.delegate = delegate;
+    delegation.created_at = Clock::get()?.unix_timestamp;
+    delegation.bump = ctx.bumps.delegation;
+
+    emit!(VoteDelegated {
+        circle: delegation.circle,
+        delegator,
+        delegate,
+    });
+
+    Ok(())
+}
+
+/// Withdraw a delegation. Votes the delegate already cast are kept, but the
+/// delegator can still override them by voting directly.
+pub(crate) fn revoke_delegation(ctx: Context<RevokeDelegation>) -> Result<()> {
+    let delegation = &ctx.accounts.delegation;
+
+    emit!(DelegationRevoked {
+        circle: delegation.circle,
+        delegator: delegation.delegator,
+        delegate: delegation.delegate,
+    });
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+#[instruction(delegate: Pubkey)]
+pub struct DelegateVote<'info> {
+    #[account(
+        init,
+        payer = delegator,
+        space = Delegation::space(),
+        seeds = [b"delegation", circle.key().as_ref(), delegator.key().as_ref()],
+        bump
+    )]
+    pub delegation: Account<'info, Delegation>,
+
+    pub circle: Account<'info, Circle>,
+
+    #[account(
+        seeds = [b"member", circle.key().as_ref(), delegator.key().as_ref()],
+        bump = delegator_member.bump
+    )]
+    pub delegator_member: Account<'info, Member>,
+
+    #[account(
+        seeds = [b"member", circle.key().as_ref(), delegate.as_ref()],
+        bump = delegate_member.bump
+    )]
+    pub delegate_member: Account<'info, Member>,
+
+    #[account(mut)]
+    pub delegator: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct RevokeDelegation<'info> {
+    #[account(
+        mut,
+        seeds = [b"delegation", delegation.circle.as_ref(), delegator.key().as_ref()],
+        bump = delegation.bump,
+        has_one = delegator @ HaloError::UnauthorizedMember,
+        close = delegator
+    )]
+    pub delegation: Account<'info, Delegation>,
+
+    #[account(mut)]
+    pub delegator: Signer<'info>,
+}
+
+#[event]
+pub struct VoteDelegated {
+    pub circle: Pubkey,
+    pub delegator: Pubkey,
+    pub delegate: Pubkey,
+}
+
+#[event]
+pub struct DelegationRevoked {
+    pub circle: Pubkey,
+    pub delegator: Pubkey,
+    pub delegate: Pubkey,
+}
@@ -0,0 +1,428 @@
+use anchor_lang::prelude::*;
+
+use crate::delegation::Delegation;
+use crate::errors::HaloError;
+use crate::state::{Circle, Member, MemberStatus, Treasury, TrustPenaltyKind, TrustScore};
+
+/// A member's appeal against one recorded trust penalty
+#[account]
+pub struct TrustDispute {
+    /// Circle the disputed penalty was recorded in
+    pub circle: Pubkey,
+    /// Member disputing the penalty
+    pub disputant: Pubkey,
+    /// Kind of the disputed penalty
+    pub kind: TrustPenaltyKind,
+    /// Hashes of off-chain evidence (e.g. outage reports, receipts)
+    pub evidence_hashes: Vec<[u8; 32]>,
+    /// Current status of the dispute
+    pub status: DisputeStatus,
+    /// Votes in favour of reversing the penalty
+    pub votes_for: u16,
+    /// Votes against reversing the penalty
+    pub votes_against: u16,
+    /// Timestamp when the dispute was filed
+    pub created_at: i64,
+    /// Timestamp when voting ends
+    pub voting_end: i64,
+    /// Bump seed for PDA
+    pub bump: u8,
+    /// Sequence number of the disputed penalty record
+    pub penalty_sequence: u32,
+    /// When the disputed penalty was recorded
+    pub penalty_recorded_at: i64,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, PartialEq, Eq, Default)]
+pub enum DisputeStatus {
+    #[default]
+    Open,      // Voting in progress
+    Accepted,  // Penalty reversed
+    Rejected,  // Penalty upheld
+}
+
+impl TrustDispute {
+    pub const MAX_EVIDENCE_HASHES: usize = 5;
+    pub const VOTING_PERIOD: i64 = 3 * 24 * 60 * 60; // 3 days
+    pub const MIN_VOTES: u16 = 2;
+
+    pub fn space() -> usize {
+        8 + // discriminator
+        32 + // circle
+        32 + // disputant
+        1 + // kind
+        4 + 32 * Self::MAX_EVIDENCE_HASHES + // evidence_hashes vec
+        1 + // status
+        2 + // votes_for
+        2 + // votes_against
+        8 + // created_at
+        8 + // voting_end
+        1 + // bump
+        4 + // penalty_sequence
+        8 + // penalty_recorded_at
+        50 // extra space
+    }
+}
+
+/// Neutral party allowed to vote on any trust dispute
+#[account]
+pub struct Arbiter {
+    /// The arbiter's wallet
+    pub authority: Pubkey,
+    /// Treasury authority that registered this arbiter
+    pub registered_by: Pubkey,
+    /// Timestamp of registration
+    pub registered_at: i64,
+    /// Bump seed for PDA
+    pub bump: u8,
+}
+
+impl Arbiter {
+    pub const SPACE: usize = 8 + // discriminator
+        32 + // authority
+        32 + // registered_by
+        8 + // registered_at
+        1; // bump
+}
+
+/// Record of a single vote on a trust dispute
+#[account]
+pub struct DisputeVote {
+    /// The dispute voted on
+    pub dispute: Pubkey,
+    /// The voter
+    pub voter: Pubkey,
+    /// Whether the voter supports reversing the penalty
+    pub support: bool,
+    /// Timestamp of the vote
+    pub voted_at: i64,
+    /// Bump seed for PDA
+    pub bump: u8,
+}
+
+impl DisputeVote {
+    pub const SPACE: usize = 8 + // discriminator
+        32 + // dispute
+        32 + // voter
+        1 + // support
+        8 + // voted_at
+        1; // bump
+}
+
+#[derive(Accounts)]
+#[instruction(penalty_sequence: u32)]
+pub struct FileTrustDispute<'info> {
+    /// One dispute per penalty record, ever
+    #[account(
+        init,
+        payer = disputant,
+        space = TrustDispute::space(),
+        seeds = [
+            b"trust_dispute",
+            disputant.key().as_ref(),
+            penalty_sequence.to_le_bytes().as_ref()
+        ],
+        bump
+    )]
+    pub dispute: Account<'info, TrustDispute>,
+
+    pub circle: Account<'info, Circle>,
+
+    #[account(
+        seeds = [b"member", circle.key().as_ref(), disputant.key().as_ref()],
+        bump = member.bump
+    )]
+    pub member: Account<'info, Member>,
+
+    #[account(
+        seeds = [b"trust_score", disputant.key().as_ref()],
+        bump = trust_score.bump
+    )]
+    pub trust_score: Account<'info, TrustScore>,
+
+    #[account(mut)]
+    pub disputant: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+#[instruction(arbiter_authority: Pubkey)]
+pub struct RegisterArbiter<'info> {
+    #[account(
+        init,
+        payer = authority,
+        space = Arbiter::SPACE,
+        seeds = [b"arbiter", arbiter_authority.as_ref()],
+        bump
+    )]
+    pub arbiter: Account<'info, Arbiter>,
+
+    #[account(
+        seeds = [b"treasury"],
+        bump = treasury.bump,
+        constraint = treasury.authority == authority.key() @ HaloError::UnauthorizedMember
+    )]
+    pub treasury: Account<'info, Treasury>,
+
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct RemoveArbiter<'info> {
+    #[account(
+        mut,
+        close = authority,
+        seeds = [b"arbiter", arbiter.authority.as_ref()],
+        bump = arbiter.bump
+    )]
+    pub arbiter: Account<'info, Arbiter>,
+
+    #[account(
+        seeds = [b"treasury"],
+        bump = treasury.bump,
+        constraint = treasury.authority == authority.key() @ HaloError::UnauthorizedMember
+    )]
+    pub treasury: Account<'info, Treasury>,
+
+    #[account(mut)]
+    pub authority: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct CastDisputeVote<'info> {
+    #[account(mut)]
+    pub dispute: Account<'info, TrustDispute>,
+
+    #[account(
+        init,
+        payer = voter,
+        space = DisputeVote::SPACE,
+        seeds = [b"dispute_vote", dispute.key().as_ref(), voter.key().as_ref()],
+        bump
+    )]
+    pub dispute_vote: Account<'info, DisputeVote>,
+
+    /// Required when voting as a member of the circle the penalty was recorded in
+    #[account(
+        seeds = [b"member", dispute.circle.as_ref(), voter.key().as_ref()],
+        bump = voter_member.bump
+    )]
+    pub voter_member: Option<Account<'info, Member>>,
+
+    /// CHECK: The disputant's delegation in the circle, which may not exist;
+    /// their delegate cannot vote on the dispute
+    #[account(seeds = [b"delegation", dispute.circle.as_ref(), dispute.disputant.as_ref()], bump)]
+    pub disputant_delegation: UncheckedAccount<'info>,
+
+    /// Required when voting as a registered arbiter
+    #[account(
+        seeds = [b"arbiter", voter.key().as_ref()],
+        bump = arbiter.bump
+    )]
+    pub arbiter: Option<Account<'info, Arbiter>>,
+
+    #[account(mut)]
+    pub voter: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct ResolveTrustDispute<'info> {
+    #[account(
+        mut,
+        seeds = [
+            b"trust_dispute",
+            dispute.disputant.as_ref(),
+            dispute.penalty_sequence.to_le_bytes().as_ref()
+        ],
+        bump = dispute.bump
+    )]
+    pub dispute: Account<'info, TrustDispute>,
+
+    #[account(
+        mut,
+        seeds = [b"trust_score", dispute.disputant.as_ref()],
+        bump = trust_score.bump
+    )]
+    pub trust_score: Account<'info, TrustScore>,
+
+    #[account(
+        mut,
+        seeds = [b"member", dispute.circle.as_ref(), dispute.disputant.as_ref()],
+        bump = member.bump
+    )]
+    pub member: Account<'info, Member>,
+
+    pub authority: Signer<'info>,
+}
+
+/// Dispute one recorded trust penalty. `circle` must be the circle the
+/// penalty was recorded in.
+pub(crate) fn file_trust_dispute(
+    ctx: Context<FileTrustDispute>,
+    penalty_sequence: u32,
+    evidence_hashes: Vec<[u8; 32]>,
+) -> Result<()> {
+    require!(
+        !evidence_hashes.is_empty() && evidence_hashes.len() <= TrustDispute::MAX_EVIDENCE_HASHES,
+        HaloError::InvalidDisputeEvidence
+    );
+    let record = ctx.accounts.trust_score
+        .penalty_record(penalty_sequence)
+        .filter(|record| !record.reversed)
+        .ok_or(HaloError::NothingToDispute)?;
+    require!(record.circle == ctx.accounts.circle.key(), HaloError::DisputedPenaltyNotInCircle);
+    let kind = record.kind;
+    let penalty_recorded_at = record.recorded_at;
+
+    let clock = Clock::get()?;
+    let dispute = &mut ctx.accounts.dispute;
+    dispute.circle = ctx.accounts.circle.key();
+    dispute.disputant = ctx.accounts.disputant.key();
+    dispute.kind = kind;
+    dispute.penalty_sequence = penalty_sequence;
+    dispute.penalty_recorded_at = penalty_recorded_at;
+    dispute.evidence_hashes = evidence_hashes;
+    dispute.status = DisputeStatus::Open;
+    dispute.votes_for = 0;
+    dispute.votes_against = 0;
+    dispute.created_at = clock.unix_timestamp;
+    dispute.voting_end = clock.unix_timestamp
+        .checked_add(TrustDispute::VOTING_PERIOD)
+        .ok_or(HaloError::ArithmeticOverflow)?;
+    dispute.bump = ctx.bumps.dispute;
+
+    emit!(TrustDisputeFiled {
+        dispute: dispute.key(),
+        circle: dispute.circle,
+        disputant: dispute.disputant,
+        kind,
+        penalty_sequence,
+        voting_end: dispute.voting_end,
+    });
+
+    Ok(())
+}
+
+pub(crate) fn register_arbiter(ctx: Context<RegisterArbiter>, arbiter_authority: Pubkey) -> Result<()> {
+    let arbiter = &mut ctx.accounts.arbiter;
+    arbiter.authority = arbiter_authority;
+    arbiter.registered_by = ctx.accounts.authority.key();
+    arbiter.registered_at = Clock::get()?.unix_timestamp;
+    arbiter.bump = ctx.bumps.arbiter;
+
+    msg!("Arbiter {} registered", arbiter_authority);
+    Ok(())
+}
+
+pub(crate) fn remove_arbiter(ctx: Context<RemoveArbiter>) -> Result<()> {
+    msg!("Arbiter {} removed", ctx.accounts.arbiter.authority);
+    Ok(())
+}
+
+/// Vote on a dispute as a registered arbiter, or as a member of the circle
+/// who already belonged to it when the penalty was recorded. The disputant
+/// and the delegate they chose in that circle cannot vote.
+pub(crate) fn cast_dispute_vote(ctx: Context<CastDisputeVote>, support: bool) -> Result<()> {
+    let dispute = &mut ctx.accounts.dispute;
+    let voter = ctx.accounts.voter.key();
+    let clock = Clock::get()?;
+
+    require!(dispute.status == DisputeStatus::Open, HaloError::DisputeNotOpen);
+    require!(clock.unix_timestamp < dispute.voting_end, HaloError::DisputeVotingEnded);
+    require!(voter != dispute.disputant, HaloError::UnauthorizedDisputeVoter);
+
+    let delegation_info = &ctx.accounts.disputant_delegation;
+    if delegation_info.owner == &crate::ID && !delegation_info.data_is_empty() {
+        let delegation = Delegation::try_deserialize(&mut &delegation_info.try_borrow_data()?[..])?;
+        require!(delegation.delegate != voter, HaloError::UnauthorizedDisputeVoter);
+    }
+
+    let is_circle_member = ctx.accounts.voter_member
+        .as_ref()
+        .is_some_and(|member| {
+            member.status == MemberStatus::Active &&
+            member.joined_at <= dispute.penalty_recorded_at
+        });
+    require!(
+        is_circle_member || ctx.accounts.arbiter.is_some(),
+        HaloError::UnauthorizedDisputeVoter
+    );
+
+    if support {
+        dispute.votes_for = dispute.votes_for.checked_add(1).ok_or(HaloError::ArithmeticOverflow)?;
+    } else {
+        dispute.votes_against = dispute.votes_against.checked_add(1).ok_or(HaloError::ArithmeticOverflow)?;
+    }
+
+    let dispute_vote = &mut ctx.accounts.dispute_vote;
+    dispute_vote.dispute = dispute.key();
+    dispute_vote.voter = voter;
+    dispute_vote.support = support;
+    dispute_vote.voted_at = clock.unix_timestamp;
+    dispute_vote.bump = ctx.bumps.dispute_vote;
+
+    msg!("Vote cast on trust dispute by {}: {}", voter, support);
+    Ok(())
+}
+
+/// Close voting on a dispute. An accepted dispute reverses the disputed
+/// penalty record and recalculates the score; a record that has since been
+/// dropped from the trust score can no longer be reversed.
+pub(crate) fn resolve_trust_dispute(ctx: Context<ResolveTrustDispute>) -> Result<()> {
+    let dispute = &mut ctx.accounts.dispute;
+    let clock = Clock::get()?;
+
+    require!(dispute.status == DisputeStatus::Open, HaloError::DisputeNotOpen);
+    require!(clock.unix_timestamp >= dispute.voting_end, HaloError::DisputeVotingActive);
+
+    let total_votes = dispute.votes_for.saturating_add(dispute.votes_against);
+    let trust_score = &mut ctx.accounts.trust_score;
+    let accepted = total_votes >= TrustDispute::MIN_VOTES &&
+        dispute.votes_for > dispute.votes_against &&
+        trust_score.reverse_penalty(dispute.penalty_sequence, clock.unix_timestamp);
+
+    if accepted {
+        let member = &mut ctx.accounts.member;
+        member.trust_score = trust_score.score;
+        member.trust_tier = trust_score.tier.clone();
+
+        dispute.status = DisputeStatus::Accepted;
+    } else {
+        dispute.status = DisputeStatus::Rejected;
+    }
+
+    emit!(TrustDisputeResolved {
+        dispute: dispute.key(),
+        disputant: dispute.disputant,
+        kind: dispute.kind,
+        accepted,
+        score: ctx.accounts.trust_score.score,
+    });
+
+    Ok(())
+}
+
+#[event]
+pub struct TrustDisputeFiled {
+    pub dispute: Pubkey,
+    pub circle: Pubkey,
+    pub disputant: Pubkey,
+    pub kind: TrustPenaltyKind,
+    pub penalty_sequence: u32,
+    pub voting_end: i64,
+}
+
+#[event]
+pub struct TrustDisputeResolved {
+    pub dispute: Pubkey,
+    pub disputant: Pubkey,
+    pub kind: TrustPenaltyKind,
+    pub accepted: bool,
+    pub score: u16,
+}
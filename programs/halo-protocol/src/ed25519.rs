@@ -0,0 +1,52 @@
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::ed25519_program;
+use solana_instructions_sysvar::{load_current_index_checked, load_instruction_at_checked};
+
+use crate::errors::HaloError;
+
+/// Size of the signature offsets header that follows the 2-byte preamble
+const SIGNATURE_OFFSETS_LEN: usize = 14;
+/// Instruction index meaning "data lives in the ed25519 instruction itself"
+const CURRENT_INSTRUCTION: u16 = u16::MAX;
+
+/// Read the message and signer of the ed25519 precompile instruction placed
+/// directly before the current instruction. The runtime has already verified
+/// the signature if the transaction got this far; we only check that exactly
+/// one signature is present and that all data is inline.
+pub(crate) fn load_verified_message(instructions_sysvar: &AccountInfo) -> Result<(Pubkey, Vec<u8>)> {
+    let current_index = load_current_index_checked(instructions_sysvar)?;
+    require!(current_index > 0, HaloError::InvalidAttestation);
+
+    let ix = load_instruction_at_checked((current_index - 1) as usize, instructions_sysvar)?;
+    require!(ix.program_id == ed25519_program::ID, HaloError::InvalidAttestation);
+    require!(ix.accounts.is_empty(), HaloError::InvalidAttestation);
+
+    let data = &ix.data;
+    require!(data.len() >= 2 + SIGNATURE_OFFSETS_LEN, HaloError::InvalidAttestation);
+    require!(data[0] == 1, HaloError::InvalidAttestation);
+
+    let read_u16 = |offset: usize| u16::from_le_bytes([data[offset], data[offset + 1]]);
+    let signature_instruction_index = read_u16(4);
+    let public_key_offset = read_u16(6) as usize;
+    let public_key_instruction_index = read_u16(8);
+    let message_offset = read_u16(10) as usize;
+    let message_size = read_u16(12) as usize;
+    let message_instruction_index = read_u16(14);
+
+    require!(
+        signature_instruction_index == CURRENT_INSTRUCTION &&
+        public_key_instruction_index == CURRENT_INSTRUCTION &&
+        message_instruction_index == CURRENT_INSTRUCTION,
+        HaloError::InvalidAttestation
+    );
+
+    let public_key = data
+        .get(public_key_offset..public_key_offset + 32)
+        .ok_or(HaloError::InvalidAttestation)?;
+    let message = data
+        .get(message_offset..message_offset + message_size)
+        .ok_or(HaloError::InvalidAttestation)?;
+
+    let signer = Pubkey::try_from(public_key).map_err(|_| HaloError::InvalidAttestation)?;
+    Ok((signer, message.to_vec()))
+}
@@ -0,0 +1,288 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token::{self, Token, TokenAccount, Transfer};
+
+use crate::errors::HaloError;
+use crate::state::{Circle, CircleEscrow, CircleStatus, Member, MemberStatus, WindDownSnapshot};
+use crate::yield_integration::{redeem_solend_collateral, SolendRedeemAccounts, SOLEND_PROGRAM_ID};
+
+/// Recall funds from yield venues and freeze every member's net position so
+/// the escrow can be shared pro rata. The circle's member accounts must be
+/// passed as remaining accounts, in the order of `circle.members`. Members
+/// removed by governance were refunded their stake at removal.
+pub(crate) fn settle_wind_down(ctx: Context<SettleWindDown>) -> Result<()> {
+    let circle_key = ctx.accounts.circle.key();
+    {
+        let circle = &ctx.accounts.circle;
+        require!(circle.status == CircleStatus::WindingDown, HaloError::CircleNotWindingDown);
+        require!(circle.wind_down.is_none(), HaloError::WindDownAlreadySettled);
+    }
+
+    // Redeem everything deposited with Solend back into the escrow
+    let mut recalled = 0;
+    if ctx.accounts.escrow.solend_c_token_balance > 0 {
+        let accounts = &ctx.accounts;
+        let collateral_account = accounts.solend_collateral_account
+            .as_ref()
+            .ok_or(HaloError::YieldAccountsRequired)?;
+        let (
+            Some(reserve),
+            Some(reserve_collateral_mint),
+            Some(reserve_liquidity_supply),
+            Some(lending_market),
+            Some(lending_market_authority),
+            Some(solend_program),
+        ) = (
+            accounts.solend_reserve.as_ref(),
+            accounts.solend_reserve_collateral_mint.as_ref(),
+            accounts.solend_reserve_liquidity_supply.as_ref(),
+            accounts.solend_lending_market.as_ref(),
+            accounts.solend_lending_market_authority.as_ref(),
+            accounts.solend_program.as_ref(),
+        ) else {
+            return err!(HaloError::YieldAccountsRequired);
+        };
+
+        let circle_key = accounts.circle.key();
+        let escrow_seeds = &[
+            b"escrow",
+            circle_key.as_ref(),
+            &[accounts.escrow.bump],
+        ];
+        let escrow_signer = &[&escrow_seeds[..]];
+
+        let liquidity_before = accounts.escrow_token_account.amount;
+        redeem_solend_collateral(
+            solend_program,
+            SolendRedeemAccounts {
+                source_collateral: &collateral_account.to_account_info(),
+                destination_liquidity: &accounts.escrow_token_account.to_account_info(),
+                reserve,
+                reserve_collateral_mint,
+                reserve_liquidity_supply,
+                lending_market,
+                lending_market_authority,
+                transfer_authority: &accounts.escrow.to_account_info(),
+                token_program: &accounts.token_program.to_account_info(),
+            },
+            collateral_account.amount,
+            escrow_signer,
+        )?;
+
+        ctx.accounts.escrow_token_account.reload()?;
+        recalled = ctx.accounts.escrow_token_account.amount
+            .checked_sub(liquidity_before)
+            .ok_or(HaloError::ArithmeticOverflow)?;
+
+        let escrow = &mut ctx.accounts.escrow;
+        escrow.total_amount = escrow.total_amount
+            .checked_add(recalled)
+            .ok_or(HaloError::ArithmeticOverflow)?;
+        escrow.solend_c_token_balance = 0;
+
+        let circle = &mut ctx.accounts.circle;
+        circle.total_pot = circle.total_pot
+            .checked_add(recalled)
+            .ok_or(HaloError::ArithmeticOverflow)?;
+    }
+
+    // Sum net positions over every current member
+    let circle = &mut ctx.accounts.circle;
+    require!(
+        ctx.remaining_accounts.len() == circle.members.len(),
+        HaloError::InvalidMemberAccounts
+    );
+    let mut total_position: u64 = 0;
+    for (info, authority) in ctx.remaining_accounts.iter().zip(circle.members.iter()) {
+        require!(info.owner == &crate::ID, HaloError::InvalidMemberAccounts);
+        let data = info.try_borrow_data()?;
+        let member = Member::try_deserialize(&mut &data[..])?;
+        require!(
+            member.circle == circle_key && member.authority == *authority,
+            HaloError::InvalidMemberAccounts
+        );
+        total_position = total_position
+            .checked_add(member.net_position())
+            .ok_or(HaloError::ArithmeticOverflow)?;
+    }
+
+    ctx.accounts.escrow_token_account.reload()?;
+    let pool = ctx.accounts.escrow_token_account.amount;
+    let now = Clock::get()?.unix_timestamp;
+    circle.wind_down = Some(WindDownSnapshot {
+        pool,
+        total_position,
+        claimed: 0,
+        settled_at: now,
+    });
+
+    emit!(WindDownSettled {
+        circle: circle_key,
+        pool,
+        total_position,
+        recalled,
+    });
+
+    Ok(())
+}
+
+/// Withdraw a member's pro-rata share of a settled wind-down
+pub(crate) fn claim_wind_down_share(ctx: Context<ClaimWindDownShare>) -> Result<()> {
+    let circle = &mut ctx.accounts.circle;
+    let member = &mut ctx.accounts.member;
+
+    require!(member.status != MemberStatus::Exited, HaloError::WindDownShareClaimed);
+    require!(circle.members.contains(&member.authority), HaloError::MemberNotFound);
+    let snapshot = circle.wind_down.as_mut().ok_or(HaloError::WindDownNotSettled)?;
+
+    let position = member.net_position();
+    let share = snapshot.share_of(position).ok_or(HaloError::ArithmeticOverflow)?;
+    snapshot.claimed = snapshot.claimed
+        .checked_add(share)
+        .ok_or(HaloError::ArithmeticOverflow)?;
+    require!(snapshot.claimed <= snapshot.pool, HaloError::ArithmeticOverflow);
+
+    if share > 0 {
+        let circle_key = circle.key();
+        let escrow_seeds = &[
+            b"escrow",
+            circle_key.as_ref(),
+            &[ctx.accounts.escrow.bump],
+        ];
+        let escrow_signer = &[&escrow_seeds[..]];
+
+        let transfer_instruction = Transfer {
+            from: ctx.accounts.escrow_token_account.to_account_info(),
+            to: ctx.accounts.member_token_account.to_account_info(),
+            authority: ctx.accounts.escrow.to_account_info(),
+        };
+        let cpi_ctx = CpiContext::new_with_signer(
+            ctx.accounts.token_program.to_account_info(),
+            transfer_instruction,
+            escrow_signer,
+        );
+        token::transfer(cpi_ctx, share)?;
+
+        let escrow = &mut ctx.accounts.escrow;
+        escrow.total_amount = escrow.total_amount.saturating_sub(share);
+    }
+
+    circle.total_stake = circle.total_stake.saturating_sub(member.stake_amount);
+    member.status = MemberStatus::Exited;
+    member.stake_amount = 0;
+    member.vouched_stake_locked = 0;
+
+    emit!(WindDownShareClaimed {
+        circle: circle.key(),
+        member: member.authority,
+        position,
+        amount: share,
+    });
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct SettleWindDown<'info> {
+    #[account(mut)]
+    pub circle: Account<'info, Circle>,
+
+    #[account(
+        mut,
+        seeds = [b"escrow", circle.key().as_ref()],
+        bump = escrow.bump
+    )]
+    pub escrow: Account<'info, CircleEscrow>,
+
+    #[account(
+        mut,
+        constraint = escrow_token_account.owner == escrow.key()
+    )]
+    pub escrow_token_account: Account<'info, TokenAccount>,
+
+    /// The escrow's Solend cToken account; this and the other Solend
+    /// accounts are required when the escrow still has funds deposited
+    #[account(
+        mut,
+        constraint = solend_collateral_account.owner == escrow.key() @ HaloError::YieldAccountsRequired
+    )]
+    pub solend_collateral_account: Option<Account<'info, TokenAccount>>,
+
+    /// CHECK: Validated by the Solend program during redemption
+    #[account(mut)]
+    pub solend_reserve: Option<UncheckedAccount<'info>>,
+
+    /// CHECK: Validated by the Solend program during redemption
+    #[account(mut)]
+    pub solend_reserve_collateral_mint: Option<UncheckedAccount<'info>>,
+
+    /// CHECK: Validated by the Solend program during redemption
+    #[account(mut)]
+    pub solend_reserve_liquidity_supply: Option<UncheckedAccount<'info>>,
+
+    /// CHECK: Validated by the Solend program during redemption
+    pub solend_lending_market: Option<UncheckedAccount<'info>>,
+
+    /// CHECK: Validated by the Solend program during redemption
+    pub solend_lending_market_authority: Option<UncheckedAccount<'info>>,
+
+    /// CHECK: Address checked against the Solend program id
+    #[account(address = SOLEND_PROGRAM_ID @ HaloError::YieldAccountsRequired)]
+    pub solend_program: Option<UncheckedAccount<'info>>,
+
+    /// Anyone may settle a circle that governance voted to wind down
+    pub authority: Signer<'info>,
+
+    pub token_program: Program<'info, Token>,
+}
+
+#[derive(Accounts)]
+pub struct ClaimWindDownShare<'info> {
+    #[account(mut)]
+    pub circle: Account<'info, Circle>,
+
+    #[account(
+        mut,
+        seeds = [b"member", circle.key().as_ref(), member_authority.key().as_ref()],
+        bump = member.bump
+    )]
+    pub member: Account<'info, Member>,
+
+    #[account(
+        mut,
+        seeds = [b"escrow", circle.key().as_ref()],
+        bump = escrow.bump
+    )]
+    pub escrow: Account<'info, CircleEscrow>,
+
+    pub member_authority: Signer<'info>,
+
+    #[account(
+        mut,
+        constraint = member_token_account.owner == member_authority.key()
+    )]
+    pub member_token_account: Account<'info, TokenAccount>,
+
+    #[account(
+        mut,
+        constraint = escrow_token_account.owner == escrow.key()
+    )]
+    pub escrow_token_account: Account<'info, TokenAccount>,
+
+    pub token_program: Program<'info, Token>,
+}
+
+#[event]
+pub struct WindDownSettled {
+    pub circle: Pubkey,
+    pub pool: u64,
+    pub total_position: u64,
+    pub recalled: u64,
+}
+
+#[event]
+pub struct WindDownShareClaimed {
+    pub circle: Pubkey,
+    pub member: Pubkey,
+    pub position: u64,
+    pub amount: u64,
+}
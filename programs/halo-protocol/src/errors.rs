@@ -137,4 +137,126 @@ pub enum HaloError {
     NoClaimableYield,
     #[msg("Unauthorized member")]
     UnauthorizedMember,
-}
\ No newline at end of file
+    // Vouching errors
+    #[msg("Voucher trust tier too low")]
+    VoucherTierTooLow,
+    #[msg("Insufficient unlocked stake")]
+    InsufficientUnlockedStake,
+    #[msg("Invalid vouch")]
+    InvalidVouch,
+    #[msg("Vouch cannot be released yet")]
+    VouchNotReleasable,
+    #[msg("Stake is locked by an active vouch")]
+    StakeLockedByVouch,
+    // Trust dispute errors
+    #[msg("Invalid dispute evidence")]
+    InvalidDisputeEvidence,
+    #[msg("No recorded penalty of this kind to dispute")]
+    NothingToDispute,
+    #[msg("Dispute is not open")]
+    DisputeNotOpen,
+    #[msg("Dispute voting has ended")]
+    DisputeVotingEnded,
+    #[msg("Dispute voting is still active")]
+    DisputeVotingActive,
+    #[msg("Not allowed to vote on this dispute")]
+    UnauthorizedDisputeVoter,
+    // Private trust score errors
+    #[msg("Private trust score is not enabled")]
+    PrivacyNotEnabled,
+    #[msg("Invalid encrypted trust score")]
+    InvalidEncryptedScore,
+    #[msg("Invalid trust tier attestation")]
+    InvalidAttestation,
+    #[msg("Trust tier attestation has expired")]
+    AttestationExpired,
+    // Governance configuration errors
+    #[msg("Invalid governance configuration")]
+    InvalidGovernanceConfig,
+    #[msg("Token account does not hold the governance mint")]
+    GovernanceMintMismatch,
+    #[msg("Voting period is still active")]
+    VotingPeriodActive,
+    #[msg("Proposal has not succeeded")]
+    ProposalNotSucceeded,
+    #[msg("Execution timelock has not elapsed")]
+    TimelockNotElapsed,
+    #[msg("Execution timelock has already elapsed")]
+    TimelockElapsed,
+    #[msg("Invalid proposal action")]
+    InvalidProposalAction,
+    #[msg("Account required by the proposal action is missing or wrong")]
+    MissingProposalAccount,
+    // Emergency errors
+    #[msg("Circle is paused by an emergency proposal")]
+    CirclePaused,
+    #[msg("Circle is not winding down")]
+    CircleNotWindingDown,
+    #[msg("Wind-down has already been settled")]
+    WindDownAlreadySettled,
+    #[msg("Wind-down has not been settled yet")]
+    WindDownNotSettled,
+    #[msg("Yield venue accounts are required to recall deposited funds")]
+    YieldAccountsRequired,
+    #[msg("Member accounts do not match the circle's members")]
+    InvalidMemberAccounts,
+    #[msg("Wind-down share already claimed")]
+    WindDownShareClaimed,
+    // Delegation errors
+    #[msg("Invalid vote delegation")]
+    InvalidDelegation,
+    // Automation payout errors
+    #[msg("No payout recipient is scheduled for this round")]
+    NoPayoutRecipient,
+    #[msg("Recipient does not match the scheduled payout recipient")]
+    PayoutRecipientMismatch,
+    // Keeper errors
+    #[msg("Keeper account does not belong to the signer")]
+    UnauthorizedKeeper,
+    // Oracle callback errors
+    #[msg("Oracle queue or function account is not the configured one")]
+    InvalidOracleAccount,
+    #[msg("Oracle callback is not signed by the configured oracle or is malformed")]
+    InvalidOracleCallback,
+    #[msg("Oracle callback was already processed or has expired")]
+    StaleOracleCallback,
+    // Circle automation errors
+    #[msg("Only the circle creator or the automation authority can change a circle's automation")]
+    UnauthorizedAutomationChange,
+    // Revenue ledger errors
+    #[msg("Revenue report period is older than the treasury's epoch history")]
+    RevenueHistoryExpired,
+    #[msg("Yield can only be attributed to a circle when it is paid into the circle's escrow")]
+    YieldRecipientNotCircleEscrow,
+    // Management fee errors
+    #[msg("Management fee batch must list circle, escrow and escrow token account for each circle")]
+    InvalidManagementFeeBatch,
+    // Vouch slashing errors
+    #[msg("Vouched newcomer has not defaulted")]
+    NewcomerNotDefaulted,
+    // Trust dispute errors
+    #[msg("Disputed penalty was not recorded in this circle")]
+    DisputedPenaltyNotInCircle,
+    // Auction payout errors
+    #[msg("Auction payouts require the settled auction for the round being paid")]
+    AuctionRequiredForPayout,
+    #[msg("Auction was held for a different round")]
+    AuctionRoundMismatch,
+    // Keeper report errors
+    #[msg("Crank slot is still due; only cranks for served slots can be reported")]
+    CrankSlotStillDue,
+    // Treasury migration errors
+    #[msg("Treasury predates revenue epochs; run migrate_treasury first")]
+    TreasuryNotMigrated,
+    // Circle migration errors
+    #[msg("Circle already tracks its stake and management fee timer")]
+    CircleAlreadyMigrated,
+    // Treasury token account errors
+    #[msg("Token account is not owned by the treasury")]
+    InvalidTreasuryTokenAccount,
+    // Management fee collection errors
+    #[msg("Circle predates per-circle management fees; run migrate_circle_stake first")]
+    CircleNotMigrated,
+    #[msg("Escrow token account must be owned by the circle's escrow and hold the treasury's mint")]
+    InvalidEscrowTokenAccount,
+}
@@ -1,9 +1,18 @@
 use anchor_lang::prelude::*;
-use anchor_spl::token::{self, Token, TokenAccount, Transfer};
+use anchor_lang::solana_program::program_option::COption;
+use anchor_spl::token::{self, Approve, Revoke, Token, TokenAccount, Transfer};
 
 use crate::errors::HaloError;
-use crate::state::{Circle, Member, CircleEscrow, CircleStatus, MemberStatus, MonthlyContribution, MemberContribution, TrustScore, TrustTier, SocialProof, AutomationState, CircleAutomation, AutomationEvent, AutomationEventType, Treasury, RevenueParams, GovernanceProposal, Vote, Auction, Bid, ProposalType, ProposalStatus, AuctionStatus, PayoutMethod};
+use crate::state::{Circle, Member, CircleEscrow, CircleStatus, MemberStatus, MonthlyContribution, MemberContribution, TrustScore, TrustTier, TrustPenaltyKind, TrustTierThresholds, TrustCredential, EncryptedTrustScore, PrivacyConfig, SocialProof, AutomationState, CircleAutomation, AutomationLog, AutomationRecord, AutomationOutcome, AutomationEventType, DueRound, PenaltyOutcome, Treasury, RevenueParams, CircleFeeLedger, GovernanceProposal, Vote, DelegatedPower, Auction, Bid, ProposalType, ProposalStatus, ProposalAction, GovernanceConfig, VotingPowerSource, TallyMode, AuctionStatus, PayoutMethod};
+use crate::math::{self, Rounding};
 use crate::revenue;
+use crate::credential::TrustCredentialSynced;
+use crate::vouching::{Vouch, VouchStatus};
+use crate::privacy::verify_tier_claim;
+use crate::delegation::{collect_delegated_power, member_voting_power, snapshot_token_balances};
+use crate::protocol_governance::ProtocolRealm;
+use crate::keeper::{reward_crank, CrankClaim, Keeper, KeeperBudget};
+use solana_instructions_sysvar::ID as INSTRUCTIONS_SYSVAR_ID;
 
 pub(crate) fn initialize_circle(
     ctx: Context<InitializeCircle>,
@@ -48,6 +57,18 @@ pub(crate) fn initialize_circle(
     circle.escrow_account = ctx.accounts.escrow.key();
     circle.total_yield_earned = 0;
     circle.next_payout_recipient = None;
+    circle.proposal_count = 0;
+    circle.governance = GovernanceConfig::default();
+    circle.grace_period_days = Circle::DEFAULT_GRACE_PERIOD_DAYS;
+    circle.emergency_paused = false;
+    circle.wind_down = None;
+    circle.fee_ledger = CircleFeeLedger {
+        last_management_fee_at: clock.unix_timestamp,
+        ..CircleFeeLedger::default()
+    };
+    circle.total_stake = 0;
+    circle.paused_at = 0;
+    circle.paused_seconds = 0;
 
     // Initialize escrow
     let escrow = &mut ctx.accounts.escrow;
@@ -91,6 +112,22 @@ pub(crate) fn join_circle(ctx: Context<JoinCircle>, stake_amount: u64) -> Result
             .and_then(|v| v.checked_div(100))
             .ok_or(HaloError::ArithmeticOverflow)?;
         (trust_score, trust_tier, min_stake)
+    } else if let Some(encrypted_trust_score) = &ctx.accounts.encrypted_trust_score {
+        // Private score: an attested claim proves the tier, the raw score stays hidden
+        let privacy_config = ctx.accounts.privacy_config.as_ref().ok_or(HaloError::InvalidAttestation)?;
+        let instructions_sysvar = ctx.accounts.instructions_sysvar.as_ref().ok_or(HaloError::InvalidAttestation)?;
+        let trust_tier = verify_tier_claim(
+            encrypted_trust_score,
+            privacy_config,
+            instructions_sysvar,
+            ctx.accounts.member_authority.key(),
+            clock.unix_timestamp,
+        )?;
+        let min_stake = circle.contribution_amount
+            .checked_mul(trust_tier.minimum_stake_multiplier())
+            .and_then(|v| v.checked_div(100))
+            .ok_or(HaloError::ArithmeticOverflow)?;
+        (0, trust_tier, min_stake)
     } else {
         // Default for new users without trust score
         let default_stake = circle.contribution_amount
@@ -99,6 +136,17 @@ pub(crate) fn join_circle(ctx: Context<JoinCircle>, stake_amount: u64) -> Result
         (0, TrustTier::Newcomer, default_stake) // 2x stake for newcomers
     };
 
+    require!(trust_tier.as_u8() >= circle.min_trust_tier, HaloError::InsufficientTrustScore);
+
+    // A guarantor's locked stake covers part of the requirement
+    let (minimum_stake_required, vouched_by) = if let Some(vouch) = &mut ctx.accounts.vouch {
+        require!(vouch.status == VouchStatus::Pending, HaloError::InvalidVouch);
+        vouch.status = VouchStatus::Active;
+        (minimum_stake_required.saturating_sub(vouch.locked_amount), Some(vouch.voucher))
+    } else {
+        (minimum_stake_required, None)
+    };
+
     require!(stake_amount >= minimum_stake_required, HaloError::InsufficientStake);
 
     // Transfer stake to escrow
@@ -132,6 +180,10 @@ pub(crate) fn join_circle(ctx: Context<JoinCircle>, stake_amount: u64) -> Result
     member_account.payout_position = circle.current_members; // Position in order they join
     member_account.insurance_staked = 0;
     member_account.contribution_records = Vec::new();
+    member_account.vouched_stake_locked = 0;
+    member_account.vouched_by = vouched_by;
+    member_account.payouts_received = 0;
+    member_account.auto_contribution_rounds = 0;
 
     // Add member to circle
     circle.members.push(ctx.accounts.member_authority.key());
@@ -142,33 +194,96 @@ pub(crate) fn join_circle(ctx: Context<JoinCircle>, stake_amount: u64) -> Result
     // Update escrow
     let escrow = &mut ctx.accounts.escrow;
     escrow.total_amount = escrow.total_amount.checked_add(stake_amount).ok_or(HaloError::ArithmeticOverflow)?;
+    circle.total_stake = circle.total_stake.checked_add(stake_amount).ok_or(HaloError::ArithmeticOverflow)?;
 
     msg!("Member {} joined circle with trust tier {:?}", ctx.accounts.member_authority.key(), member_account.trust_tier);
     Ok(())
 }
 
+/// Record a contribution for `month` in the member, circle and escrow books.
+/// Shared by manual contributions and automated collection.
+pub(crate) fn record_contribution(
+    circle: &mut Circle,
+    escrow: &mut CircleEscrow,
+    member: &mut Member,
+    month: u8,
+    amount: u64,
+    timestamp: i64,
+) -> Result<()> {
+    // Governance may have extended the circle since this member joined
+    let duration = circle.duration_months as usize;
+    if member.contribution_history.len() < duration {
+        member.contribution_history.resize(duration, 0);
+    }
+    if escrow.monthly_pots.len() < duration {
+        escrow.monthly_pots.resize(duration, 0);
+    }
+
+    // Check if already contributed for this month
+    require!(
+        month < member.contribution_history.len() as u8 &&
+        member.contribution_history[month as usize] == 0,
+        HaloError::ContributionAlreadyMade
+    );
+
+    // Record contribution
+    member.contribution_history[month as usize] = amount;
+
+    // Ensure we have a monthly contribution record up to this month
+    while circle.monthly_contributions.len() <= month as usize {
+        let next_month = circle.monthly_contributions.len() as u8;
+        circle.monthly_contributions.push(MonthlyContribution {
+            month: next_month,
+            contributions: Vec::new(),
+            total_collected: 0,
+            distributed_to: None,
+        });
+    }
+
+    let monthly_contrib = &mut circle.monthly_contributions[month as usize];
+    monthly_contrib.contributions.push(MemberContribution {
+        member: member.authority,
+        amount,
+        timestamp,
+    });
+    monthly_contrib.total_collected = monthly_contrib.total_collected
+        .checked_add(amount)
+        .ok_or(HaloError::ArithmeticOverflow)?;
+
+    // Update totals
+    circle.total_pot = circle.total_pot.checked_add(amount).ok_or(HaloError::ArithmeticOverflow)?;
+    escrow.total_amount = escrow.total_amount.checked_add(amount).ok_or(HaloError::ArithmeticOverflow)?;
+    escrow.monthly_pots[month as usize] = escrow.monthly_pots[month as usize]
+        .checked_add(amount)
+        .ok_or(HaloError::ArithmeticOverflow)?;
+
+    Ok(())
+}
+
 pub(crate) fn contribute(ctx: Context<Contribute>, amount: u64) -> Result<()> {
     let circle = &mut ctx.accounts.circle;
     let member = &mut ctx.accounts.member;
     let clock = Clock::get()?;
 
     require!(circle.status == CircleStatus::Active, HaloError::CircleNotActive);
+    require!(!circle.emergency_paused, HaloError::CirclePaused);
     require!(member.status == MemberStatus::Active, HaloError::MemberInDefault);
     require!(amount == circle.contribution_amount, HaloError::InvalidContributionAmount);
 
-    // Calculate current month (simple implementation - in practice would use more sophisticated time calculation)
-    let months_since_creation = ((clock.unix_timestamp - circle.created_at) / (30 * 24 * 60 * 60)) as u8;
-    let current_month = std::cmp::min(months_since_creation, circle.duration_months - 1);
+    // Calculate current month, not counting time spent paused
+    let current_month = circle.month_at(clock.unix_timestamp);
     
     // Update circle's current month
     circle.current_month = current_month;
 
-    // Check if already contributed for this month
-    require!(
-        current_month < member.contribution_history.len() as u8 && 
-        member.contribution_history[current_month as usize] == 0,
-        HaloError::ContributionAlreadyMade
-    );
+    record_contribution(
+        circle,
+        &mut ctx.accounts.escrow,
+        member,
+        current_month,
+        amount,
+        clock.unix_timestamp,
+    )?;
 
     // Transfer contribution to escrow
     let cpi_accounts = Transfer {
@@ -180,9 +295,6 @@ pub(crate) fn contribute(ctx: Context<Contribute>, amount: u64) -> Result<()> {
     let cpi_ctx = CpiContext::new(cpi_program, cpi_accounts);
     token::transfer(cpi_ctx, amount)?;
 
-    // Record contribution
-    member.contribution_history[current_month as usize] = amount;
-
     // Update member's trust score data if trust score account is available
     if let Some(trust_score_account) = &mut ctx.accounts.trust_score {
         trust_score_account.total_contributions = trust_score_account.total_contributions
@@ -198,64 +310,30 @@ pub(crate) fn contribute(ctx: Context<Contribute>, amount: u64) -> Result<()> {
         member.trust_tier = trust_score_account.tier.clone();
     }
 
-    // Update or create monthly contribution record
-    if let Some(monthly_contrib) = circle.monthly_contributions.get_mut(current_month as usize) {
-        monthly_contrib.contributions.push(MemberContribution {
-            member: member.authority,
-            amount,
-            timestamp: clock.unix_timestamp,
-        });
-        monthly_contrib.total_collected = monthly_contrib.total_collected
-            .checked_add(amount)
-            .ok_or(HaloError::ArithmeticOverflow)?;
-    } else {
-        // Ensure we have enough space in the vector
-        let current_len = circle.monthly_contributions.len();
-        while current_len <= current_month as usize {
-            circle.monthly_contributions.push(MonthlyContribution {
-                month: current_len as u8,
-                contributions: Vec::new(),
-                total_collected: 0,
-                distributed_to: None,
-            });
-        }
-        
-        let monthly_contrib = &mut circle.monthly_contributions[current_month as usize];
-        monthly_contrib.contributions.push(MemberContribution {
-            member: member.authority,
-            amount,
-            timestamp: clock.unix_timestamp,
-        });
-        monthly_contrib.total_collected = amount;
-    }
-
-    // Update totals
-    circle.total_pot = circle.total_pot.checked_add(amount).ok_or(HaloError::ArithmeticOverflow)?;
-    let escrow = &mut ctx.accounts.escrow;
-    escrow.total_amount = escrow.total_amount.checked_add(amount).ok_or(HaloError::ArithmeticOverflow)?;
-    escrow.monthly_pots[current_month as usize] = escrow.monthly_pots[current_month as usize]
-        .checked_add(amount)
-        .ok_or(HaloError::ArithmeticOverflow)?;
-
     msg!("Contribution of {} made by {} for month {}", amount, member.authority, current_month);
     Ok(())
 }
 
 pub(crate) fn distribute_pot(ctx: Context<DistributePot>) -> Result<()> {
-    let circle = &mut ctx.accounts.circle;
-    let recipient_member = &mut ctx.accounts.recipient_member;
-    let escrow = &mut ctx.accounts.escrow;
-    let treasury = &mut ctx.accounts.treasury;
-    let revenue_params = &ctx.accounts.revenue_params;
+    let month = ctx.accounts.circle.month_at(Clock::get()?.unix_timestamp);
+    pay_out_pot(ctx.accounts, month)?;
+    Ok(())
+}
+
+/// Pay `current_month`'s pot, less the distribution fee, to the recipient.
+/// Shared by manual and automated distribution. Returns the net amount paid.
+pub(crate) fn pay_out_pot(accounts: &mut DistributePot, current_month: u8) -> Result<u64> {
+    let circle = &mut accounts.circle;
+    let recipient_member = &mut accounts.recipient_member;
+    let escrow = &mut accounts.escrow;
+    let treasury = &mut accounts.treasury;
+    let revenue_params = &accounts.revenue_params;
     let clock = Clock::get()?;
 
     require!(circle.status == CircleStatus::Active, HaloError::CircleNotActive);
+    require!(!circle.emergency_paused, HaloError::CirclePaused);
     require!(!recipient_member.has_received_pot, HaloError::MemberAlreadyReceivedPot);
 
-    // Calculate current month
-    let months_since_creation = ((clock.unix_timestamp - circle.created_at) / (30 * 24 * 60 * 60)) as u8;
-    let current_month = std::cmp::min(months_since_creation, circle.duration_months - 1);
-
     require!(current_month < circle.monthly_contributions.len() as u8, HaloError::NoContributionsToDistribute);
 
     let pot_amount = {
@@ -265,8 +343,13 @@ pub(crate) fn distribute_pot(ctx: Context<DistributePot>) -> Result<()> {
         monthly_contrib.total_collected
     };
 
-    // Calculate distribution fee (0.5% by default)
-    let distribution_fee = revenue_params.calculate_distribution_fee(pot_amount)?;
+    // Calculate distribution fee (0.5% by default), discounted by the recipient's tier
+    let recipient_tier = accounts.recipient_trust_score
+        .as_ref()
+        .map(|trust_score| trust_score.tier.clone())
+        .unwrap_or_default();
+    let (distribution_fee, fee_discount) =
+        revenue_params.calculate_discounted_distribution_fee(pot_amount, &recipient_tier)?;
     let net_distribution_amount = pot_amount.checked_sub(distribution_fee)
         .ok_or(HaloError::ArithmeticOverflow)?;
 
@@ -282,24 +365,29 @@ pub(crate) fn distribute_pot(ctx: Context<DistributePot>) -> Result<()> {
     // Collect distribution fee first (if any)
     if distribution_fee > 0 {
         revenue::collect_distribution_fee(
-            pot_amount,
-            revenue_params,
+            distribution_fee,
             treasury,
-            &ctx.accounts.escrow_token_account.to_account_info(),
-            &ctx.accounts.treasury_token_account.to_account_info(),
+            &accounts.escrow_token_account.to_account_info(),
+            &accounts.treasury_token_account.to_account_info(),
             &escrow.to_account_info(),
-            &ctx.accounts.token_program.to_account_info(),
+            &accounts.token_program.to_account_info(),
             Some(escrow_signer),
         )?;
     }
+    revenue::record_fee_activity(
+        treasury,
+        Some(&mut circle.fee_ledger),
+        revenue::FeeActivity::Distribution { amount: pot_amount, fee: distribution_fee },
+        clock.unix_timestamp,
+    )?;
 
     // Transfer remaining pot to recipient
     let cpi_accounts = Transfer {
-        from: ctx.accounts.escrow_token_account.to_account_info(),
-        to: ctx.accounts.recipient_token_account.to_account_info(),
+        from: accounts.escrow_token_account.to_account_info(),
+        to: accounts.recipient_token_account.to_account_info(),
         authority: escrow.to_account_info(),
     };
-    let cpi_program = ctx.accounts.token_program.to_account_info();
+    let cpi_program = accounts.token_program.to_account_info();
     let cpi_ctx = CpiContext::new_with_signer(cpi_program, cpi_accounts, escrow_signer);
     token::transfer(cpi_ctx, net_distribution_amount)?;
 
@@ -307,6 +395,9 @@ pub(crate) fn distribute_pot(ctx: Context<DistributePot>) -> Result<()> {
     let monthly_contrib = &mut circle.monthly_contributions[current_month as usize];
     monthly_contrib.distributed_to = Some(recipient_member.authority);
     recipient_member.has_received_pot = true;
+    recipient_member.payouts_received = recipient_member.payouts_received
+        .checked_add(net_distribution_amount)
+        .ok_or(HaloError::ArithmeticOverflow)?;
     escrow.total_amount = escrow.total_amount.checked_sub(pot_amount).ok_or(HaloError::ArithmeticOverflow)?;
 
     // Check if circle is completed
@@ -317,9 +408,18 @@ pub(crate) fn distribute_pot(ctx: Context<DistributePot>) -> Result<()> {
         // in a separate instruction due to Anchor account constraints
     }
 
+    emit!(PotDistributed {
+        circle: circle_key,
+        recipient: recipient_member.authority,
+        month: current_month,
+        amount: net_distribution_amount,
+        fee: distribution_fee,
+        fee_discount,
+    });
+
     msg!("Pot of {} distributed to {} (fee: {}, net: {}) for month {}", 
          pot_amount, recipient_member.authority, distribution_fee, net_distribution_amount, current_month);
-    Ok(())
+    Ok(net_distribution_amount)
 }
 
 pub(crate) fn claim_penalty(ctx: Context<ClaimPenalty>) -> Result<()> {
@@ -367,17 +467,23 @@ pub(crate) fn leave_circle(ctx: Context<LeaveCircle>) -> Result<()> {
     let clock = Clock::get()?;
 
     require!(circle.status != CircleStatus::Completed, HaloError::CircleEnded);
+    // Once a wind-down is settled, members withdraw through their share instead
+    require!(circle.wind_down.is_none(), HaloError::WindDownAlreadySettled);
 
     // Calculate if member can leave (simplified - in practice would have more complex rules)
-    let months_since_creation = ((clock.unix_timestamp - circle.created_at) / (30 * 24 * 60 * 60)) as u8;
-    let current_month = std::cmp::min(months_since_creation, circle.duration_months - 1);
+    let current_month = circle.month_at(clock.unix_timestamp);
 
-    // Only allow leaving if circle hasn't started active contributions or if member is in default
+    // Only allow leaving if circle hasn't started active contributions, if member is in default,
+    // or if governance removed the member
+    let removed = member.status == MemberStatus::Removed;
     require!(
-        current_month == 0 || member.status == MemberStatus::Defaulted,
+        current_month == 0 || member.status == MemberStatus::Defaulted || removed,
         HaloError::CannotLeaveActivePeriod
     );
 
+    // Stake guaranteeing a newcomer cannot be withdrawn
+    require!(member.vouched_stake_locked == 0, HaloError::StakeLockedByVouch);
+
     // Return stake if available
     if member.stake_amount > 0 {
         let escrow = &mut ctx.accounts.escrow;
@@ -399,6 +505,18 @@ pub(crate) fn leave_circle(ctx: Context<LeaveCircle>) -> Result<()> {
         token::transfer(cpi_ctx, member.stake_amount)?;
 
         escrow.total_amount = escrow.total_amount.checked_sub(member.stake_amount).ok_or(HaloError::ArithmeticOverflow)?;
+        circle.total_stake = circle.total_stake.saturating_sub(member.stake_amount);
+    }
+
+    // Every exit before completion counts against the member's trust score,
+    // and leaving with penalties still owed is recorded separately
+    // A governance removal is not a voluntary exit
+    let trust_score = &mut ctx.accounts.trust_score;
+    if !removed {
+        apply_trust_penalty(trust_score, member, TrustPenaltyKind::EarlyExit, clock.unix_timestamp);
+    }
+    if member.penalties > 0 {
+        apply_trust_penalty(trust_score, member, TrustPenaltyKind::UnpaidPenalty, clock.unix_timestamp);
     }
 
     // Update member status
@@ -434,6 +552,14 @@ pub(crate) fn initialize_trust_score(ctx: Context<InitializeTrustScore>) -> Resu
     trust_score.social_proofs = Vec::new();
     trust_score.last_updated = clock.unix_timestamp;
     trust_score.bump = ctx.bumps.trust_score;
+    trust_score.guarantor_defaults = 0;
+    trust_score.defaults = 0;
+    trust_score.slashings = 0;
+    trust_score.early_exits = 0;
+    trust_score.unpaid_penalties = 0;
+    trust_score.penalty_sequence = 0;
+    trust_score.penalty_records = Vec::new();
+    trust_score.tier_thresholds = TrustTierThresholds::default();
 
     msg!("Trust score initialized for {}", ctx.accounts.authority.key());
     Ok(())
@@ -444,14 +570,52 @@ pub(crate) fn update_trust_score(ctx: Context<UpdateTrustScore>) -> Result<()> {
     let clock = Clock::get()?;
 
     // Recalculate trust score based on current data
+    if let Some(realm) = &ctx.accounts.protocol_realm {
+        trust_score.tier_thresholds = realm.trust_tier_thresholds.clone();
+    }
     trust_score.calculate_score();
     trust_score.last_updated = clock.unix_timestamp;
 
+    // Keep the soulbound credential in sync if the user opted into one
+    if let Some(credential) = &mut ctx.accounts.trust_credential {
+        credential.sync(trust_score, clock.unix_timestamp);
+
+        emit!(TrustCredentialSynced {
+            authority: credential.authority,
+            schema_version: credential.schema_version,
+            tier: credential.tier,
+            score: credential.score,
+            updated_at: credential.updated_at,
+        });
+    }
+
     msg!("Trust score updated for {}: {} points, tier {:?}", 
          trust_score.authority, trust_score.score, trust_score.tier);
     Ok(())
 }
 
+/// Record a negative outcome against a member's trust score, refresh the
+/// values cached on the member and emit `TrustPenaltyApplied`.
+pub(crate) fn apply_trust_penalty(
+    trust_score: &mut TrustScore,
+    member: &mut Member,
+    kind: TrustPenaltyKind,
+    now: i64,
+) {
+    let sequence = trust_score.record_penalty(kind, member.circle, now);
+    member.trust_score = trust_score.score;
+    member.trust_tier = trust_score.tier.clone();
+
+    emit!(TrustPenaltyApplied {
+        authority: trust_score.authority,
+        circle: member.circle,
+        kind,
+        sequence,
+        score: trust_score.score,
+        tier: trust_score.tier.as_u8(),
+    });
+}
+
 pub(crate) fn add_social_proof(
     ctx: Context<AddSocialProof>,
     proof_type: String,
@@ -557,6 +721,8 @@ pub(crate) fn initialize_automation_state(
     automation_state.min_interval = min_interval;
     automation_state.last_check = Clock::get()?.unix_timestamp;
     automation_state.bump = ctx.bumps.automation_state;
+    automation_state.oracle_program = *ctx.accounts.switchboard_queue.owner;
+    automation_state.oracle_signer = Pubkey::default();
     
     msg!("Automation state initialized with min_interval: {}", min_interval);
     Ok(())
@@ -567,10 +733,10 @@ pub(crate) fn setup_circle_automation(
     auto_collect: bool,
     auto_distribute: bool,
     auto_penalty: bool,
+    catch_up: bool,
 ) -> Result<()> {
     let circle_automation = &mut ctx.accounts.circle_automation;
     let circle = &ctx.accounts.circle;
-    let _clock = Clock::get()?;
 
     circle_automation.circle = circle.key();
     circle_automation.job_account = ctx.accounts.switchboard_job.key();
@@ -597,84 +763,450 @@ pub(crate) fn setup_circle_automation(
     circle_automation.last_distribution_check = 0;
     circle_automation.last_penalty_check = 0;
     circle_automation.bump = ctx.bumps.circle_automation;
+    circle_automation.last_rewarded_slots = [0; 3];
+    circle_automation.catch_up_enabled = catch_up;
+    circle_automation.paused_at = 0;
+    circle_automation.paused_seconds = 0;
+    circle_automation.last_callback_at = 0;
+    circle_automation.last_callback_run = 0;
+    // Schedules start from creation; push them past any pauses so far
+    circle_automation.sync_with_circle(circle);
+
+    let automation_log = &mut ctx.accounts.automation_log;
+    automation_log.circle = circle.key();
+    automation_log.head = 0;
+    automation_log.total_records = 0;
+    automation_log.records = Vec::new();
+    automation_log.bump = ctx.bumps.automation_log;
     
     // Update global automation state
-    let automation_state = &mut ctx.accounts.automation_state;
-    automation_state.active_jobs = automation_state.active_jobs
-        .checked_add(1)
-        .ok_or(HaloError::ArithmeticOverflow)?;
+    track_active_job(&mut ctx.accounts.automation_state, false, circle_automation.is_active())?;
     
     msg!("Circle automation setup for circle: {}", circle.key());
     Ok(())
 }
 
-pub(crate) fn automated_contribution_collection(
-    ctx: Context<AutomatedContributionCollection>,
-    _event_timestamp: i64,
+/// Keep `AutomationState::active_jobs` in step with a circle whose
+/// automation went from `was_active` to `is_active`
+fn track_active_job(automation_state: &mut AutomationState, was_active: bool, is_active: bool) -> Result<()> {
+    automation_state.active_jobs = match (was_active, is_active) {
+        (false, true) => automation_state.active_jobs
+            .checked_add(1)
+            .ok_or(HaloError::ArithmeticOverflow)?,
+        (true, false) => automation_state.active_jobs
+            .checked_sub(1)
+            .ok_or(HaloError::ArithmeticOverflow)?,
+        _ => automation_state.active_jobs,
+    };
+    Ok(())
+}
+
+/// Change which tasks a circle automates, its catch-up mode or the job
+/// account driving it. Turning every task off pauses the circle's automation
+/// without losing its schedule or log.
+pub(crate) fn update_circle_automation(
+    ctx: Context<UpdateCircleAutomation>,
+    auto_collect: Option<bool>,
+    auto_distribute: Option<bool>,
+    auto_penalty: Option<bool>,
+    catch_up: Option<bool>,
+) -> Result<()> {
+    let circle_automation = &mut ctx.accounts.circle_automation;
+    let was_active = circle_automation.is_active();
+
+    if let Some(enabled) = auto_collect {
+        circle_automation.auto_collect_enabled = enabled;
+    }
+    if let Some(enabled) = auto_distribute {
+        circle_automation.auto_distribute_enabled = enabled;
+    }
+    if let Some(enabled) = auto_penalty {
+        circle_automation.auto_penalty_enabled = enabled;
+    }
+    if let Some(enabled) = catch_up {
+        circle_automation.catch_up_enabled = enabled;
+    }
+    if let Some(switchboard_job) = &ctx.accounts.switchboard_job {
+        circle_automation.job_account = switchboard_job.key();
+    }
+
+    track_active_job(&mut ctx.accounts.automation_state, was_active, circle_automation.is_active())?;
+
+    msg!("Circle automation updated for circle {}: collect={}, distribute={}, penalty={}, catch_up={}",
+         circle_automation.circle,
+         circle_automation.auto_collect_enabled,
+         circle_automation.auto_distribute_enabled,
+         circle_automation.auto_penalty_enabled,
+         circle_automation.catch_up_enabled);
+    Ok(())
+}
+
+/// Remove a circle's automation, closing its config and log
+pub(crate) fn teardown_circle_automation(ctx: Context<TeardownCircleAutomation>) -> Result<()> {
+    let was_active = ctx.accounts.circle_automation.is_active();
+    track_active_job(&mut ctx.accounts.automation_state, was_active, false)?;
+
+    msg!("Circle automation torn down for circle {}", ctx.accounts.circle.key());
+    Ok(())
+}
+
+/// Approve the circle escrow to pull `rounds` future contributions from the
+/// member's token account during automated collection
+pub(crate) fn enable_auto_contribution(ctx: Context<EnableAutoContribution>, rounds: u8) -> Result<()> {
+    let circle = &ctx.accounts.circle;
+    let member = &mut ctx.accounts.member;
+
+    require!(circle.status == CircleStatus::Active, HaloError::CircleNotActive);
+    require!(member.status == MemberStatus::Active, HaloError::MemberInDefault);
+    require!(rounds > 0 && rounds <= circle.duration_months, HaloError::InvalidAutomationConfig);
+
+    let allowance = circle.contribution_amount
+        .checked_mul(rounds as u64)
+        .ok_or(HaloError::ArithmeticOverflow)?;
+
+    let cpi_accounts = Approve {
+        to: ctx.accounts.member_token_account.to_account_info(),
+        delegate: ctx.accounts.escrow.to_account_info(),
+        authority: ctx.accounts.member_authority.to_account_info(),
+    };
+    let cpi_ctx = CpiContext::new(ctx.accounts.token_program.to_account_info(), cpi_accounts);
+    token::approve(cpi_ctx, allowance)?;
+
+    member.auto_contribution_rounds = rounds;
+
+    msg!("Member {} approved {} automated contributions ({})", member.authority, rounds, allowance);
+    Ok(())
+}
+
+/// Revoke the escrow's approval and stop automated collection for the member
+pub(crate) fn disable_auto_contribution(ctx: Context<DisableAutoContribution>) -> Result<()> {
+    let cpi_accounts = Revoke {
+        source: ctx.accounts.member_token_account.to_account_info(),
+        authority: ctx.accounts.member_authority.to_account_info(),
+    };
+    let cpi_ctx = CpiContext::new(ctx.accounts.token_program.to_account_info(), cpi_accounts);
+    token::revoke(cpi_ctx)?;
+
+    let member = &mut ctx.accounts.member;
+    member.auto_contribution_rounds = 0;
+
+    msg!("Automated contributions disabled for member {}", member.authority);
+    Ok(())
+}
+
+/// Recompute a circle's automation schedules from its current timing and
+/// announce the change, if there was one
+fn regenerate_schedule(circle_automation: &mut CircleAutomation, circle: &Circle) {
+    if circle_automation.sync_with_circle(circle) {
+        emit!(AutomationScheduleRegenerated {
+            circle: circle_automation.circle,
+            rounds: circle_automation.contribution_schedule.len() as u8,
+            paused: circle_automation.paused_at != 0,
+            paused_seconds: circle_automation.paused_seconds,
+        });
+    }
+}
+
+/// Append a record to a circle's automation log and emit it for indexers
+fn log_automation(
+    automation_log: &mut AutomationLog,
+    event_type: AutomationEventType,
+    round: u8,
+    outcome: AutomationOutcome,
+    amount: u64,
+    timestamp: i64,
+) {
+    let sequence = automation_log.append(AutomationRecord {
+        event_type: event_type.clone(),
+        round,
+        outcome,
+        amount,
+        timestamp,
+    });
+    emit!(AutomationLogged {
+        circle: automation_log.circle,
+        sequence,
+        event_type,
+        round,
+        outcome,
+        amount,
+        timestamp,
+    });
+}
+
+/// Surface due rounds a crank passed over to reach `round`, logging each
+/// as skipped
+fn report_skipped_rounds(
+    automation_log: &mut AutomationLog,
+    task: AutomationEventType,
+    round: &DueRound,
+    now: i64,
+) {
+    if round.skipped == 0 {
+        return;
+    }
+    let first_skipped_month = round.month - round.skipped;
+    for month in first_skipped_month..round.month {
+        log_automation(automation_log, task.clone(), month, AutomationOutcome::Skipped, 0, now);
+    }
+    emit!(AutomationRoundsSkipped {
+        circle: automation_log.circle,
+        task,
+        first_skipped_month,
+        skipped: round.skipped,
+        processed_month: round.month,
+    });
+}
+
+/// Bring a circle's automation schedules up to date after its duration
+/// changed or it was paused or resumed. Anyone may call this; the automated
+/// tasks also do it before picking their next round.
+pub(crate) fn sync_automation_schedule(ctx: Context<SyncAutomationSchedule>) -> Result<()> {
+    regenerate_schedule(&mut ctx.accounts.circle_automation, &ctx.accounts.circle);
+
+    msg!("Automation schedule synced for circle {}", ctx.accounts.circle.key());
+    Ok(())
+}
+
+/// Pull the current round's contribution from every opted-in member.
+///
+/// Remaining accounts come in triples of `[member, member_token_account,
+/// trust_score]`, all writable, one per member in the order of
+/// `circle.members`. Members who already paid this round or never opted in
+/// are skipped; the latter are left to penalty enforcement. An opted-in
+/// member whose approval has run out or whose balance is too low is recorded
+/// as missed and the batch carries on. Trust scores and the values cached on
+/// members are updated as in `contribute`.
+///
+/// The round is logged with the amount collected, as `Missed` if anyone missed.
+pub(crate) fn automated_contribution_collection<'info>(
+    ctx: Context<'_, '_, '_, 'info, AutomatedContributionCollection<'info>>,
 ) -> Result<()> {
     let circle_automation = &mut ctx.accounts.circle_automation;
+    let circle = &mut ctx.accounts.circle;
+    let escrow = &mut ctx.accounts.escrow;
     let clock = Clock::get()?;
     
     // Verify automation is enabled and it's time to collect
     require!(circle_automation.auto_collect_enabled, HaloError::AutomationDisabled);
+    regenerate_schedule(circle_automation, circle);
+    let round = circle_automation
+        .next_due_round(&AutomationEventType::ContributionCollection, clock.unix_timestamp)
+        .ok_or(HaloError::AutomationNotScheduled)?;
+    require!(circle.status == CircleStatus::Active, HaloError::CircleNotActive);
+    require!(!circle.emergency_paused, HaloError::CirclePaused);
+    let groups = ctx.remaining_accounts.chunks_exact(3);
     require!(
-        circle_automation.should_collect_contributions(clock.unix_timestamp),
-        HaloError::AutomationNotScheduled
+        groups.remainder().is_empty() && groups.len() == circle.members.len(),
+        HaloError::InvalidMemberAccounts
     );
     
-    // Update last check time
-    circle_automation.last_contribution_check = clock.unix_timestamp;
-    
-    // Log automation event
-    let automation_event = &mut ctx.accounts.automation_event;
-    automation_event.circle = circle_automation.circle;
-    automation_event.event_type = AutomationEventType::ContributionCollection;
-    automation_event.timestamp = clock.unix_timestamp;
-    automation_event.success = true;
-    automation_event.data = Vec::new();
-    automation_event.error_message = None;
-    automation_event.bump = ctx.bumps.automation_event;
+    // Mark the round served so the next crank moves on to the one after it
+    circle_automation.last_contribution_check = round.scheduled_at;
+    let automation_log = &mut ctx.accounts.automation_log;
+    report_skipped_rounds(automation_log, AutomationEventType::ContributionCollection, &round, clock.unix_timestamp);
+
+    let current_month = round.month;
+    circle.current_month = circle.current_month.max(current_month);
+
+    let circle_key = circle.key();
+    let escrow_key = escrow.key();
+    let escrow_bump = escrow.bump;
+    let escrow_seeds = &[
+        b"escrow",
+        circle_key.as_ref(),
+        &[escrow_bump],
+    ];
+    let escrow_signer = &[&escrow_seeds[..]];
+
+    let amount = circle.contribution_amount;
+    let mut collected = 0u32;
+    let mut missed = 0u32;
+    let mut total_collected = 0u64;
+
+    let members = circle.members.clone();
+    for (group, authority) in groups.zip(members.iter()) {
+        let (member_info, token_info, trust_score_info) = (&group[0], &group[1], &group[2]);
+        require!(
+            member_info.owner == &crate::ID && member_info.is_writable,
+            HaloError::InvalidMemberAccounts
+        );
+        let mut member = Member::try_deserialize(&mut &member_info.try_borrow_data()?[..])?;
+        require!(
+            member.circle == circle_key && member.authority == *authority,
+            HaloError::InvalidMemberAccounts
+        );
+
+        let already_paid = member.contribution_history
+            .get(current_month as usize)
+            .is_some_and(|paid| *paid > 0);
+        // Members who never opted in are left to penalty enforcement
+        if member.status != MemberStatus::Active || already_paid || member.auto_contribution_rounds == 0 {
+            continue;
+        }
+
+        require!(
+            trust_score_info.owner == &crate::ID && trust_score_info.is_writable,
+            HaloError::InvalidMemberAccounts
+        );
+        let mut trust_score = TrustScore::try_deserialize(&mut &trust_score_info.try_borrow_data()?[..])?;
+        require!(trust_score.authority == member.authority, HaloError::InvalidMemberAccounts);
+
+        require!(token_info.owner == &token::ID, HaloError::InvalidMemberAccounts);
+        let token_account = TokenAccount::try_deserialize(&mut &token_info.try_borrow_data()?[..])?;
+        require!(
+            token_account.owner == member.authority &&
+            token_account.mint == ctx.accounts.escrow_token_account.mint,
+            HaloError::InvalidMemberAccounts
+        );
+
+        let approved = token_account.delegate == COption::Some(escrow_key) &&
+            token_account.delegated_amount >= amount;
+        if approved && token_account.amount >= amount {
+            let cpi_accounts = Transfer {
+                from: token_info.clone(),
+                to: ctx.accounts.escrow_token_account.to_account_info(),
+                authority: escrow.to_account_info(),
+            };
+            let cpi_ctx = CpiContext::new_with_signer(
+                ctx.accounts.token_program.to_account_info(),
+                cpi_accounts,
+                escrow_signer,
+            );
+            token::transfer(cpi_ctx, amount)?;
+
+            record_contribution(circle, escrow, &mut member, current_month, amount, clock.unix_timestamp)?;
+            member.auto_contribution_rounds -= 1;
+            collected += 1;
+            total_collected = total_collected.checked_add(amount).ok_or(HaloError::ArithmeticOverflow)?;
+            trust_score.total_contributions = trust_score.total_contributions
+                .checked_add(amount)
+                .ok_or(HaloError::ArithmeticOverflow)?;
+        } else if member.record_missed(current_month, clock.unix_timestamp, 0) {
+            missed += 1;
+            trust_score.missed_contributions = trust_score.missed_contributions.saturating_add(1);
+        }
+
+        // Recalculate the trust score and refresh the member's cached copy
+        trust_score.calculate_score();
+        trust_score.last_updated = clock.unix_timestamp;
+        member.trust_score = trust_score.score;
+        member.trust_tier = trust_score.tier.clone();
+
+        let mut data = member_info.try_borrow_mut_data()?;
+        member.try_serialize(&mut &mut data[..])?;
+        let mut data = trust_score_info.try_borrow_mut_data()?;
+        trust_score.try_serialize(&mut &mut data[..])?;
+    }
     
-    msg!("Automated contribution collection triggered for circle: {}", circle_automation.circle);
+    let outcome = if missed > 0 { AutomationOutcome::Missed } else { AutomationOutcome::Completed };
+    log_automation(
+        automation_log,
+        AutomationEventType::ContributionCollection,
+        current_month,
+        outcome,
+        total_collected,
+        clock.unix_timestamp,
+    );
+
+    // Pay the keeper for serving this slot
+    reward_crank(
+        ctx.accounts.keeper.as_mut(),
+        ctx.accounts.keeper_budget.as_mut(),
+        ctx.accounts.treasury.as_mut(),
+        &ctx.accounts.payer.to_account_info(),
+        circle_automation,
+        CrankClaim { task: AutomationEventType::ContributionCollection, slot: round.scheduled_at, now: clock.unix_timestamp },
+    )?;
+    
+    msg!("Automated contribution collection for circle {}: {} collected, {} missed", 
+         circle_automation.circle, collected, missed);
     Ok(())
 }
 
+/// Pay the current pot to the member the circle has scheduled for it: the
+/// winner of a settled auction when one is passed, otherwise the next payout
+/// recipient. `recipient` must match, so a keeper cannot redirect funds.
 pub(crate) fn automated_payout_distribution(
     ctx: Context<AutomatedPayoutDistribution>,
-    _event_timestamp: i64,
     recipient: Pubkey,
 ) -> Result<()> {
     let circle_automation = &mut ctx.accounts.circle_automation;
     let clock = Clock::get()?;
     
-    // Verify automation is enabled and it's time to distribute
     require!(circle_automation.auto_distribute_enabled, HaloError::AutomationDisabled);
+
+    let circle = &ctx.accounts.distribute_pot_accounts.circle;
+    regenerate_schedule(circle_automation, circle);
+    let round = circle_automation.next_due_round(&AutomationEventType::PayoutDistribution, clock.unix_timestamp);
+    let current_month = match &round {
+        Some(round) => round.month,
+        None => circle.month_at(clock.unix_timestamp),
+    };
+    let scheduled = if circle.payout_method == PayoutMethod::Auction {
+        // Auction circles pay only the winner of this round's auction
+        let auction = ctx.accounts.auction.as_ref().ok_or(HaloError::AuctionRequiredForPayout)?;
+        require!(auction.circle == circle.key(), HaloError::PayoutRecipientMismatch);
+        require!(auction.month == current_month, HaloError::AuctionRoundMismatch);
+        require!(auction.settled, HaloError::AuctionNotEnded);
+        auction.highest_bidder
+    } else {
+        circle.scheduled_payout_recipient(current_month)
+    };
+    let scheduled = scheduled.ok_or(HaloError::NoPayoutRecipient)?;
     require!(
-        circle_automation.should_distribute_payouts(clock.unix_timestamp),
-        HaloError::AutomationNotScheduled
+        recipient == scheduled &&
+        ctx.accounts.distribute_pot_accounts.recipient_member.authority == scheduled,
+        HaloError::PayoutRecipientMismatch
     );
-    
-    // Update last check time
-    circle_automation.last_distribution_check = clock.unix_timestamp;
-    
-    // Log automation event
-    let automation_event = &mut ctx.accounts.automation_event;
-    automation_event.circle = circle_automation.circle;
-    automation_event.event_type = AutomationEventType::PayoutDistribution;
-    automation_event.timestamp = clock.unix_timestamp;
-    automation_event.success = true;
-    automation_event.data = recipient.to_bytes().to_vec();
-    automation_event.error_message = None;
-    automation_event.bump = ctx.bumps.automation_event;
-    
-    msg!("Automated payout distribution triggered for circle: {}, recipient: {}", 
+
+    // Verify it's time to distribute
+    let round = round.ok_or(HaloError::AutomationNotScheduled)?;
+    let paid = pay_out_pot(&mut ctx.accounts.distribute_pot_accounts, round.month)?;
+    
+    // Mark the round served so the next crank moves on to the one after it
+    circle_automation.last_distribution_check = round.scheduled_at;
+    let automation_log = &mut ctx.accounts.automation_log;
+    report_skipped_rounds(automation_log, AutomationEventType::PayoutDistribution, &round, clock.unix_timestamp);
+    
+    log_automation(
+        automation_log,
+        AutomationEventType::PayoutDistribution,
+        round.month,
+        AutomationOutcome::Completed,
+        paid,
+        clock.unix_timestamp,
+    );
+
+    // Pay the keeper for serving this slot
+    reward_crank(
+        ctx.accounts.keeper.as_mut(),
+        ctx.accounts.keeper_budget.as_mut(),
+        Some(&mut ctx.accounts.distribute_pot_accounts.treasury),
+        &ctx.accounts.payer.to_account_info(),
+        circle_automation,
+        CrankClaim { task: AutomationEventType::PayoutDistribution, slot: round.scheduled_at, now: clock.unix_timestamp },
+    )?;
+    
+    msg!("Automated payout distribution for circle: {}, recipient: {}", 
          circle_automation.circle, recipient);
     Ok(())
 }
 
+/// Apply late penalties for the current month once its grace period is over.
+///
+/// Remaining accounts are `[member, trust_score]` pairs, both writable, in
+/// the order of `circle.members`. A member who has not contributed has the
+/// month recorded as missed, on the member and their trust score, unless
+/// collection already did so. They are charged `penalty_rate` of the
+/// contribution once per month and, after `Circle::MAX_MISSED_CONTRIBUTIONS`
+/// misses or once penalties reach their stake, moved to `Defaulted` with a
+/// `TrustPenaltyKind::Default` recorded on their trust score.
+///
+/// The round is logged with the total charged, and `AutomatedPenaltiesApplied`
+/// carries one `PenaltyOutcome` code per member in circle order.
 pub(crate) fn automated_penalty_enforcement(
     ctx: Context<AutomatedPenaltyEnforcement>,
-    _event_timestamp: i64,
 ) -> Result<()> {
     let circle_automation = &mut ctx.accounts.circle_automation;
     let circle = &ctx.accounts.circle;
@@ -682,78 +1214,139 @@ pub(crate) fn automated_penalty_enforcement(
     
     // Verify automation is enabled and it's time to enforce penalties
     require!(circle_automation.auto_penalty_enabled, HaloError::AutomationDisabled);
+    regenerate_schedule(circle_automation, circle);
+    let round = circle_automation
+        .next_due_round(&AutomationEventType::PenaltyEnforcement, clock.unix_timestamp)
+        .ok_or(HaloError::AutomationNotScheduled)?;
+    require!(circle.status == CircleStatus::Active, HaloError::CircleNotActive);
+    require!(!circle.emergency_paused, HaloError::CirclePaused);
+    
+    // The round's month is the one checked for missed contributions
+    let current_month = round.month;
+
+    // Contributions are only late once the grace period has passed
+    let due_at = circle_automation.contribution_schedule
+        .get(current_month as usize)
+        .copied()
+        .unwrap_or(circle.created_at + current_month as i64 * 30 * 24 * 60 * 60);
+    let grace_ends_at = due_at + circle.grace_period_days as i64 * 24 * 60 * 60;
+    require!(clock.unix_timestamp >= grace_ends_at, HaloError::AutomationNotScheduled);
+    let days_late = u8::try_from((clock.unix_timestamp - due_at) / (24 * 60 * 60)).unwrap_or(u8::MAX);
+
+    let groups = ctx.remaining_accounts.chunks_exact(2);
     require!(
-        circle_automation.should_enforce_penalties(clock.unix_timestamp),
-        HaloError::AutomationNotScheduled
+        groups.remainder().is_empty() && groups.len() == circle.members.len(),
+        HaloError::InvalidMemberAccounts
     );
-    
-    // Update last check time
-    circle_automation.last_penalty_check = clock.unix_timestamp;
-    
-    // Calculate current month to check for missed contributions
-    let months_since_creation = ((clock.unix_timestamp - circle.created_at) / (30 * 24 * 60 * 60)) as u8;
-    let _current_month = std::cmp::min(months_since_creation, circle.duration_months - 1);
 
-    // Check which members have missed contributions and apply penalties
-    let penalties_applied = 0u32;
-    
-    // This is a simplified penalty check - in practice, you'd iterate through members
-    // and check their contribution history against the current month
-    
-    // Log automation event
-    let automation_event = &mut ctx.accounts.automation_event;
-    automation_event.circle = circle_automation.circle;
-    automation_event.event_type = AutomationEventType::PenaltyEnforcement;
-    automation_event.timestamp = clock.unix_timestamp;
-    automation_event.success = true;
-    automation_event.data = penalties_applied.to_le_bytes().to_vec();
-    automation_event.error_message = None;
-    automation_event.bump = ctx.bumps.automation_event;
-    
-    msg!("Automated penalty enforcement triggered for circle: {}, penalties applied: {}", 
-         circle_automation.circle, penalties_applied);
-    Ok(())
-}
+    // Mark the round served so the next crank moves on to the one after it
+    circle_automation.last_penalty_check = round.scheduled_at;
+    let automation_log = &mut ctx.accounts.automation_log;
+    report_skipped_rounds(automation_log, AutomationEventType::PenaltyEnforcement, &round, clock.unix_timestamp);
 
-pub(crate) fn update_automation_settings(
-    ctx: Context<UpdateAutomationSettings>,
-    enabled: bool,
-    min_interval: Option<i64>,
-) -> Result<()> {
-    let automation_state = &mut ctx.accounts.automation_state;
-    
-    automation_state.enabled = enabled;
-    if let Some(interval) = min_interval {
-        automation_state.min_interval = interval;
-    }
-    automation_state.last_check = Clock::get()?.unix_timestamp;
-    
-    msg!("Automation settings updated: enabled={}, min_interval={}", 
-         enabled, automation_state.min_interval);
-    Ok(())
-}
+    let penalty = math::bps_of(circle.contribution_amount, circle.penalty_rate as u64, Rounding::Down)
+        .ok_or(HaloError::ArithmeticOverflow)?;
+    let circle_key = circle.key();
 
-pub(crate) fn switchboard_automation_callback(
-    ctx: Context<SwitchboardAutomationCallback>,
-) -> Result<()> {
-    let automation_state = &ctx.accounts.automation_state;
-    let clock = Clock::get()?;
-    
-    // Verify this is called by Switchboard
-    require!(automation_state.enabled, HaloError::AutomationDisabled);
+    let mut outcomes = Vec::with_capacity(circle.members.len());
+    let mut penalties_applied = 0u32;
+    for (group, authority) in groups.zip(circle.members.iter()) {
+        let (info, trust_score_info) = (&group[0], &group[1]);
+        require!(info.owner == &crate::ID && info.is_writable, HaloError::InvalidMemberAccounts);
+        let mut member = Member::try_deserialize(&mut &info.try_borrow_data()?[..])?;
+        require!(
+            member.circle == circle_key && member.authority == *authority,
+            HaloError::InvalidMemberAccounts
+        );
+        require!(
+            trust_score_info.owner == &crate::ID && trust_score_info.is_writable,
+            HaloError::InvalidMemberAccounts
+        );
+        let mut trust_score = TrustScore::try_deserialize(&mut &trust_score_info.try_borrow_data()?[..])?;
+        require!(trust_score.authority == *authority, HaloError::InvalidMemberAccounts);
+
+        let paid = member.contribution_history
+            .get(current_month as usize)
+            .is_some_and(|amount| *amount > 0);
+        let mut newly_missed = false;
+        let outcome = if member.status != MemberStatus::Active {
+            PenaltyOutcome::Skipped
+        } else if paid {
+            PenaltyOutcome::Paid
+        } else {
+            // Collection may have recorded the miss already; it still counts
+            // once on the trust score and the penalty is charged once
+            newly_missed = member.record_missed(current_month, clock.unix_timestamp, days_late);
+            if newly_missed {
+                trust_score.missed_contributions = trust_score.missed_contributions.saturating_add(1);
+                trust_score.calculate_score();
+                trust_score.last_updated = clock.unix_timestamp;
+                member.trust_score = trust_score.score;
+                member.trust_tier = trust_score.tier.clone();
+            }
+            let charged = member.mark_penalty_charged(current_month);
+            if charged {
+                member.penalties = member.penalties
+                    .checked_add(penalty)
+                    .ok_or(HaloError::ArithmeticOverflow)?;
+                penalties_applied += 1;
+            }
+
+            if member.contributions_missed >= Circle::MAX_MISSED_CONTRIBUTIONS ||
+                member.penalties >= member.stake_amount
+            {
+                member.status = MemberStatus::Defaulted;
+                apply_trust_penalty(&mut trust_score, &mut member, TrustPenaltyKind::Default, clock.unix_timestamp);
+                PenaltyOutcome::Defaulted
+            } else if charged {
+                PenaltyOutcome::Penalized
+            } else {
+                PenaltyOutcome::Skipped
+            }
+        };
+        outcomes.push(outcome.as_u8());
+
+        if matches!(outcome, PenaltyOutcome::Penalized | PenaltyOutcome::Defaulted) {
+            let mut account_data = info.try_borrow_mut_data()?;
+            member.try_serialize(&mut &mut account_data[..])?;
+        }
+        if newly_missed || outcome == PenaltyOutcome::Defaulted {
+            let mut account_data = trust_score_info.try_borrow_mut_data()?;
+            trust_score.try_serialize(&mut &mut account_data[..])?;
+        }
+    }
     
-    // Check if enough time has passed since last check
-    require!(
-        clock.unix_timestamp >= automation_state.last_check + automation_state.min_interval,
-        HaloError::AutomationTooFrequent
+    let charged = penalty
+        .checked_mul(penalties_applied as u64)
+        .ok_or(HaloError::ArithmeticOverflow)?;
+    let outcome = if penalties_applied > 0 { AutomationOutcome::Missed } else { AutomationOutcome::Completed };
+    log_automation(
+        automation_log,
+        AutomationEventType::PenaltyEnforcement,
+        current_month,
+        outcome,
+        charged,
+        clock.unix_timestamp,
     );
-    
-    // This callback would trigger specific automation based on the round data
-    // For now, we just update the last check timestamp
-    let automation_state = &mut ctx.accounts.automation_state;
-    automation_state.last_check = clock.unix_timestamp;
-    
-    msg!("Switchboard automation callback executed at timestamp: {}", clock.unix_timestamp);
+    emit!(AutomatedPenaltiesApplied {
+        circle: circle_key,
+        month: current_month,
+        penalty,
+        outcomes,
+    });
+
+    // Pay the keeper for serving this slot
+    reward_crank(
+        ctx.accounts.keeper.as_mut(),
+        ctx.accounts.keeper_budget.as_mut(),
+        ctx.accounts.treasury.as_mut(),
+        &ctx.accounts.payer.to_account_info(),
+        circle_automation,
+        CrankClaim { task: AutomationEventType::PenaltyEnforcement, slot: round.scheduled_at, now: clock.unix_timestamp },
+    )?;
+    
+    msg!("Automated penalty enforcement for circle: {}, month {}, penalties applied: {}", 
+         circle_automation.circle, current_month, penalties_applied);
     Ok(())
 }
 
@@ -811,6 +1404,32 @@ pub struct JoinCircle<'info> {
         bump,
     )]
     pub trust_score: Option<Account<'info, TrustScore>>,
+
+    /// Guarantee the joining member chose to accept; one per voucher
+    #[account(
+        mut,
+        seeds = [b"vouch", circle.key().as_ref(), member_authority.key().as_ref(), vouch.voucher.as_ref()],
+        bump = vouch.bump,
+    )]
+    pub vouch: Option<Account<'info, Vouch>>,
+
+    /// Private trust score, used instead of `trust_score` together with an
+    /// attested tier claim in the preceding ed25519 instruction
+    #[account(
+        seeds = [b"encrypted_trust_score", member_authority.key().as_ref()],
+        bump = encrypted_trust_score.bump,
+    )]
+    pub encrypted_trust_score: Option<Account<'info, EncryptedTrustScore>>,
+
+    #[account(
+        seeds = [b"privacy_config"],
+        bump = privacy_config.bump,
+    )]
+    pub privacy_config: Option<Account<'info, PrivacyConfig>>,
+
+    /// CHECK: Instructions sysvar, needed to read the attested tier claim
+    #[account(address = INSTRUCTIONS_SYSVAR_ID)]
+    pub instructions_sysvar: Option<AccountInfo<'info>>,
     
     #[account(mut)]
     pub member_token_account: Account<'info, TokenAccount>,
@@ -891,16 +1510,29 @@ pub struct DistributePot<'info> {
     
     pub authority: Signer<'info>, // Could be circle creator or governance
     
-    #[account(mut)]
+    #[account(
+        mut,
+        constraint = recipient_token_account.owner == recipient_member.authority @ HaloError::PayoutRecipientMismatch
+    )]
     pub recipient_token_account: Account<'info, TokenAccount>,
     
     #[account(mut)]
     pub escrow_token_account: Account<'info, TokenAccount>,
     
-    #[account(mut)]
+    #[account(
+        mut,
+        constraint = treasury_token_account.owner == treasury.key() @ HaloError::InvalidTreasuryTokenAccount
+    )]
     pub treasury_token_account: Account<'info, TokenAccount>,
     
     pub token_program: Program<'info, Token>,
+    
+    /// Recipient's trust score; sets their distribution fee discount
+    #[account(
+        seeds = [b"trust_score", recipient_member.authority.as_ref()],
+        bump = recipient_trust_score.bump
+    )]
+    pub recipient_trust_score: Option<Account<'info, TrustScore>>,
 }
 
 #[derive(Accounts)]
@@ -951,6 +1583,13 @@ pub struct LeaveCircle<'info> {
     )]
     pub escrow: Account<'info, CircleEscrow>,
     
+    #[account(
+        mut,
+        seeds = [b"trust_score", member_authority.key().as_ref()],
+        bump = trust_score.bump
+    )]
+    pub trust_score: Account<'info, TrustScore>,
+    
     #[account(mut)]
     pub member_authority: Signer<'info>,
     
@@ -987,15 +1626,29 @@ pub struct UpdateTrustScore<'info> {
         bump = trust_score.bump
     )]
     pub trust_score: Account<'info, TrustScore>,
-    
-    pub authority: Signer<'info>,
-}
 
-#[derive(Accounts)]
-pub struct AddSocialProof<'info> {
     #[account(
         mut,
-        seeds = [b"trust_score", authority.key().as_ref()],
+        seeds = [b"trust_credential", authority.key().as_ref()],
+        bump = trust_credential.bump
+    )]
+    pub trust_credential: Option<Account<'info, TrustCredential>>,
+
+    /// Syncs the tier thresholds set by protocol governance
+    #[account(
+        seeds = [b"protocol_realm"],
+        bump = protocol_realm.bump
+    )]
+    pub protocol_realm: Option<Account<'info, ProtocolRealm>>,
+    
+    pub authority: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct AddSocialProof<'info> {
+    #[account(
+        mut,
+        seeds = [b"trust_score", authority.key().as_ref()],
         bump = trust_score.bump
     )]
     pub trust_score: Account<'info, TrustScore>,
@@ -1073,6 +1726,15 @@ pub struct SetupCircleAutomation<'info> {
         bump
     )]
     pub circle_automation: Account<'info, CircleAutomation>,
+
+    #[account(
+        init,
+        payer = authority,
+        space = AutomationLog::SPACE,
+        seeds = [b"automation_log", circle.key().as_ref()],
+        bump
+    )]
+    pub automation_log: Account<'info, AutomationLog>,
     
     #[account(mut)]
     pub automation_state: Account<'info, AutomationState>,
@@ -1089,7 +1751,131 @@ pub struct SetupCircleAutomation<'info> {
 }
 
 #[derive(Accounts)]
-#[instruction(event_timestamp: i64)]
+pub struct UpdateCircleAutomation<'info> {
+    #[account(
+        mut,
+        seeds = [b"circle_automation", circle.key().as_ref()],
+        bump = circle_automation.bump
+    )]
+    pub circle_automation: Account<'info, CircleAutomation>,
+
+    #[account(
+        mut,
+        seeds = [b"automation_state"],
+        bump = automation_state.bump
+    )]
+    pub automation_state: Account<'info, AutomationState>,
+
+    pub circle: Account<'info, Circle>,
+
+    /// CHECK: New Switchboard job account, when the job is being moved
+    pub switchboard_job: Option<AccountInfo<'info>>,
+
+    /// Circle creator or the automation authority
+    #[account(
+        constraint = authority.key() == circle.creator ||
+            authority.key() == automation_state.authority @ HaloError::UnauthorizedAutomationChange
+    )]
+    pub authority: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct TeardownCircleAutomation<'info> {
+    #[account(
+        mut,
+        close = authority,
+        seeds = [b"circle_automation", circle.key().as_ref()],
+        bump = circle_automation.bump
+    )]
+    pub circle_automation: Account<'info, CircleAutomation>,
+
+    #[account(
+        mut,
+        close = authority,
+        seeds = [b"automation_log", circle.key().as_ref()],
+        bump = automation_log.bump
+    )]
+    pub automation_log: Account<'info, AutomationLog>,
+
+    #[account(
+        mut,
+        seeds = [b"automation_state"],
+        bump = automation_state.bump
+    )]
+    pub automation_state: Account<'info, AutomationState>,
+
+    pub circle: Account<'info, Circle>,
+
+    /// Circle creator or the automation authority; receives the rent
+    #[account(
+        mut,
+        constraint = authority.key() == circle.creator ||
+            authority.key() == automation_state.authority @ HaloError::UnauthorizedAutomationChange
+    )]
+    pub authority: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct EnableAutoContribution<'info> {
+    pub circle: Account<'info, Circle>,
+
+    #[account(
+        mut,
+        seeds = [b"member", circle.key().as_ref(), member_authority.key().as_ref()],
+        bump = member.bump
+    )]
+    pub member: Account<'info, Member>,
+
+    #[account(
+        seeds = [b"escrow", circle.key().as_ref()],
+        bump = escrow.bump
+    )]
+    pub escrow: Account<'info, CircleEscrow>,
+
+    #[account(
+        mut,
+        constraint = member_token_account.owner == member_authority.key()
+    )]
+    pub member_token_account: Account<'info, TokenAccount>,
+
+    pub member_authority: Signer<'info>,
+
+    pub token_program: Program<'info, Token>,
+}
+
+#[derive(Accounts)]
+pub struct DisableAutoContribution<'info> {
+    #[account(
+        mut,
+        seeds = [b"member", member.circle.as_ref(), member_authority.key().as_ref()],
+        bump = member.bump
+    )]
+    pub member: Account<'info, Member>,
+
+    #[account(
+        mut,
+        constraint = member_token_account.owner == member_authority.key()
+    )]
+    pub member_token_account: Account<'info, TokenAccount>,
+
+    pub member_authority: Signer<'info>,
+
+    pub token_program: Program<'info, Token>,
+}
+
+#[derive(Accounts)]
+pub struct SyncAutomationSchedule<'info> {
+    #[account(
+        mut,
+        seeds = [b"circle_automation", circle.key().as_ref()],
+        bump = circle_automation.bump
+    )]
+    pub circle_automation: Account<'info, CircleAutomation>,
+
+    pub circle: Account<'info, Circle>,
+}
+
+#[derive(Accounts)]
 pub struct AutomatedContributionCollection<'info> {
     #[account(
         mut,
@@ -1099,22 +1885,62 @@ pub struct AutomatedContributionCollection<'info> {
     pub circle_automation: Account<'info, CircleAutomation>,
 
     #[account(
-        init,
-        payer = payer,
-        space = AutomationEvent::SPACE,
-        seeds = [b"automation_event", circle_automation.circle.as_ref(), &event_timestamp.to_le_bytes()],
-        bump
+        mut,
+        constraint = circle.key() == circle_automation.circle
+    )]
+    pub circle: Account<'info, Circle>,
+
+    #[account(
+        mut,
+        seeds = [b"escrow", circle.key().as_ref()],
+        bump = escrow.bump
     )]
-    pub automation_event: Account<'info, AutomationEvent>,
+    pub escrow: Account<'info, CircleEscrow>,
+
+    #[account(
+        mut,
+        constraint = escrow_token_account.owner == escrow.key()
+    )]
+    pub escrow_token_account: Account<'info, TokenAccount>,
+
+    pub token_program: Program<'info, Token>,
+
+    #[account(
+        mut,
+        seeds = [b"automation_log", circle_automation.circle.as_ref()],
+        bump = automation_log.bump
+    )]
+    pub automation_log: Account<'info, AutomationLog>,
+
+    /// Registered keeper signing as payer; earns the crank bounty
+    #[account(
+        mut,
+        seeds = [b"keeper", payer.key().as_ref()],
+        bump = keeper.bump
+    )]
+    pub keeper: Option<Account<'info, Keeper>>,
+
+    /// Circle's keeper budget, paid from before the treasury
+    #[account(
+        mut,
+        seeds = [b"keeper_budget", circle_automation.circle.as_ref()],
+        bump = keeper_budget.bump
+    )]
+    pub keeper_budget: Option<Account<'info, KeeperBudget>>,
+
+    /// Pays the bounty when the circle's budget cannot
+    #[account(
+        mut,
+        seeds = [b"treasury"],
+        bump = treasury.bump
+    )]
+    pub treasury: Option<Account<'info, Treasury>>,
 
     #[account(mut)]
     pub payer: Signer<'info>,
-
-    pub system_program: Program<'info, System>,
 }
 
 #[derive(Accounts)]
-#[instruction(event_timestamp: i64)]
 pub struct AutomatedPayoutDistribution<'info> {
     #[account(
         mut,
@@ -1124,24 +1950,40 @@ pub struct AutomatedPayoutDistribution<'info> {
     pub circle_automation: Account<'info, CircleAutomation>,
 
     #[account(
-        init,
-        payer = payer,
-        space = AutomationEvent::SPACE,
-        seeds = [b"automation_event", circle_automation.circle.as_ref(), &event_timestamp.to_le_bytes()],
-        bump
+        mut,
+        seeds = [b"automation_log", circle_automation.circle.as_ref()],
+        bump = automation_log.bump
     )]
-    pub automation_event: Account<'info, AutomationEvent>,
+    pub automation_log: Account<'info, AutomationLog>,
 
+    #[account(constraint = distribute_pot_accounts.circle.key() == circle_automation.circle)]
     pub distribute_pot_accounts: DistributePot<'info>,
 
+    /// Settled auction deciding this round's recipient; required for
+    /// auction payouts and ignored otherwise
+    pub auction: Option<Account<'info, Auction>>,
+
+    /// Registered keeper signing as payer; earns the crank bounty
+    #[account(
+        mut,
+        seeds = [b"keeper", payer.key().as_ref()],
+        bump = keeper.bump
+    )]
+    pub keeper: Option<Account<'info, Keeper>>,
+
+    /// Circle's keeper budget, paid from before the treasury
+    #[account(
+        mut,
+        seeds = [b"keeper_budget", circle_automation.circle.as_ref()],
+        bump = keeper_budget.bump
+    )]
+    pub keeper_budget: Option<Account<'info, KeeperBudget>>,
+
     #[account(mut)]
     pub payer: Signer<'info>,
-
-    pub system_program: Program<'info, System>,
 }
 
 #[derive(Accounts)]
-#[instruction(event_timestamp: i64)]
 pub struct AutomatedPenaltyEnforcement<'info> {
     #[account(
         mut,
@@ -1150,49 +1992,50 @@ pub struct AutomatedPenaltyEnforcement<'info> {
     )]
     pub circle_automation: Account<'info, CircleAutomation>,
 
+    #[account(constraint = circle.key() == circle_automation.circle)]
     pub circle: Account<'info, Circle>,
 
     #[account(
-        init,
-        payer = payer,
-        space = AutomationEvent::SPACE,
-        seeds = [b"automation_event", circle_automation.circle.as_ref(), &event_timestamp.to_le_bytes()],
-        bump
+        mut,
+        seeds = [b"automation_log", circle_automation.circle.as_ref()],
+        bump = automation_log.bump
     )]
-    pub automation_event: Account<'info, AutomationEvent>,
-
-    #[account(mut)]
-    pub payer: Signer<'info>,
+    pub automation_log: Account<'info, AutomationLog>,
 
-    pub system_program: Program<'info, System>,
-}
+    /// Registered keeper signing as payer; earns the crank bounty
+    #[account(
+        mut,
+        seeds = [b"keeper", payer.key().as_ref()],
+        bump = keeper.bump
+    )]
+    pub keeper: Option<Account<'info, Keeper>>,
 
-#[derive(Accounts)]
-pub struct UpdateAutomationSettings<'info> {
+    /// Circle's keeper budget, paid from before the treasury
     #[account(
         mut,
-        seeds = [b"automation_state"],
-        bump = automation_state.bump,
-        has_one = authority
+        seeds = [b"keeper_budget", circle_automation.circle.as_ref()],
+        bump = keeper_budget.bump
     )]
-    pub automation_state: Account<'info, AutomationState>,
-    
-    pub authority: Signer<'info>,
-}
+    pub keeper_budget: Option<Account<'info, KeeperBudget>>,
 
-#[derive(Accounts)]
-pub struct SwitchboardAutomationCallback<'info> {
+    /// Pays the bounty when the circle's budget cannot
     #[account(
         mut,
-        seeds = [b"automation_state"],
-        bump = automation_state.bump
+        seeds = [b"treasury"],
+        bump = treasury.bump
     )]
-    pub automation_state: Account<'info, AutomationState>,
-    
-    /// CHECK: Switchboard aggregator account  
-    pub switchboard_feed: AccountInfo<'info>,
-}// Governance and Auction Instructions
+    pub treasury: Option<Account<'info, Treasury>>,
 
+    #[account(mut)]
+    pub payer: Signer<'info>,
+}
+
+// Governance and Auction Instructions
+
+/// Open a proposal in a circle. Token-balance circles pass every member's
+/// associated token account for the governance mint as remaining accounts,
+/// in `circle.members` order; those balances are the voting power for the
+/// whole proposal.
 pub(crate) fn create_proposal(
     ctx: Context<CreateProposal>,
     title: String,
@@ -1200,7 +2043,7 @@ pub(crate) fn create_proposal(
     proposal_type: u8, // 0 = InterestRateChange, 1 = CircleParameter, 2 = Emergency
     voting_duration_hours: u16,
     execution_threshold: u64,
-    new_interest_rate: Option<u16>,
+    action: Option<ProposalAction>,
 ) -> Result<()> {
     require!(title.len() <= GovernanceProposal::MAX_TITLE_LENGTH, HaloError::InvalidProposalType);
     require!(description.len() <= GovernanceProposal::MAX_DESCRIPTION_LENGTH, HaloError::InvalidProposalType);
@@ -1208,7 +2051,7 @@ pub(crate) fn create_proposal(
     require!(proposal_type <= 2, HaloError::InvalidProposalType);
     
     let clock = Clock::get()?;
-    let circle = &ctx.accounts.circle;
+    let circle = &mut ctx.accounts.circle;
     let proposal = &mut ctx.accounts.proposal;
     let proposer = &ctx.accounts.proposer;
 
@@ -1223,99 +2066,371 @@ pub(crate) fn create_proposal(
         _ => return Err(HaloError::InvalidProposalType.into()),
     };
 
-    // For interest rate proposals, require the new rate parameter
-    if prop_type == ProposalType::InterestRateChange {
-        require!(new_interest_rate.is_some(), HaloError::InvalidProposalType);
-        require!(new_interest_rate.unwrap() <= 10000, HaloError::InvalidProposalType); // Max 100%
+    // Parameter proposals must carry a typed action matching their type
+    match prop_type {
+        ProposalType::InterestRateChange => require!(
+            matches!(action, Some(ProposalAction::SetPenaltyRate { .. })),
+            HaloError::InvalidProposalAction
+        ),
+        ProposalType::CircleParameter => require!(
+            action.as_ref().is_some_and(|a| !a.is_emergency()),
+            HaloError::InvalidProposalAction
+        ),
+        ProposalType::Emergency => require!(
+            action.as_ref().is_some_and(|a| a.is_emergency()),
+            HaloError::InvalidProposalAction
+        ),
+        ProposalType::Protocol => return Err(HaloError::InvalidProposalType.into()),
+    }
+    if let Some(action) = &action {
+        action.validate(circle)?;
+    }
+
+    let voting_end = clock.unix_timestamp + (voting_duration_hours as i64 * 3600);
+
+    // Initialize proposal
+    proposal.id = circle.proposal_count;
+    proposal.circle = circle.key();
+    proposal.proposer = proposer.key();
+    proposal.title = title;
+    proposal.description = description;
+    proposal.proposal_type = prop_type;
+    proposal.status = ProposalStatus::Active;
+    proposal.voting_start = clock.unix_timestamp;
+    proposal.voting_end = voting_end;
+    proposal.execution_threshold = execution_threshold;
+    proposal.total_voting_power = 0;
+    proposal.votes_for = 0;
+    proposal.votes_against = 0;
+    proposal.quadratic_votes_for = 0;
+    proposal.quadratic_votes_against = 0;
+    proposal.executed = false;
+    proposal.executed_at = None;
+    proposal.new_interest_rate = match action {
+        Some(ProposalAction::SetPenaltyRate { rate }) => Some(rate),
+        _ => None,
+    };
+    proposal.bump = ctx.bumps.proposal;
+    proposal.voting_power_source = circle.governance.voting_power_source.clone();
+    proposal.governance_mint = circle.governance.governance_mint;
+    proposal.quorum_bps = circle.governance.quorum_bps;
+    proposal.tally_mode = circle.governance.tally_mode.clone();
+    proposal.timelock_seconds = circle.governance.timelock_seconds;
+    proposal.eligible_voters = circle.current_members;
+    proposal.voter_count = 0;
+    proposal.executable_at = 0;
+    proposal.delegated_voters = Vec::new();
+    proposal.token_snapshot = match (&proposal.voting_power_source, proposal.governance_mint) {
+        (VotingPowerSource::TokenBalance, Some(governance_mint)) => {
+            snapshot_token_balances(&circle.members, governance_mint, ctx.remaining_accounts)?
+        },
+        _ => Vec::new(),
+    };
+    proposal.action = action;
+
+    circle.proposal_count = circle.proposal_count
+        .checked_add(1)
+        .ok_or(HaloError::ArithmeticOverflow)?;
+
+    emit!(ProposalCreated {
+        proposal_id: proposal.id,
+        circle: circle.key(),
+        proposer: proposer.key(),
+        proposal_type: proposal_type,
+        voting_end,
+    });
+
+    Ok(())
+}
+
+/// Choose how votes in a circle are weighted and decided. Proposals keep the
+/// settings that were configured when they were created.
+pub(crate) fn configure_circle_governance(
+    ctx: Context<ConfigureCircleGovernance>,
+    voting_power_source: VotingPowerSource,
+    governance_mint: Option<Pubkey>,
+    quorum_bps: u16,
+    tally_mode: TallyMode,
+    timelock_seconds: i64,
+) -> Result<()> {
+    require!(
+        (voting_power_source == VotingPowerSource::TokenBalance) == governance_mint.is_some(),
+        HaloError::InvalidGovernanceConfig
+    );
+    require!(quorum_bps <= 10000, HaloError::InvalidGovernanceConfig);
+    require!(
+        (0..=GovernanceConfig::MAX_TIMELOCK_SECONDS).contains(&timelock_seconds),
+        HaloError::InvalidGovernanceConfig
+    );
+
+    let circle = &mut ctx.accounts.circle;
+    circle.governance.voting_power_source = voting_power_source;
+    circle.governance.governance_mint = governance_mint;
+    circle.governance.quorum_bps = quorum_bps;
+    circle.governance.tally_mode = tally_mode;
+    circle.governance.timelock_seconds = timelock_seconds;
+
+    msg!("Circle governance set to {:?}", circle.governance.voting_power_source);
+    Ok(())
+}
+
+/// Quadratic voting weight of the given power
+pub(crate) fn quadratic_weight_of(voting_power: u64) -> u64 {
+    crate::math::isqrt(voting_power)
+}
+
+/// Record a new vote's power and add it to the proposal's tallies. Quadratic
+/// weight is taken per member so delegation cannot concentrate it. The caller
+/// fills in the vote's proposal, voter, support, timestamp and bump first.
+pub(crate) fn tally_vote(
+    proposal: &mut GovernanceProposal,
+    vote: &mut Vote,
+    own_power: u64,
+    delegations: Vec<DelegatedPower>,
+    counts_voter: bool,
+) -> Result<()> {
+    let mut voting_power = own_power;
+    let mut quadratic_weight = quadratic_weight_of(own_power);
+    for delegated in &delegations {
+        voting_power = voting_power.checked_add(delegated.power)
+            .ok_or(HaloError::ArithmeticOverflow)?;
+        quadratic_weight = quadratic_weight.checked_add(quadratic_weight_of(delegated.power))
+            .ok_or(HaloError::ArithmeticOverflow)?;
+    }
+    require!(voting_power > 0 && quadratic_weight > 0, HaloError::InsufficientVotingPower);
+
+    let new_voters = delegations.len() as u16 + if counts_voter { 1 } else { 0 };
+    vote.voting_power = voting_power;
+    vote.quadratic_weight = quadratic_weight;
+    vote.delegations = delegations;
+
+    proposal.voter_count = proposal.voter_count.checked_add(new_voters)
+        .ok_or(HaloError::ArithmeticOverflow)?;
+    proposal.total_voting_power = proposal.total_voting_power.checked_add(voting_power)
+        .ok_or(HaloError::ArithmeticOverflow)?;
+    
+    if vote.support {
+        proposal.votes_for = proposal.votes_for.checked_add(voting_power)
+            .ok_or(HaloError::ArithmeticOverflow)?;
+        proposal.quadratic_votes_for = proposal.quadratic_votes_for.checked_add(quadratic_weight)
+            .ok_or(HaloError::ArithmeticOverflow)?;
+    } else {
+        proposal.votes_against = proposal.votes_against.checked_add(voting_power)
+            .ok_or(HaloError::ArithmeticOverflow)?;
+        proposal.quadratic_votes_against = proposal.quadratic_votes_against.checked_add(quadratic_weight)
+            .ok_or(HaloError::ArithmeticOverflow)?;
+    }
+
+    emit!(VoteCast {
+        proposal_id: proposal.id,
+        voter: vote.voter,
+        support: vote.support,
+        voting_power,
+        quadratic_weight,
+    });
+
+    Ok(())
+}
+
+pub(crate) fn cast_vote(
+    ctx: Context<CastVote>,
+    support: bool,
+) -> Result<()> {
+    let proposal_key = ctx.accounts.proposal.key();
+    let proposal = &mut ctx.accounts.proposal;
+    let voter = &ctx.accounts.voter;
+    let voter_member = &ctx.accounts.voter_member;
+    let vote_account = &mut ctx.accounts.vote;
+    let clock = Clock::get()?;
+
+    // Check proposal is active and in voting period
+    require!(proposal.is_active(), HaloError::ProposalNotActive);
+    require!(clock.unix_timestamp >= proposal.voting_start, HaloError::VotingPeriodNotStarted);
+    require!(!proposal.voting_ended(clock.unix_timestamp), HaloError::VotingPeriodEnded);
+    require!(voter_member.status == MemberStatus::Active, HaloError::MemberNotFound);
+
+    // Derive voting power on-chain from the proposal's configured source
+    let own_power = member_voting_power(
+        proposal,
+        voter_member,
+        ctx.accounts.trust_score.as_deref(),
+    )?;
+
+    // A direct vote overrides a delegate who already cast this member's power
+    let overrides_delegate = if let Some(position) = proposal.delegated_voters
+        .iter()
+        .position(|m| *m == voter.key())
+    {
+        let delegate_vote = ctx.accounts.delegate_vote
+            .as_mut()
+            .ok_or(HaloError::InvalidDelegation)?;
+        require!(delegate_vote.proposal == proposal_key, HaloError::InvalidDelegation);
+        let entry = delegate_vote.delegations
+            .iter()
+            .position(|d| d.delegator == voter.key())
+            .ok_or(HaloError::InvalidDelegation)?;
+        let withdrawn = delegate_vote.delegations.remove(entry);
+        let withdrawn_weight = quadratic_weight_of(withdrawn.power);
+
+        delegate_vote.voting_power = delegate_vote.voting_power
+            .checked_sub(withdrawn.power)
+            .ok_or(HaloError::ArithmeticOverflow)?;
+        delegate_vote.quadratic_weight = delegate_vote.quadratic_weight
+            .checked_sub(withdrawn_weight)
+            .ok_or(HaloError::ArithmeticOverflow)?;
+        proposal.total_voting_power = proposal.total_voting_power
+            .checked_sub(withdrawn.power)
+            .ok_or(HaloError::ArithmeticOverflow)?;
+        if delegate_vote.support {
+            proposal.votes_for = proposal.votes_for.checked_sub(withdrawn.power)
+                .ok_or(HaloError::ArithmeticOverflow)?;
+            proposal.quadratic_votes_for = proposal.quadratic_votes_for.checked_sub(withdrawn_weight)
+                .ok_or(HaloError::ArithmeticOverflow)?;
+        } else {
+            proposal.votes_against = proposal.votes_against.checked_sub(withdrawn.power)
+                .ok_or(HaloError::ArithmeticOverflow)?;
+            proposal.quadratic_votes_against = proposal.quadratic_votes_against.checked_sub(withdrawn_weight)
+                .ok_or(HaloError::ArithmeticOverflow)?;
+        }
+        proposal.delegated_voters.remove(position);
+        true
+    } else {
+        false
+    };
+
+    // Add the power of members who delegated to this voter
+    let delegations = collect_delegated_power(proposal, proposal_key, voter.key(), ctx.remaining_accounts)?;
+
+    // Initialize vote record
+    vote_account.proposal = proposal.key();
+    vote_account.voter = voter.key();
+    vote_account.support = support;
+    vote_account.timestamp = clock.unix_timestamp;
+    vote_account.bump = ctx.bumps.vote;
+
+    // An overriding delegator was already counted as a voter
+    tally_vote(proposal, vote_account, own_power, delegations, !overrides_delegate)?;
+
+    Ok(())
+}
+
+/// Switch a cast vote to the other side while voting is open. The vote's
+/// full weight, including power cast for delegators, moves with it.
+pub(crate) fn change_vote(ctx: Context<ChangeVote>, support: bool) -> Result<()> {
+    let proposal = &mut ctx.accounts.proposal;
+    let vote = &mut ctx.accounts.vote;
+    let clock = Clock::get()?;
+
+    require!(proposal.is_active(), HaloError::ProposalNotActive);
+    require!(!proposal.voting_ended(clock.unix_timestamp), HaloError::VotingPeriodEnded);
+    require!(vote.support != support, HaloError::AlreadyVoted);
+
+    if support {
+        proposal.votes_against = proposal.votes_against.checked_sub(vote.voting_power)
+            .ok_or(HaloError::ArithmeticOverflow)?;
+        proposal.quadratic_votes_against = proposal.quadratic_votes_against.checked_sub(vote.quadratic_weight)
+            .ok_or(HaloError::ArithmeticOverflow)?;
+        proposal.votes_for = proposal.votes_for.checked_add(vote.voting_power)
+            .ok_or(HaloError::ArithmeticOverflow)?;
+        proposal.quadratic_votes_for = proposal.quadratic_votes_for.checked_add(vote.quadratic_weight)
+            .ok_or(HaloError::ArithmeticOverflow)?;
+    } else {
+        proposal.votes_for = proposal.votes_for.checked_sub(vote.voting_power)
+            .ok_or(HaloError::ArithmeticOverflow)?;
+        proposal.quadratic_votes_for = proposal.quadratic_votes_for.checked_sub(vote.quadratic_weight)
+            .ok_or(HaloError::ArithmeticOverflow)?;
+        proposal.votes_against = proposal.votes_against.checked_add(vote.voting_power)
+            .ok_or(HaloError::ArithmeticOverflow)?;
+        proposal.quadratic_votes_against = proposal.quadratic_votes_against.checked_add(vote.quadratic_weight)
+            .ok_or(HaloError::ArithmeticOverflow)?;
+    }
+
+    vote.support = support;
+    vote.timestamp = clock.unix_timestamp;
+
+    emit!(VoteChanged {
+        proposal_id: proposal.id,
+        voter: vote.voter,
+        support,
+        voting_power: vote.voting_power,
+        quadratic_weight: vote.quadratic_weight,
+    });
+
+    Ok(())
+}
+
+/// Withdraw a proposal. The proposer may cancel while voting is open; anyone
+/// may cancel an unexecuted proposal whose proposer has left the circle.
+pub(crate) fn cancel_proposal(ctx: Context<CancelProposal>) -> Result<()> {
+    let proposal = &mut ctx.accounts.proposal;
+    let circle = &ctx.accounts.circle;
+    let authority = ctx.accounts.authority.key();
+    let clock = Clock::get()?;
+
+    if authority == proposal.proposer {
+        require!(proposal.is_active(), HaloError::ProposalNotActive);
+        require!(!proposal.voting_ended(clock.unix_timestamp), HaloError::VotingPeriodEnded);
+    } else {
+        require!(!circle.members.contains(&proposal.proposer), HaloError::UnauthorizedMember);
+        require!(
+            matches!(proposal.status, ProposalStatus::Active | ProposalStatus::Succeeded),
+            HaloError::ProposalNotActive
+        );
     }
 
-    let voting_end = clock.unix_timestamp + (voting_duration_hours as i64 * 3600);
-
-    // Initialize proposal
-    proposal.id = clock.unix_timestamp as u64;
-    proposal.circle = circle.key();
-    proposal.proposer = proposer.key();
-    proposal.title = title;
-    proposal.description = description;
-    proposal.proposal_type = prop_type;
-    proposal.status = ProposalStatus::Active;
-    proposal.voting_start = clock.unix_timestamp;
-    proposal.voting_end = voting_end;
-    proposal.execution_threshold = execution_threshold;
-    proposal.total_voting_power = 0;
-    proposal.votes_for = 0;
-    proposal.votes_against = 0;
-    proposal.quadratic_votes_for = 0;
-    proposal.quadratic_votes_against = 0;
-    proposal.executed = false;
-    proposal.executed_at = None;
-    proposal.new_interest_rate = new_interest_rate;
-    proposal.bump = ctx.bumps.proposal;
+    proposal.status = ProposalStatus::Cancelled;
 
-    emit!(ProposalCreated {
+    emit!(ProposalCancelled {
         proposal_id: proposal.id,
-        circle: circle.key(),
-        proposer: proposer.key(),
-        proposal_type: proposal_type,
-        voting_end,
+        circle: proposal.circle,
+        cancelled_by: authority,
     });
 
     Ok(())
 }
 
-pub(crate) fn cast_vote(
-    ctx: Context<CastVote>,
-    support: bool,
-    voting_power: u64,
-) -> Result<()> {
+/// Close voting and record the outcome. A succeeded proposal becomes
+/// executable once its timelock has passed.
+pub(crate) fn finalize_proposal(ctx: Context<FinalizeProposal>) -> Result<()> {
     let proposal = &mut ctx.accounts.proposal;
-    let voter = &ctx.accounts.voter;
-    let vote_account = &mut ctx.accounts.vote;
     let clock = Clock::get()?;
 
-    // Check proposal is active and in voting period
     require!(proposal.is_active(), HaloError::ProposalNotActive);
-    require!(clock.unix_timestamp >= proposal.voting_start, HaloError::VotingPeriodNotStarted);
-    require!(!proposal.voting_ended(clock.unix_timestamp), HaloError::VotingPeriodEnded);
-
-    // Validate voting power (this should be validated against SPL token balance)
-    require!(voting_power > 0, HaloError::InsufficientVotingPower);
-    
-    // Calculate quadratic weight: sqrt(voting_power)
-    let quadratic_weight = (voting_power as f64).sqrt() as u64;
-    require!(quadratic_weight > 0, HaloError::InsufficientVotingPower);
-
-    // Initialize vote record
-    vote_account.proposal = proposal.key();
-    vote_account.voter = voter.key();
-    vote_account.voting_power = voting_power;
-    vote_account.quadratic_weight = quadratic_weight;
-    vote_account.support = support;
-    vote_account.timestamp = clock.unix_timestamp;
-    vote_account.bump = ctx.bumps.vote;
+    require!(proposal.voting_ended(clock.unix_timestamp), HaloError::VotingPeriodActive);
 
-    // Update proposal tallies
-    proposal.total_voting_power = proposal.total_voting_power.checked_add(voting_power)
-        .ok_or(HaloError::ArithmeticOverflow)?;
-    
-    if support {
-        proposal.votes_for = proposal.votes_for.checked_add(voting_power)
-            .ok_or(HaloError::ArithmeticOverflow)?;
-        proposal.quadratic_votes_for = proposal.quadratic_votes_for.checked_add(quadratic_weight)
+    if proposal.has_passed() {
+        proposal.status = ProposalStatus::Succeeded;
+        proposal.executable_at = clock.unix_timestamp
+            .checked_add(proposal.timelock_seconds)
             .ok_or(HaloError::ArithmeticOverflow)?;
     } else {
-        proposal.votes_against = proposal.votes_against.checked_add(voting_power)
-            .ok_or(HaloError::ArithmeticOverflow)?;
-        proposal.quadratic_votes_against = proposal.quadratic_votes_against.checked_add(quadratic_weight)
-            .ok_or(HaloError::ArithmeticOverflow)?;
+        proposal.status = ProposalStatus::Defeated;
     }
 
-    emit!(VoteCast {
+    emit!(ProposalFinalized {
         proposal_id: proposal.id,
-        voter: voter.key(),
-        support,
-        voting_power,
-        quadratic_weight,
+        circle: proposal.circle,
+        succeeded: proposal.status == ProposalStatus::Succeeded,
+        executable_at: proposal.executable_at,
+    });
+
+    Ok(())
+}
+
+/// The circle creator can block a succeeded proposal during its timelock
+pub(crate) fn veto_proposal(ctx: Context<VetoProposal>) -> Result<()> {
+    let proposal = &mut ctx.accounts.proposal;
+    let clock = Clock::get()?;
+
+    require!(proposal.status == ProposalStatus::Succeeded, HaloError::ProposalNotSucceeded);
+    require!(clock.unix_timestamp < proposal.executable_at, HaloError::TimelockElapsed);
+
+    proposal.status = ProposalStatus::Vetoed;
+
+    emit!(ProposalVetoed {
+        proposal_id: proposal.id,
+        circle: proposal.circle,
+        vetoed_by: ctx.accounts.creator.key(),
     });
 
     Ok(())
@@ -1327,26 +2442,128 @@ pub(crate) fn execute_proposal(ctx: Context<ExecuteProposal>) -> Result<()> {
     let clock = Clock::get()?;
 
     // Check proposal can be executed
-    require!(proposal.is_active(), HaloError::ProposalNotActive);
-    require!(proposal.voting_ended(clock.unix_timestamp), HaloError::VotingPeriodEnded);
+    require!(proposal.status == ProposalStatus::Succeeded, HaloError::ProposalNotSucceeded);
     require!(!proposal.executed, HaloError::ProposalAlreadyExecuted);
-    require!(proposal.has_passed(), HaloError::ExecutionThresholdNotMet);
+    require!(clock.unix_timestamp >= proposal.executable_at, HaloError::TimelockNotElapsed);
+    require!(ctx.accounts.executor_member.status == MemberStatus::Active, HaloError::MemberNotFound);
+
+    // Apply the typed action; any failure reverts the whole execution
+    if let Some(action) = &proposal.action {
+        match action {
+            ProposalAction::SetPenaltyRate { rate } => {
+                circle.penalty_rate = *rate;
+            },
+            ProposalAction::SetGracePeriod { days } => {
+                circle.grace_period_days = *days;
+            },
+            ProposalAction::SetPayoutMethod { method } => {
+                circle.payout_method = method.clone();
+            },
+            ProposalAction::SetMinTrustTier { tier } => {
+                circle.min_trust_tier = *tier;
+            },
+            ProposalAction::SetAutoCollection { auto_collect, auto_distribute, auto_penalty } => {
+                let circle_automation = ctx.accounts.circle_automation
+                    .as_mut()
+                    .ok_or(HaloError::MissingProposalAccount)?;
+                let automation_state = ctx.accounts.automation_state
+                    .as_mut()
+                    .ok_or(HaloError::MissingProposalAccount)?;
+                let was_active = circle_automation.is_active();
+                circle_automation.auto_collect_enabled = *auto_collect;
+                circle_automation.auto_distribute_enabled = *auto_distribute;
+                circle_automation.auto_penalty_enabled = *auto_penalty;
+                track_active_job(automation_state, was_active, circle_automation.is_active())?;
+            },
+            ProposalAction::RemoveMember { member } => {
+                let target_member = ctx.accounts.target_member
+                    .as_mut()
+                    .ok_or(HaloError::MissingProposalAccount)?;
+                require!(
+                    target_member.authority == *member && target_member.circle == circle.key(),
+                    HaloError::MissingProposalAccount
+                );
+                let position = circle.members.iter()
+                    .position(|m| m == member)
+                    .ok_or(HaloError::MemberNotFound)?;
+                circle.members.remove(position);
+                circle.payout_queue.retain(|m| m != member);
+                circle.current_members = circle.current_members
+                    .checked_sub(1)
+                    .ok_or(HaloError::ArithmeticOverflow)?;
+                target_member.status = MemberStatus::Removed;
+
+                // Refund the stake now, since a removed member is no longer
+                // part of a wind-down; stake guaranteeing a newcomer stays
+                // locked until the vouch is released or slashed
+                let refund = target_member.stake_amount.saturating_sub(target_member.vouched_stake_locked);
+                if refund > 0 {
+                    let escrow = ctx.accounts.escrow
+                        .as_mut()
+                        .ok_or(HaloError::MissingProposalAccount)?;
+                    let escrow_token_account = ctx.accounts.escrow_token_account
+                        .as_ref()
+                        .ok_or(HaloError::MissingProposalAccount)?;
+                    let target_token_account = ctx.accounts.target_token_account
+                        .as_ref()
+                        .ok_or(HaloError::MissingProposalAccount)?;
+                    let token_program = ctx.accounts.token_program
+                        .as_ref()
+                        .ok_or(HaloError::MissingProposalAccount)?;
+                    require!(
+                        escrow_token_account.owner == escrow.key() &&
+                        target_token_account.owner == *member,
+                        HaloError::MissingProposalAccount
+                    );
+
+                    let circle_key = circle.key();
+                    let seeds = &[
+                        b"escrow",
+                        circle_key.as_ref(),
+                        &[escrow.bump],
+                    ];
+                    let signer = &[&seeds[..]];
+                    let cpi_accounts = Transfer {
+                        from: escrow_token_account.to_account_info(),
+                        to: target_token_account.to_account_info(),
+                        authority: escrow.to_account_info(),
+                    };
+                    let cpi_ctx = CpiContext::new_with_signer(token_program.to_account_info(), cpi_accounts, signer);
+                    token::transfer(cpi_ctx, refund)?;
+
+                    escrow.total_amount = escrow.total_amount.checked_sub(refund).ok_or(HaloError::ArithmeticOverflow)?;
+                    circle.total_stake = circle.total_stake.saturating_sub(refund);
+                    target_member.stake_amount = target_member.stake_amount
+                        .checked_sub(refund)
+                        .ok_or(HaloError::ArithmeticOverflow)?;
+                }
+            },
+            ProposalAction::ExtendDuration { additional_months } => {
+                let new_duration = circle.duration_months
+                    .checked_add(*additional_months)
+                    .ok_or(HaloError::ArithmeticOverflow)?;
+                require!(new_duration <= Circle::MAX_DURATION, HaloError::InvalidProposalAction);
+                circle.duration_months = new_duration;
+            },
+            ProposalAction::SetEmergencyPause { paused } => {
+                // A wind-down cannot be undone by lifting the pause
+                require!(
+                    *paused || circle.status != CircleStatus::WindingDown,
+                    HaloError::InvalidProposalAction
+                );
+                circle.set_emergency_pause(*paused, clock.unix_timestamp);
+            },
+            ProposalAction::EmergencyWindDown => {
+                require!(circle.status == CircleStatus::Active, HaloError::CircleNotActive);
+                circle.status = CircleStatus::WindingDown;
+                circle.set_emergency_pause(true, clock.unix_timestamp);
+            },
+        }
+    }
 
-    // Execute based on proposal type
-    match proposal.proposal_type {
-        ProposalType::InterestRateChange => {
-            if let Some(new_rate) = proposal.new_interest_rate {
-                circle.penalty_rate = new_rate;
-            }
-        },
-        ProposalType::CircleParameter => {
-            // Placeholder for circle parameter changes
-            // This would need specific parameter change logic
-        },
-        ProposalType::Emergency => {
-            // Placeholder for emergency actions
-            // This would need specific emergency logic
-        },
+    // Keep automation in step with duration and pause changes
+    if let Some(circle_automation) = ctx.accounts.circle_automation.as_mut() {
+        regenerate_schedule(circle_automation, circle);
     }
 
     // Mark as executed
@@ -1382,6 +2599,7 @@ pub(crate) fn create_auction(
     require!(circle.members.contains(&initiator.key()), HaloError::MemberNotFound);
 
     let end_time = clock.unix_timestamp + (duration_hours as i64 * 3600);
+    let current_month = circle.month_at(clock.unix_timestamp);
 
     // Initialize auction
     auction.id = clock.unix_timestamp as u64;
@@ -1397,6 +2615,7 @@ pub(crate) fn create_auction(
     auction.settled = false;
     auction.bid_count = 0;
     auction.bump = ctx.bumps.auction;
+    auction.month = current_month;
 
     emit!(AuctionCreated {
         auction_id: auction.id,
@@ -1515,7 +2734,7 @@ pub struct CreateProposal<'info> {
         init,
         payer = proposer,
         space = GovernanceProposal::space(),
-        seeds = [b"proposal", circle.key().as_ref()],
+        seeds = [b"proposal", circle.key().as_ref(), &circle.proposal_count.to_le_bytes()],
         bump
     )]
     pub proposal: Account<'info, GovernanceProposal>,
@@ -1529,6 +2748,17 @@ pub struct CreateProposal<'info> {
     pub system_program: Program<'info, System>,
 }
 
+#[derive(Accounts)]
+pub struct ConfigureCircleGovernance<'info> {
+    #[account(
+        mut,
+        has_one = creator @ HaloError::UnauthorizedMember
+    )]
+    pub circle: Account<'info, Circle>,
+
+    pub creator: Signer<'info>,
+}
+
 #[derive(Accounts)]
 pub struct CastVote<'info> {
     #[account(mut)]
@@ -1546,23 +2776,153 @@ pub struct CastVote<'info> {
     #[account(mut)]
     pub voter: Signer<'info>,
 
-    // SPL Token account for voting power validation
-    pub voter_token_account: Account<'info, TokenAccount>,
+    /// Every vote requires the voter's membership in the proposal's circle
+    #[account(
+        seeds = [b"member", proposal.circle.as_ref(), voter.key().as_ref()],
+        bump = voter_member.bump
+    )]
+    pub voter_member: Account<'info, Member>,
+
+    /// Required for trust-weighted voting
+    #[account(
+        seeds = [b"trust_score", voter.key().as_ref()],
+        bump = trust_score.bump
+    )]
+    pub trust_score: Option<Account<'info, TrustScore>>,
+
+    /// Required when the voter's delegate already voted with their power
+    #[account(
+        mut,
+        seeds = [b"vote", proposal.key().as_ref(), delegate_vote.voter.as_ref()],
+        bump = delegate_vote.bump
+    )]
+    pub delegate_vote: Option<Account<'info, Vote>>,
 
     pub system_program: Program<'info, System>,
 }
 
 #[derive(Accounts)]
 pub struct ExecuteProposal<'info> {
-    #[account(mut)]
+    #[account(
+        mut,
+        seeds = [b"proposal", circle.key().as_ref(), &proposal.id.to_le_bytes()],
+        bump = proposal.bump
+    )]
     pub proposal: Account<'info, GovernanceProposal>,
 
     #[account(mut)]
     pub circle: Account<'info, Circle>,
 
+    /// Only circle members can execute
+    #[account(
+        seeds = [b"member", circle.key().as_ref(), executor.key().as_ref()],
+        bump = executor_member.bump
+    )]
+    pub executor_member: Account<'info, Member>,
+
+    /// Required for `ProposalAction::SetAutoCollection`; when passed, its
+    /// schedules also follow duration and pause changes
+    #[account(
+        mut,
+        seeds = [b"circle_automation", circle.key().as_ref()],
+        bump = circle_automation.bump
+    )]
+    pub circle_automation: Option<Account<'info, CircleAutomation>>,
+
+    /// Required for `ProposalAction::SetAutoCollection`, to count active jobs
+    #[account(
+        mut,
+        seeds = [b"automation_state"],
+        bump = automation_state.bump
+    )]
+    pub automation_state: Option<Account<'info, AutomationState>>,
+
+    /// Required for `ProposalAction::RemoveMember`
+    #[account(mut)]
+    pub target_member: Option<Account<'info, Member>>,
+
+    /// Required for `ProposalAction::RemoveMember` when the member has stake
+    /// to refund, along with the token accounts and token program below
+    #[account(
+        mut,
+        seeds = [b"escrow", circle.key().as_ref()],
+        bump = escrow.bump
+    )]
+    pub escrow: Option<Account<'info, CircleEscrow>>,
+
+    #[account(mut)]
+    pub escrow_token_account: Option<Account<'info, TokenAccount>>,
+
+    /// The removed member's token account receiving their stake
+    #[account(mut)]
+    pub target_token_account: Option<Account<'info, TokenAccount>>,
+
+    pub token_program: Option<Program<'info, Token>>,
+
     pub executor: Signer<'info>,
 }
 
+#[derive(Accounts)]
+pub struct FinalizeProposal<'info> {
+    #[account(
+        mut,
+        seeds = [b"proposal", proposal.circle.as_ref(), &proposal.id.to_le_bytes()],
+        bump = proposal.bump
+    )]
+    pub proposal: Account<'info, GovernanceProposal>,
+
+    pub authority: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct VetoProposal<'info> {
+    #[account(
+        mut,
+        seeds = [b"proposal", circle.key().as_ref(), &proposal.id.to_le_bytes()],
+        bump = proposal.bump
+    )]
+    pub proposal: Account<'info, GovernanceProposal>,
+
+    #[account(has_one = creator @ HaloError::UnauthorizedMember)]
+    pub circle: Account<'info, Circle>,
+
+    pub creator: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct ChangeVote<'info> {
+    #[account(
+        mut,
+        seeds = [b"proposal", proposal.circle.as_ref(), &proposal.id.to_le_bytes()],
+        bump = proposal.bump
+    )]
+    pub proposal: Account<'info, GovernanceProposal>,
+
+    #[account(
+        mut,
+        seeds = [b"vote", proposal.key().as_ref(), voter.key().as_ref()],
+        bump = vote.bump,
+        has_one = voter @ HaloError::UnauthorizedMember
+    )]
+    pub vote: Account<'info, Vote>,
+
+    pub voter: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct CancelProposal<'info> {
+    #[account(
+        mut,
+        seeds = [b"proposal", circle.key().as_ref(), &proposal.id.to_le_bytes()],
+        bump = proposal.bump
+    )]
+    pub proposal: Account<'info, GovernanceProposal>,
+
+    pub circle: Account<'info, Circle>,
+
+    pub authority: Signer<'info>,
+}
+
 // Context structs for auction instructions
 
 #[derive(Accounts)]
@@ -1628,6 +2988,17 @@ pub struct SettleAuction<'info> {
 
 // Event definitions
 
+#[event]
+pub struct TrustPenaltyApplied {
+    pub authority: Pubkey,
+    pub circle: Pubkey,
+    pub kind: TrustPenaltyKind,
+    /// Sequence number to cite when disputing this penalty
+    pub sequence: u32,
+    pub score: u16,
+    pub tier: u8,
+}
+
 #[event]
 pub struct ProposalCreated {
     pub proposal_id: u64,
@@ -1646,6 +3017,37 @@ pub struct VoteCast {
     pub quadratic_weight: u64,
 }
 
+#[event]
+pub struct ProposalFinalized {
+    pub proposal_id: u64,
+    pub circle: Pubkey,
+    pub succeeded: bool,
+    pub executable_at: i64,
+}
+
+#[event]
+pub struct VoteChanged {
+    pub proposal_id: u64,
+    pub voter: Pubkey,
+    pub support: bool,
+    pub voting_power: u64,
+    pub quadratic_weight: u64,
+}
+
+#[event]
+pub struct ProposalCancelled {
+    pub proposal_id: u64,
+    pub circle: Pubkey,
+    pub cancelled_by: Pubkey,
+}
+
+#[event]
+pub struct ProposalVetoed {
+    pub proposal_id: u64,
+    pub circle: Pubkey,
+    pub vetoed_by: Pubkey,
+}
+
 #[event]
 pub struct ProposalExecuted {
     pub proposal_id: u64,
@@ -1718,6 +3120,32 @@ pub struct ClaimPayout<'info> {
     pub escrow_token_account: Account<'info, TokenAccount>,
     
     pub token_program: Program<'info, Token>,
+    
+    #[account(
+        mut,
+        seeds = [b"treasury"],
+        bump = treasury.bump
+    )]
+    pub treasury: Account<'info, Treasury>,
+    
+    #[account(
+        seeds = [b"revenue_params"],
+        bump = revenue_params.bump
+    )]
+    pub revenue_params: Account<'info, RevenueParams>,
+    
+    #[account(
+        mut,
+        constraint = treasury_token_account.owner == treasury.key() @ HaloError::InvalidTreasuryTokenAccount
+    )]
+    pub treasury_token_account: Account<'info, TokenAccount>,
+    
+    /// Member's trust score; sets their distribution fee discount
+    #[account(
+        seeds = [b"trust_score", member_authority.key().as_ref()],
+        bump = trust_score.bump
+    )]
+    pub trust_score: Option<Account<'info, TrustScore>>,
 }
 
 #[derive(Accounts)]
@@ -1771,6 +3199,8 @@ pub(crate) fn claim_payout(ctx: Context<ClaimPayout>) -> Result<()> {
     let circle = &mut ctx.accounts.circle;
     let member = &mut ctx.accounts.member;
     let escrow = &mut ctx.accounts.escrow;
+
+    require!(!circle.emergency_paused, HaloError::CirclePaused);
     
     // Validate it's member's turn
     require!(
@@ -1792,6 +3222,18 @@ pub(crate) fn claim_payout(ctx: Context<ClaimPayout>) -> Result<()> {
         .checked_add(member_yield_share)
         .ok_or(HaloError::ArithmeticOverflow)?;
 
+    // Distribution fee on the pot, discounted by the member's tier; the
+    // yield share was already charged when it was distributed
+    let member_tier = ctx.accounts.trust_score
+        .as_ref()
+        .map(|trust_score| trust_score.tier.clone())
+        .unwrap_or_default();
+    let (distribution_fee, fee_discount) = ctx.accounts.revenue_params
+        .calculate_discounted_distribution_fee(base_payout, &member_tier)?;
+    let net_payout = total_payout
+        .checked_sub(distribution_fee)
+        .ok_or(HaloError::ArithmeticOverflow)?;
+
     // Transfer payout to member
     // Escrow is a PDA - needs signer seeds
     let circle_key = circle.key();
@@ -1802,6 +3244,22 @@ pub(crate) fn claim_payout(ctx: Context<ClaimPayout>) -> Result<()> {
     ];
     let escrow_signer = &[&escrow_seeds[..]];
 
+    revenue::collect_distribution_fee(
+        distribution_fee,
+        &mut ctx.accounts.treasury,
+        &ctx.accounts.escrow_token_account.to_account_info(),
+        &ctx.accounts.treasury_token_account.to_account_info(),
+        &escrow.to_account_info(),
+        &ctx.accounts.token_program.to_account_info(),
+        Some(escrow_signer),
+    )?;
+    revenue::record_fee_activity(
+        &mut ctx.accounts.treasury,
+        Some(&mut circle.fee_ledger),
+        revenue::FeeActivity::Distribution { amount: base_payout, fee: distribution_fee },
+        Clock::get()?.unix_timestamp,
+    )?;
+
     let transfer_instruction = Transfer {
         from: ctx.accounts.escrow_token_account.to_account_info(),
         to: ctx.accounts.member_token_account.to_account_info(),
@@ -1814,11 +3272,14 @@ pub(crate) fn claim_payout(ctx: Context<ClaimPayout>) -> Result<()> {
         escrow_signer,
     );
 
-    token::transfer(cpi_ctx, total_payout)?;
+    token::transfer(cpi_ctx, net_payout)?;
     
     // Update member
     member.payout_claimed = true;
     member.has_received_pot = true;
+    member.payouts_received = member.payouts_received
+        .checked_add(net_payout)
+        .ok_or(HaloError::ArithmeticOverflow)?;
     
     // Update circle
     circle.current_month = circle.current_month
@@ -1848,8 +3309,10 @@ pub(crate) fn claim_payout(ctx: Context<ClaimPayout>) -> Result<()> {
     emit!(PayoutClaimed {
         circle: circle.key(),
         member: member.authority,
-        amount: total_payout,
+        amount: net_payout,
         yield_share: member_yield_share,
+        fee: distribution_fee,
+        fee_discount,
         timestamp: Clock::get()?.unix_timestamp,
     });
     
@@ -1915,7 +3378,7 @@ pub(crate) fn process_payout_round(ctx: Context<ProcessPayoutRound>) -> Result<(
     
     // Validate it's time for next round
     let current_time = Clock::get()?.unix_timestamp;
-    let time_since_creation = current_time - circle.created_at;
+    let time_since_creation = current_time - circle.created_at - circle.paused_seconds_at(current_time);
     let months_elapsed = (time_since_creation / (30 * 24 * 60 * 60)) as u8;
     
     require!(
@@ -1968,12 +3431,28 @@ pub(crate) fn process_payout_round(ctx: Context<ProcessPayoutRound>) -> Result<(
 }
 
 // Events for ROSCA functionality
+#[event]
+pub struct PotDistributed {
+    pub circle: Pubkey,
+    pub recipient: Pubkey,
+    pub month: u8,
+    /// Amount paid to the recipient, after the distribution fee
+    pub amount: u64,
+    pub fee: u64,
+    /// Part of the base distribution fee waived for the recipient's trust tier
+    pub fee_discount: u64,
+}
+
 #[event]
 pub struct PayoutClaimed {
     pub circle: Pubkey,
     pub member: Pubkey,
+    /// Amount paid to the member, after the distribution fee
     pub amount: u64,
     pub yield_share: u64,
+    pub fee: u64,
+    /// Part of the base distribution fee waived for the member's trust tier
+    pub fee_discount: u64,
     pub timestamp: i64,
 }
 
@@ -1983,4 +3462,41 @@ pub struct PayoutRoundProcessed {
     pub current_month: u8,
     pub next_recipient: Option<Pubkey>,
     pub timestamp: i64,
-}
\ No newline at end of file
+}
+
+#[event]
+pub struct AutomationScheduleRegenerated {
+    pub circle: Pubkey,
+    pub rounds: u8,
+    pub paused: bool,
+    pub paused_seconds: i64,
+}
+
+#[event]
+pub struct AutomationRoundsSkipped {
+    pub circle: Pubkey,
+    pub task: AutomationEventType,
+    pub first_skipped_month: u8,
+    pub skipped: u8,
+    pub processed_month: u8,
+}
+
+#[event]
+pub struct AutomationLogged {
+    pub circle: Pubkey,
+    pub sequence: u64,
+    pub event_type: AutomationEventType,
+    pub round: u8,
+    pub outcome: AutomationOutcome,
+    pub amount: u64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct AutomatedPenaltiesApplied {
+    pub circle: Pubkey,
+    pub month: u8,
+    pub penalty: u64,
+    /// One `PenaltyOutcome` code per member, in circle order
+    pub outcomes: Vec<u8>,
+}
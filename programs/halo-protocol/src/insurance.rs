@@ -2,7 +2,8 @@ use anchor_lang::prelude::*;
 use anchor_spl::token::{self, Token, TokenAccount, Transfer};
 
 use crate::errors::HaloError;
-use crate::state::{Circle, Member, MemberStatus};
+use crate::state::{Circle, Member, MemberStatus, TrustScore, TrustPenaltyKind};
+use crate::instructions::apply_trust_penalty;
 
 #[account]
 pub struct InsurancePool {
@@ -166,6 +167,13 @@ pub struct SlashInsurance<'info> {
     )]
     pub member: Account<'info, Member>,
     
+    #[account(
+        mut,
+        seeds = [b"trust_score", member.authority.as_ref()],
+        bump = trust_score.bump
+    )]
+    pub trust_score: Account<'info, TrustScore>,
+    
     #[account(mut)]
     pub member_authority: Signer<'info>,
     
@@ -407,6 +415,14 @@ pub(crate) fn slash_insurance(
     // Reset member insurance
     member.insurance_staked = 0;
     
+    // Record the slashing, and the default itself the first time it happens
+    let trust_score = &mut ctx.accounts.trust_score;
+    let now = Clock::get()?.unix_timestamp;
+    apply_trust_penalty(trust_score, member, TrustPenaltyKind::Slashing, now);
+    if member.status != MemberStatus::Defaulted {
+        apply_trust_penalty(trust_score, member, TrustPenaltyKind::Default, now);
+    }
+    
     // Mark member as defaulted
     member.status = MemberStatus::Defaulted;
     
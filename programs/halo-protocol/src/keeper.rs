@@ -0,0 +1,337 @@
+use anchor_lang::prelude::*;
+use anchor_lang::system_program;
+
+use crate::errors::HaloError;
+use crate::state::{AutomationEventType, Circle, CircleAutomation, Treasury};
+
+/// A registered keeper and its crank record
+#[account]
+pub struct Keeper {
+    /// Wallet that signs cranks and receives bounties
+    pub authority: Pubkey,
+    /// Cranks that served a new scheduled slot
+    pub successful_cranks: u64,
+    /// Cranks reported failed because their slot had already been served
+    pub failed_cranks: u64,
+    /// Total bounties received, in lamports
+    pub rewards_earned: u64,
+    /// Timestamp when the keeper registered
+    pub registered_at: i64,
+    /// Timestamp of the keeper's last crank
+    pub last_crank_at: i64,
+    /// Bump seed for PDA
+    pub bump: u8,
+}
+
+impl Keeper {
+    pub const SPACE: usize = 8 + // discriminator
+        32 + // authority
+        8 + // successful_cranks
+        8 + // failed_cranks
+        8 + // rewards_earned
+        8 + // registered_at
+        8 + // last_crank_at
+        1 + // bump
+        50; // padding
+}
+
+/// Lamports set aside by a circle to pay keepers for its automation
+#[account]
+pub struct KeeperBudget {
+    /// Circle the budget pays for
+    pub circle: Pubkey,
+    /// Bounty paid per successful crank, in lamports
+    pub bounty: u64,
+    /// Total lamports deposited into the budget
+    pub total_funded: u64,
+    /// Total lamports paid out to keepers
+    pub total_paid: u64,
+    /// Bump seed for PDA
+    pub bump: u8,
+}
+
+impl KeeperBudget {
+    pub const SPACE: usize = 8 + // discriminator
+        32 + // circle
+        8 + // bounty
+        8 + // total_funded
+        8 + // total_paid
+        1 + // bump
+        50; // padding
+}
+
+/// The scheduled slot a crank claims to have served
+pub(crate) struct CrankClaim {
+    pub task: AutomationEventType,
+    pub slot: i64,
+    pub now: i64,
+}
+
+/// Take `amount` lamports from a program-owned account without dipping
+/// below its rent-exempt minimum. Returns false if it cannot afford it.
+fn debit_lamports(from: &AccountInfo, to: &AccountInfo, amount: u64) -> Result<bool> {
+    let reserve = Rent::get()?.minimum_balance(from.data_len());
+    if from.lamports().saturating_sub(reserve) < amount {
+        return Ok(false);
+    }
+    let credited = to.lamports().checked_add(amount).ok_or(HaloError::ArithmeticOverflow)?;
+    **from.try_borrow_mut_lamports()? -= amount;
+    **to.try_borrow_mut_lamports()? = credited;
+    Ok(true)
+}
+
+/// Credit a keeper for a crank and pay its bounty, from the circle's budget
+/// when it can cover it and otherwise from the treasury. A slot that was
+/// already rewarded for this task pays nothing. Returns the bounty paid.
+pub(crate) fn reward_crank<'info>(
+    keeper: Option<&mut Account<'info, Keeper>>,
+    keeper_budget: Option<&mut Account<'info, KeeperBudget>>,
+    treasury: Option<&mut Account<'info, Treasury>>,
+    recipient: &AccountInfo<'info>,
+    circle_automation: &mut CircleAutomation,
+    claim: CrankClaim,
+) -> Result<u64> {
+    let Some(keeper) = keeper else {
+        return Ok(0);
+    };
+    require!(keeper.authority == recipient.key(), HaloError::UnauthorizedKeeper);
+    keeper.last_crank_at = claim.now;
+
+    if !circle_automation.mark_rewarded(&claim.task, claim.slot) {
+        return Ok(0);
+    }
+    keeper.successful_cranks = keeper.successful_cranks.saturating_add(1);
+
+    let mut paid = 0;
+    if let Some(budget) = keeper_budget {
+        let bounty = budget.bounty;
+        if bounty > 0 && debit_lamports(&budget.to_account_info(), recipient, bounty)? {
+            budget.total_paid = budget.total_paid.saturating_add(bounty);
+            paid = bounty;
+        }
+    }
+    if paid == 0 {
+        if let Some(treasury) = treasury {
+            let bounty = treasury.keeper_bounty;
+            if bounty > 0 && debit_lamports(&treasury.to_account_info(), recipient, bounty)? {
+                treasury.keeper_rewards_paid = treasury.keeper_rewards_paid.saturating_add(bounty);
+                paid = bounty;
+            }
+        }
+    }
+
+    keeper.rewards_earned = keeper.rewards_earned.saturating_add(paid);
+    if paid > 0 {
+        emit!(KeeperRewarded {
+            keeper: keeper.authority,
+            circle: circle_automation.circle,
+            slot: claim.slot,
+            amount: paid,
+        });
+    }
+
+    Ok(paid)
+}
+
+/// Join the keeper registry so cranks can earn bounties
+pub(crate) fn register_keeper(ctx: Context<RegisterKeeper>) -> Result<()> {
+    let keeper = &mut ctx.accounts.keeper;
+    keeper.authority = ctx.accounts.authority.key();
+    keeper.successful_cranks = 0;
+    keeper.failed_cranks = 0;
+    keeper.rewards_earned = 0;
+    keeper.registered_at = Clock::get()?.unix_timestamp;
+    keeper.last_crank_at = 0;
+    keeper.bump = ctx.bumps.keeper;
+
+    msg!("Keeper registered: {}", keeper.authority);
+    Ok(())
+}
+
+/// Record a crank that reverted because its slot had already been served.
+/// Cranks revert in that case, so the keeper reports them separately.
+pub(crate) fn report_failed_crank(ctx: Context<ReportFailedCrank>, task: AutomationEventType) -> Result<()> {
+    let now = Clock::get()?.unix_timestamp;
+    require!(
+        task != AutomationEventType::ScheduleUpdate &&
+            ctx.accounts.circle_automation.next_due_round(&task, now).is_none(),
+        HaloError::CrankSlotStillDue
+    );
+
+    let keeper = &mut ctx.accounts.keeper;
+    keeper.failed_cranks = keeper.failed_cranks.saturating_add(1);
+    keeper.last_crank_at = now;
+
+    emit!(KeeperCrankFailed {
+        keeper: keeper.authority,
+        circle: ctx.accounts.circle_automation.circle,
+        at: now,
+    });
+    Ok(())
+}
+
+/// Create a circle's keeper budget with the bounty it pays per crank
+pub(crate) fn initialize_keeper_budget(ctx: Context<InitializeKeeperBudget>, bounty: u64) -> Result<()> {
+    let budget = &mut ctx.accounts.keeper_budget;
+    budget.circle = ctx.accounts.circle.key();
+    budget.bounty = bounty;
+    budget.total_funded = 0;
+    budget.total_paid = 0;
+    budget.bump = ctx.bumps.keeper_budget;
+
+    msg!("Keeper budget created for circle {} with bounty {}", budget.circle, bounty);
+    Ok(())
+}
+
+/// Deposit lamports into a circle's keeper budget; anyone may fund it
+pub(crate) fn fund_keeper_budget(ctx: Context<FundKeeperBudget>, amount: u64) -> Result<()> {
+    require!(amount > 0, HaloError::InvalidAutomationConfig);
+
+    let cpi_ctx = CpiContext::new(
+        ctx.accounts.system_program.to_account_info(),
+        system_program::Transfer {
+            from: ctx.accounts.funder.to_account_info(),
+            to: ctx.accounts.keeper_budget.to_account_info(),
+        },
+    );
+    system_program::transfer(cpi_ctx, amount)?;
+
+    let budget = &mut ctx.accounts.keeper_budget;
+    budget.total_funded = budget.total_funded
+        .checked_add(amount)
+        .ok_or(HaloError::ArithmeticOverflow)?;
+
+    msg!("Keeper budget for circle {} funded with {}", budget.circle, amount);
+    Ok(())
+}
+
+/// Close a circle's keeper budget, returning its lamports to the creator
+pub(crate) fn close_keeper_budget(ctx: Context<CloseKeeperBudget>) -> Result<()> {
+    let budget = &ctx.accounts.keeper_budget;
+    msg!("Keeper budget for circle {} closed with {} lamports",
+         budget.circle, budget.to_account_info().lamports());
+    Ok(())
+}
+
+/// Set the bounty the treasury pays when a circle's own budget cannot
+pub(crate) fn set_keeper_bounty(ctx: Context<SetKeeperBounty>, bounty: u64) -> Result<()> {
+    ctx.accounts.treasury.keeper_bounty = bounty;
+
+    msg!("Treasury keeper bounty set to {}", bounty);
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct RegisterKeeper<'info> {
+    #[account(
+        init,
+        payer = authority,
+        space = Keeper::SPACE,
+        seeds = [b"keeper", authority.key().as_ref()],
+        bump
+    )]
+    pub keeper: Account<'info, Keeper>,
+
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct ReportFailedCrank<'info> {
+    #[account(
+        mut,
+        seeds = [b"keeper", authority.key().as_ref()],
+        bump = keeper.bump
+    )]
+    pub keeper: Account<'info, Keeper>,
+
+    #[account(
+        seeds = [b"circle_automation", circle_automation.circle.as_ref()],
+        bump = circle_automation.bump
+    )]
+    pub circle_automation: Account<'info, CircleAutomation>,
+
+    pub authority: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct InitializeKeeperBudget<'info> {
+    #[account(
+        init,
+        payer = creator,
+        space = KeeperBudget::SPACE,
+        seeds = [b"keeper_budget", circle.key().as_ref()],
+        bump
+    )]
+    pub keeper_budget: Account<'info, KeeperBudget>,
+
+    #[account(has_one = creator @ HaloError::UnauthorizedMember)]
+    pub circle: Account<'info, Circle>,
+
+    #[account(mut)]
+    pub creator: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct FundKeeperBudget<'info> {
+    #[account(
+        mut,
+        seeds = [b"keeper_budget", keeper_budget.circle.as_ref()],
+        bump = keeper_budget.bump
+    )]
+    pub keeper_budget: Account<'info, KeeperBudget>,
+
+    #[account(mut)]
+    pub funder: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct CloseKeeperBudget<'info> {
+    #[account(
+        mut,
+        close = creator,
+        seeds = [b"keeper_budget", circle.key().as_ref()],
+        bump = keeper_budget.bump
+    )]
+    pub keeper_budget: Account<'info, KeeperBudget>,
+
+    #[account(has_one = creator @ HaloError::UnauthorizedMember)]
+    pub circle: Account<'info, Circle>,
+
+    #[account(mut)]
+    pub creator: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct SetKeeperBounty<'info> {
+    #[account(
+        mut,
+        seeds = [b"treasury"],
+        bump = treasury.bump,
+        has_one = authority @ HaloError::UnauthorizedRevenueOperation
+    )]
+    pub treasury: Account<'info, Treasury>,
+
+    pub authority: Signer<'info>,
+}
+
+#[event]
+pub struct KeeperRewarded {
+    pub keeper: Pubkey,
+    pub circle: Pubkey,
+    pub slot: i64,
+    pub amount: u64,
+}
+
+#[event]
+pub struct KeeperCrankFailed {
+    pub keeper: Pubkey,
+    pub circle: Pubkey,
+    pub at: i64,
+}
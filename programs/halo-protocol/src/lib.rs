@@ -4,11 +4,22 @@ use anchor_spl::token::{self, Token, TokenAccount, Transfer};
 declare_id!("25yXdB1i6MN7MvRoR17Q5okn3pEktaMEH2QP4wJv3Bs5");
 
 pub mod errors;
+pub mod math;
 pub mod instructions;
 pub mod state;
 pub mod revenue;
 pub mod insurance;
 pub mod yield_integration;
+pub mod credential;
+pub mod vouching;
+pub mod disputes;
+pub mod ed25519;
+pub mod privacy;
+pub mod emergency;
+pub mod delegation;
+pub mod protocol_governance;
+pub mod keeper;
+pub mod oracle;
 
 pub use errors::*;
 pub use instructions::*;
@@ -16,6 +27,15 @@ pub use state::*;
 pub use revenue::*;
 pub use insurance::*;
 pub use yield_integration::*;
+pub use credential::*;
+pub use vouching::*;
+pub use disputes::*;
+pub use privacy::*;
+pub use emergency::*;
+pub use delegation::*;
+pub use protocol_governance::*;
+pub use keeper::*;
+pub use oracle::*;
 
 #[program]
 pub mod halo_protocol {
@@ -60,6 +80,14 @@ pub mod halo_protocol {
         instructions::update_trust_score(ctx)
     }
 
+    pub fn issue_trust_credential(ctx: Context<IssueTrustCredential>) -> Result<()> {
+        credential::issue_trust_credential(ctx)
+    }
+
+    pub fn get_trust_tier(ctx: Context<GetTrustTier>) -> Result<TrustTierView> {
+        credential::get_trust_tier(ctx)
+    }
+
     pub fn add_social_proof(
         ctx: Context<AddSocialProof>,
         proof_type: String,
@@ -87,6 +115,72 @@ pub mod halo_protocol {
         instructions::complete_circle_update_trust(ctx)
     }
 
+    // Vouching instructions
+    pub fn vouch_for_newcomer(
+        ctx: Context<VouchForNewcomer>,
+        newcomer: Pubkey,
+        amount: u64,
+    ) -> Result<()> {
+        vouching::vouch_for_newcomer(ctx, newcomer, amount)
+    }
+
+    pub fn release_vouch(ctx: Context<ReleaseVouch>) -> Result<()> {
+        vouching::release_vouch(ctx)
+    }
+
+    pub fn slash_vouch(ctx: Context<SlashVouch>) -> Result<()> {
+        vouching::slash_vouch(ctx)
+    }
+
+    // Trust dispute instructions
+    pub fn file_trust_dispute(
+        ctx: Context<FileTrustDispute>,
+        penalty_sequence: u32,
+        evidence_hashes: Vec<[u8; 32]>,
+    ) -> Result<()> {
+        disputes::file_trust_dispute(ctx, penalty_sequence, evidence_hashes)
+    }
+
+    pub fn register_arbiter(ctx: Context<RegisterArbiter>, arbiter_authority: Pubkey) -> Result<()> {
+        disputes::register_arbiter(ctx, arbiter_authority)
+    }
+
+    pub fn remove_arbiter(ctx: Context<RemoveArbiter>) -> Result<()> {
+        disputes::remove_arbiter(ctx)
+    }
+
+    pub fn cast_dispute_vote(ctx: Context<CastDisputeVote>, support: bool) -> Result<()> {
+        disputes::cast_dispute_vote(ctx, support)
+    }
+
+    pub fn resolve_trust_dispute(ctx: Context<ResolveTrustDispute>) -> Result<()> {
+        disputes::resolve_trust_dispute(ctx)
+    }
+
+    // Private trust score instructions
+    pub fn initialize_privacy_config(ctx: Context<InitializePrivacyConfig>, attestor: Pubkey) -> Result<()> {
+        privacy::initialize_privacy_config(ctx, attestor)
+    }
+
+    pub fn set_trust_attestor(ctx: Context<SetTrustAttestor>, attestor: Pubkey) -> Result<()> {
+        privacy::set_trust_attestor(ctx, attestor)
+    }
+
+    pub fn enable_private_trust_score(
+        ctx: Context<EnablePrivateTrustScore>,
+        encrypted_score: Vec<u8>,
+        arcium_compute_key: Pubkey,
+    ) -> Result<()> {
+        privacy::enable_private_trust_score(ctx, encrypted_score, arcium_compute_key)
+    }
+
+    pub fn update_encrypted_trust_score(
+        ctx: Context<UpdateEncryptedTrustScore>,
+        encrypted_score: Vec<u8>,
+    ) -> Result<()> {
+        privacy::update_encrypted_trust_score(ctx, encrypted_score)
+    }
+
     // Governance Instructions
     pub fn create_proposal(
         ctx: Context<CreateProposal>,
@@ -95,23 +189,115 @@ pub mod halo_protocol {
         proposal_type: u8,
         voting_duration_hours: u16,
         execution_threshold: u64,
-        new_interest_rate: Option<u16>,
+        action: Option<ProposalAction>,
+    ) -> Result<()> {
+        instructions::create_proposal(ctx, title, description, proposal_type, voting_duration_hours, execution_threshold, action)
+    }
+
+    pub fn configure_circle_governance(
+        ctx: Context<ConfigureCircleGovernance>,
+        voting_power_source: VotingPowerSource,
+        governance_mint: Option<Pubkey>,
+        quorum_bps: u16,
+        tally_mode: TallyMode,
+        timelock_seconds: i64,
     ) -> Result<()> {
-        instructions::create_proposal(ctx, title, description, proposal_type, voting_duration_hours, execution_threshold, new_interest_rate)
+        instructions::configure_circle_governance(
+            ctx,
+            voting_power_source,
+            governance_mint,
+            quorum_bps,
+            tally_mode,
+            timelock_seconds,
+        )
     }
 
     pub fn cast_vote(
         ctx: Context<CastVote>,
         support: bool,
-        voting_power: u64,
     ) -> Result<()> {
-        instructions::cast_vote(ctx, support, voting_power)
+        instructions::cast_vote(ctx, support)
+    }
+
+    pub fn delegate_vote(ctx: Context<DelegateVote>, delegate: Pubkey) -> Result<()> {
+        delegation::delegate_vote(ctx, delegate)
+    }
+
+    pub fn revoke_delegation(ctx: Context<RevokeDelegation>) -> Result<()> {
+        delegation::revoke_delegation(ctx)
+    }
+
+    pub fn finalize_proposal(ctx: Context<FinalizeProposal>) -> Result<()> {
+        instructions::finalize_proposal(ctx)
+    }
+
+    pub fn change_vote(ctx: Context<ChangeVote>, support: bool) -> Result<()> {
+        instructions::change_vote(ctx, support)
+    }
+
+    pub fn cancel_proposal(ctx: Context<CancelProposal>) -> Result<()> {
+        instructions::cancel_proposal(ctx)
+    }
+
+    pub fn veto_proposal(ctx: Context<VetoProposal>) -> Result<()> {
+        instructions::veto_proposal(ctx)
     }
 
     pub fn execute_proposal(ctx: Context<ExecuteProposal>) -> Result<()> {
         instructions::execute_proposal(ctx)
     }
 
+    pub fn settle_wind_down(ctx: Context<SettleWindDown>) -> Result<()> {
+        emergency::settle_wind_down(ctx)
+    }
+
+    pub fn claim_wind_down_share(ctx: Context<ClaimWindDownShare>) -> Result<()> {
+        emergency::claim_wind_down_share(ctx)
+    }
+
+    // Protocol Governance Instructions
+    pub fn initialize_protocol_realm(
+        ctx: Context<InitializeProtocolRealm>,
+        quorum_bps: u16,
+        timelock_seconds: i64,
+    ) -> Result<()> {
+        protocol_governance::initialize_protocol_realm(ctx, quorum_bps, timelock_seconds)
+    }
+
+    pub fn register_protocol_voter(
+        ctx: Context<RegisterProtocolVoter>,
+        voter: Pubkey,
+        voting_power: u64,
+    ) -> Result<()> {
+        protocol_governance::register_protocol_voter(ctx, voter, voting_power)
+    }
+
+    pub fn remove_protocol_voter(ctx: Context<RemoveProtocolVoter>) -> Result<()> {
+        protocol_governance::remove_protocol_voter(ctx)
+    }
+
+    pub fn create_protocol_proposal(
+        ctx: Context<CreateProtocolProposal>,
+        title: String,
+        description: String,
+        action: ProtocolAction,
+        voting_duration_hours: u16,
+    ) -> Result<()> {
+        protocol_governance::create_protocol_proposal(ctx, title, description, action, voting_duration_hours)
+    }
+
+    pub fn cast_protocol_vote(ctx: Context<CastProtocolVote>, support: bool) -> Result<()> {
+        protocol_governance::cast_protocol_vote(ctx, support)
+    }
+
+    pub fn execute_protocol_proposal(ctx: Context<ExecuteProtocolProposal>) -> Result<()> {
+        protocol_governance::execute_protocol_proposal(ctx)
+    }
+
+    pub fn sync_trust_tier_thresholds(ctx: Context<SyncTrustTierThresholds>) -> Result<()> {
+        protocol_governance::sync_trust_tier_thresholds(ctx)
+    }
+
     // Auction Instructions
     pub fn create_auction(
         ctx: Context<CreateAuction>,
@@ -146,44 +332,97 @@ pub mod halo_protocol {
         auto_collect: bool,
         auto_distribute: bool,
         auto_penalty: bool,
+        catch_up: bool,
     ) -> Result<()> {
-        instructions::setup_circle_automation(ctx, auto_collect, auto_distribute, auto_penalty)
+        instructions::setup_circle_automation(ctx, auto_collect, auto_distribute, auto_penalty, catch_up)
+    }
+
+    pub fn update_circle_automation(
+        ctx: Context<UpdateCircleAutomation>,
+        auto_collect: Option<bool>,
+        auto_distribute: Option<bool>,
+        auto_penalty: Option<bool>,
+        catch_up: Option<bool>,
+    ) -> Result<()> {
+        instructions::update_circle_automation(ctx, auto_collect, auto_distribute, auto_penalty, catch_up)
+    }
+
+    pub fn teardown_circle_automation(ctx: Context<TeardownCircleAutomation>) -> Result<()> {
+        instructions::teardown_circle_automation(ctx)
+    }
+
+    pub fn sync_automation_schedule(ctx: Context<SyncAutomationSchedule>) -> Result<()> {
+        instructions::sync_automation_schedule(ctx)
+    }
+
+    pub fn enable_auto_contribution(ctx: Context<EnableAutoContribution>, rounds: u8) -> Result<()> {
+        instructions::enable_auto_contribution(ctx, rounds)
+    }
+
+    pub fn disable_auto_contribution(ctx: Context<DisableAutoContribution>) -> Result<()> {
+        instructions::disable_auto_contribution(ctx)
     }
 
-    pub fn automated_contribution_collection(
-        ctx: Context<AutomatedContributionCollection>,
-        event_timestamp: i64,
+    pub fn automated_contribution_collection<'info>(
+        ctx: Context<'_, '_, '_, 'info, AutomatedContributionCollection<'info>>,
     ) -> Result<()> {
-        instructions::automated_contribution_collection(ctx, event_timestamp)
+        instructions::automated_contribution_collection(ctx)
     }
 
     pub fn automated_payout_distribution(
         ctx: Context<AutomatedPayoutDistribution>,
-        event_timestamp: i64,
         recipient: Pubkey,
     ) -> Result<()> {
-        instructions::automated_payout_distribution(ctx, event_timestamp, recipient)
+        instructions::automated_payout_distribution(ctx, recipient)
     }
 
     pub fn automated_penalty_enforcement(
         ctx: Context<AutomatedPenaltyEnforcement>,
-        event_timestamp: i64,
     ) -> Result<()> {
-        instructions::automated_penalty_enforcement(ctx, event_timestamp)
+        instructions::automated_penalty_enforcement(ctx)
     }
 
-    pub fn update_automation_settings(
-        ctx: Context<UpdateAutomationSettings>,
-        enabled: bool,
-        min_interval: Option<i64>,
+    pub fn configure_automation_oracle(
+        ctx: Context<ConfigureAutomationOracle>,
+        oracle_program: Pubkey,
+        oracle_signer: Pubkey,
     ) -> Result<()> {
-        instructions::update_automation_settings(ctx, enabled, min_interval)
+        oracle::configure_automation_oracle(ctx, oracle_program, oracle_signer)
     }
 
-    pub fn switchboard_automation_callback(
-        ctx: Context<SwitchboardAutomationCallback>,
+    pub fn migrate_automation_state(ctx: Context<MigrateAutomationState>) -> Result<()> {
+        oracle::migrate_automation_state(ctx)
+    }
+
+    pub fn switchboard_automation_callback<'info>(
+        ctx: Context<'_, '_, '_, 'info, SwitchboardAutomationCallback<'info>>,
     ) -> Result<()> {
-        instructions::switchboard_automation_callback(ctx)
+        oracle::switchboard_automation_callback(ctx)
+    }
+
+    // Keeper Instructions
+    pub fn register_keeper(ctx: Context<RegisterKeeper>) -> Result<()> {
+        keeper::register_keeper(ctx)
+    }
+
+    pub fn report_failed_crank(ctx: Context<ReportFailedCrank>, task: AutomationEventType) -> Result<()> {
+        keeper::report_failed_crank(ctx, task)
+    }
+
+    pub fn initialize_keeper_budget(ctx: Context<InitializeKeeperBudget>, bounty: u64) -> Result<()> {
+        keeper::initialize_keeper_budget(ctx, bounty)
+    }
+
+    pub fn fund_keeper_budget(ctx: Context<FundKeeperBudget>, amount: u64) -> Result<()> {
+        keeper::fund_keeper_budget(ctx, amount)
+    }
+
+    pub fn close_keeper_budget(ctx: Context<CloseKeeperBudget>) -> Result<()> {
+        keeper::close_keeper_budget(ctx)
+    }
+
+    pub fn set_keeper_bounty(ctx: Context<SetKeeperBounty>, bounty: u64) -> Result<()> {
+        keeper::set_keeper_bounty(ctx, bounty)
     }
 
     // Revenue module instructions
@@ -191,24 +430,28 @@ pub mod halo_protocol {
         revenue::initialize_treasury(ctx)
     }
 
-    pub fn initialize_revenue_params(ctx: Context<InitializeRevenueParams>) -> Result<()> {
-        revenue::initialize_revenue_params(ctx)
+    pub fn migrate_treasury(ctx: Context<MigrateTreasury>) -> Result<()> {
+        revenue::migrate_treasury(ctx)
     }
 
-    pub fn update_revenue_params(
-        ctx: Context<UpdateRevenueParams>,
-        distribution_fee_rate: Option<u16>,
-        yield_fee_rate: Option<u16>,
-        management_fee_rate: Option<u16>,
-        management_fee_interval: Option<i64>,
-    ) -> Result<()> {
-        revenue::update_revenue_params(ctx, distribution_fee_rate, yield_fee_rate, management_fee_rate, management_fee_interval)
+    pub fn migrate_circle_stake(ctx: Context<MigrateCircleStake>) -> Result<()> {
+        revenue::migrate_circle_stake(ctx)
+    }
+
+    pub fn initialize_revenue_params(ctx: Context<InitializeRevenueParams>) -> Result<()> {
+        revenue::initialize_revenue_params(ctx)
     }
 
     pub fn collect_management_fees(ctx: Context<CollectManagementFees>) -> Result<()> {
         revenue::collect_management_fees(ctx)
     }
 
+    pub fn collect_management_fees_batch<'info>(
+        ctx: Context<'_, '_, 'info, 'info, CollectManagementFeesBatch<'info>>,
+    ) -> Result<()> {
+        revenue::collect_management_fees_batch(ctx)
+    }
+
     pub fn create_revenue_report(
         ctx: Context<CreateRevenueReport>,
         period_start: i64,
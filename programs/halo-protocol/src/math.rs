@@ -0,0 +1,158 @@
+//! Deterministic integer math shared by voting, fee and yield calculations.
+//! Everything here avoids floating point and widens to u128 for products.
+
+/// Basis point denominator (100% = 10_000 bps)
+pub const BPS_DENOMINATOR: u64 = 10_000;
+/// Seconds in a 365-day year, used to annualize returns
+pub const SECONDS_PER_YEAR: u64 = 31_536_000;
+
+/// Direction to round a division that does not come out even
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Rounding {
+    Down,
+    Up,
+}
+
+/// Floor of the square root of `n`
+pub fn isqrt(n: u64) -> u64 {
+    if n < 2 {
+        return n;
+    }
+
+    // Newton's method from a power of two at or above the root converges
+    // downwards and stops at the floor
+    let shift = (u64::BITS - n.leading_zeros()).div_ceil(2);
+    let mut x = 1u64 << shift;
+    loop {
+        let y = (x + n / x) / 2;
+        if y >= x {
+            return x;
+        }
+        x = y;
+    }
+}
+
+/// `a * b / denominator` with a u128 intermediate. Returns `None` when the
+/// denominator is zero or the result does not fit in u64.
+pub fn mul_div(a: u64, b: u64, denominator: u64, rounding: Rounding) -> Option<u64> {
+    if denominator == 0 {
+        return None;
+    }
+    let product = (a as u128) * (b as u128);
+    let denominator = denominator as u128;
+    let mut quotient = product / denominator;
+    let remainder = product % denominator;
+    if rounding == Rounding::Up && remainder != 0 {
+        quotient += 1;
+    }
+    u64::try_from(quotient).ok()
+}
+
+/// Portion of `amount` given by a rate in basis points
+pub fn bps_of(amount: u64, bps: u64, rounding: Rounding) -> Option<u64> {
+    mul_div(amount, bps, BPS_DENOMINATOR, rounding)
+}
+
+/// Annualized return of `gain` on `principal` over `elapsed_seconds`, in
+/// basis points rounded down. Zero when either input is zero; saturates at
+/// u64::MAX.
+pub fn annualized_bps(gain: u64, principal: u64, elapsed_seconds: u64) -> u64 {
+    if principal == 0 || elapsed_seconds == 0 {
+        return 0;
+    }
+    // gain * year * 10_000 < 2^64 * 2^25 * 2^14 and principal * elapsed < 2^128
+    let numerator = (gain as u128) * (SECONDS_PER_YEAR as u128) * (BPS_DENOMINATOR as u128);
+    let denominator = (principal as u128) * (elapsed_seconds as u128);
+    u64::try_from(numerator / denominator).unwrap_or(u64::MAX)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use proptest::prelude::*;
+
+    /// Reference floor sqrt by binary search over u128 squares
+    fn reference_isqrt(n: u64) -> u64 {
+        let (mut lo, mut hi) = (0u64, u32::MAX as u64);
+        while lo < hi {
+            let mid = lo + (hi - lo).div_ceil(2);
+            if (mid as u128) * (mid as u128) <= n as u128 {
+                lo = mid;
+            } else {
+                hi = mid - 1;
+            }
+        }
+        lo
+    }
+
+    #[test]
+    fn isqrt_edges() {
+        assert_eq!(isqrt(0), 0);
+        assert_eq!(isqrt(1), 1);
+        assert_eq!(isqrt(3), 1);
+        assert_eq!(isqrt(4), 2);
+        assert_eq!(isqrt(2_000_000), 1414);
+        assert_eq!(isqrt(u64::MAX), u32::MAX as u64);
+        // Above 2^53 f64 rounds (2^32 - 1)^2 - 1 up to a perfect square
+        let n = (u32::MAX as u64) * (u32::MAX as u64) - 1;
+        assert_eq!(isqrt(n), (u32::MAX as u64) - 1);
+    }
+
+    #[test]
+    fn mul_div_rounding_and_overflow() {
+        assert_eq!(mul_div(10, 3, 4, Rounding::Down), Some(7));
+        assert_eq!(mul_div(10, 3, 4, Rounding::Up), Some(8));
+        assert_eq!(mul_div(8, 3, 4, Rounding::Up), Some(6));
+        assert_eq!(mul_div(1, 1, 0, Rounding::Down), None);
+        assert_eq!(mul_div(u64::MAX, u64::MAX, u64::MAX, Rounding::Down), Some(u64::MAX));
+        assert_eq!(mul_div(u64::MAX, 2, 1, Rounding::Down), None);
+    }
+
+    #[test]
+    fn annualized_bps_matches_simple_rate() {
+        // 5% over a full year
+        assert_eq!(annualized_bps(50, 1_000, SECONDS_PER_YEAR), 500);
+        // 1% over half a year annualizes to 2%
+        assert_eq!(annualized_bps(10, 1_000, SECONDS_PER_YEAR / 2), 200);
+        assert_eq!(annualized_bps(10, 0, 1), 0);
+        assert_eq!(annualized_bps(10, 1, 0), 0);
+        assert_eq!(annualized_bps(u64::MAX, 1, 1), u64::MAX);
+    }
+
+    proptest! {
+        #[test]
+        fn isqrt_matches_reference(n: u64) {
+            prop_assert_eq!(isqrt(n), reference_isqrt(n));
+        }
+
+        #[test]
+        fn isqrt_is_floor(n: u64) {
+            let root = isqrt(n) as u128;
+            prop_assert!(root * root <= n as u128);
+            prop_assert!((root + 1) * (root + 1) > n as u128);
+        }
+
+        #[test]
+        fn mul_div_matches_u128_reference(a: u64, b: u64, d in 1u64..) {
+            let product = (a as u128) * (b as u128);
+            let down = product / d as u128;
+            let remainder = product % d as u128;
+            let up = down + u128::from(remainder != 0);
+            prop_assert_eq!(mul_div(a, b, d, Rounding::Down), u64::try_from(down).ok());
+            prop_assert_eq!(mul_div(a, b, d, Rounding::Up), u64::try_from(up).ok());
+        }
+
+        #[test]
+        fn bps_of_never_exceeds_amount(amount: u64, bps in 0u64..=BPS_DENOMINATOR) {
+            let down = bps_of(amount, bps, Rounding::Down).unwrap();
+            let up = bps_of(amount, bps, Rounding::Up).unwrap();
+            prop_assert!(down <= up && up <= amount);
+            prop_assert!(up - down <= 1);
+        }
+
+        #[test]
+        fn annualized_bps_is_monotonic_in_gain(gain in 0u64..u64::MAX, principal in 1u64.., elapsed in 1u64..) {
+            prop_assert!(annualized_bps(gain, principal, elapsed) <= annualized_bps(gain + 1, principal, elapsed));
+        }
+    }
+}
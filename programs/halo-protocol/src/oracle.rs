@@ -0,0 +1,269 @@
+use anchor_lang::prelude::*;
+use anchor_lang::system_program;
+use anchor_lang::solana_program::instruction::Instruction;
+use anchor_lang::solana_program::program::invoke;
+use anchor_lang::InstructionData;
+use solana_instructions_sysvar::ID as INSTRUCTIONS_SYSVAR_ID;
+
+use crate::ed25519::load_verified_message;
+use crate::errors::HaloError;
+use crate::program::HaloProtocol;
+use crate::state::{AutomationEventType, AutomationState, CircleAutomation};
+
+/// Task the oracle asks Halo to run, signed by the queue's enclave.
+/// Message: b"halo:automation-callback:v1" || borsh(AutomationCallback)
+#[derive(AnchorSerialize, AnchorDeserialize, Clone)]
+pub struct AutomationCallback {
+    pub circle: Pubkey,
+    pub task: AutomationEventType,
+    /// Scheduled recipient, only read for payout distribution
+    pub recipient: Pubkey,
+    pub issued_at: i64,
+}
+
+impl AutomationCallback {
+    pub const CALLBACK_DOMAIN: &'static [u8] = b"halo:automation-callback:v1";
+    /// How far a callback's issue time may drift from the cluster clock
+    pub const MAX_CALLBACK_AGE: u64 = 300;
+}
+
+/// Queue an oracle function account runs on. Function accounts start with
+/// that queue, right after their 8-byte discriminator.
+fn function_queue(function: &AccountInfo) -> Option<Pubkey> {
+    let data = function.try_borrow_data().ok()?;
+    let queue = data.get(8..40)?;
+    Pubkey::try_from(queue).ok()
+}
+
+/// Verify the signed callback in the preceding ed25519 instruction
+fn verify_automation_callback(
+    automation_state: &AutomationState,
+    instructions_sysvar: &AccountInfo,
+    now: i64,
+) -> Result<AutomationCallback> {
+    let (signer, message) = load_verified_message(instructions_sysvar)?;
+    require!(
+        automation_state.oracle_signer != Pubkey::default() && signer == automation_state.oracle_signer,
+        HaloError::InvalidOracleCallback
+    );
+
+    let payload = message
+        .strip_prefix(AutomationCallback::CALLBACK_DOMAIN)
+        .ok_or(HaloError::InvalidOracleCallback)?;
+    let callback = AutomationCallback::try_from_slice(payload).map_err(|_| HaloError::InvalidOracleCallback)?;
+
+    require!(
+        now.abs_diff(callback.issued_at) <= AutomationCallback::MAX_CALLBACK_AGE,
+        HaloError::StaleOracleCallback
+    );
+
+    Ok(callback)
+}
+
+/// Point automation at an oracle program's queue and the key its enclave
+/// signs callbacks with
+pub(crate) fn configure_automation_oracle(
+    ctx: Context<ConfigureAutomationOracle>,
+    oracle_program: Pubkey,
+    oracle_signer: Pubkey,
+) -> Result<()> {
+    let automation_state = &mut ctx.accounts.automation_state;
+    automation_state.switchboard_queue = ctx.accounts.switchboard_queue.key();
+    automation_state.oracle_program = oracle_program;
+    automation_state.oracle_signer = oracle_signer;
+
+    msg!("Automation oracle set to program {} with signer {}", oracle_program, oracle_signer);
+    Ok(())
+}
+
+/// Grow an automation state created before the oracle fields were appended
+/// to the current layout. The new fields read as unset until
+/// `configure_automation_oracle` runs. Anyone may call this; the payer
+/// covers the added rent.
+pub(crate) fn migrate_automation_state(ctx: Context<MigrateAutomationState>) -> Result<()> {
+    let automation_state = ctx.accounts.automation_state.to_account_info();
+    if automation_state.data_len() < AutomationState::SPACE {
+        let rent_due = Rent::get()?
+            .minimum_balance(AutomationState::SPACE)
+            .saturating_sub(automation_state.lamports());
+        if rent_due > 0 {
+            let cpi_ctx = CpiContext::new(
+                ctx.accounts.system_program.to_account_info(),
+                system_program::Transfer {
+                    from: ctx.accounts.payer.to_account_info(),
+                    to: automation_state.clone(),
+                },
+            );
+            system_program::transfer(cpi_ctx, rent_due)?;
+        }
+        automation_state.resize(AutomationState::SPACE)?;
+    }
+
+    // The appended fields read as zeros from the old padding and new space
+    AutomationState::try_deserialize(&mut &automation_state.try_borrow_data()?[..])?;
+
+    msg!("Automation state migrated to {} bytes", AutomationState::SPACE);
+    Ok(())
+}
+
+/// Run the circle automation task named in an oracle-signed callback.
+///
+/// The remaining accounts are exactly the accounts of the target instruction
+/// (followed by its own remaining accounts), starting with the circle's
+/// automation config, writable. That config must name the calling function
+/// account, which must run on the configured queue. Replays and
+/// `min_interval` are checked against the circle's own last callback.
+pub(crate) fn switchboard_automation_callback<'info>(
+    ctx: Context<'_, '_, '_, 'info, SwitchboardAutomationCallback<'info>>,
+) -> Result<()> {
+    let clock = Clock::get()?;
+    let automation_state = &ctx.accounts.automation_state;
+
+    require!(automation_state.enabled, HaloError::AutomationDisabled);
+    require!(
+        function_queue(&ctx.accounts.switchboard_function) == Some(automation_state.switchboard_queue),
+        HaloError::InvalidOracleAccount
+    );
+
+    let callback = verify_automation_callback(
+        automation_state,
+        &ctx.accounts.instructions_sysvar,
+        clock.unix_timestamp,
+    )?;
+
+    let target_accounts = ctx.remaining_accounts;
+    let circle_automation_info = target_accounts.first().ok_or(HaloError::InvalidOracleCallback)?;
+    require!(
+        circle_automation_info.owner == &crate::ID && circle_automation_info.is_writable,
+        HaloError::InvalidOracleCallback
+    );
+    let circle_automation = CircleAutomation::try_deserialize(&mut &circle_automation_info.try_borrow_data()?[..])?;
+    require!(circle_automation.circle == callback.circle, HaloError::InvalidOracleCallback);
+    require!(
+        circle_automation.job_account == ctx.accounts.switchboard_function.key(),
+        HaloError::InvalidOracleAccount
+    );
+    require!(
+        callback.issued_at > circle_automation.last_callback_at,
+        HaloError::StaleOracleCallback
+    );
+    require!(
+        clock.unix_timestamp >= circle_automation.last_callback_run.saturating_add(automation_state.min_interval),
+        HaloError::AutomationTooFrequent
+    );
+
+    let data = match callback.task {
+        AutomationEventType::ContributionCollection => {
+            crate::instruction::AutomatedContributionCollection {}.data()
+        }
+        AutomationEventType::PayoutDistribution => {
+            crate::instruction::AutomatedPayoutDistribution { recipient: callback.recipient }.data()
+        }
+        AutomationEventType::PenaltyEnforcement => {
+            crate::instruction::AutomatedPenaltyEnforcement {}.data()
+        }
+        AutomationEventType::ScheduleUpdate => crate::instruction::SyncAutomationSchedule {}.data(),
+    };
+
+    let task = Instruction {
+        program_id: crate::ID,
+        accounts: target_accounts
+            .iter()
+            .map(|info| AccountMeta {
+                pubkey: info.key(),
+                is_signer: info.is_signer,
+                is_writable: info.is_writable,
+            })
+            .collect(),
+        data,
+    };
+    let mut account_infos = target_accounts.to_vec();
+    account_infos.push(ctx.accounts.halo_program.to_account_info());
+    invoke(&task, &account_infos)?;
+
+    // The task may have updated the config, so record the callback on its
+    // latest state
+    let mut circle_automation = CircleAutomation::try_deserialize(&mut &circle_automation_info.try_borrow_data()?[..])?;
+    circle_automation.last_callback_at = callback.issued_at;
+    circle_automation.last_callback_run = clock.unix_timestamp;
+    let mut data = circle_automation_info.try_borrow_mut_data()?;
+    circle_automation.try_serialize(&mut &mut data[..])?;
+
+    emit!(OracleCallbackDispatched {
+        circle: callback.circle,
+        task: callback.task,
+        issued_at: callback.issued_at,
+    });
+    Ok(())
+}
+
+#[derive(Accounts)]
+#[instruction(oracle_program: Pubkey)]
+pub struct ConfigureAutomationOracle<'info> {
+    #[account(
+        mut,
+        seeds = [b"automation_state"],
+        bump = automation_state.bump,
+        has_one = authority
+    )]
+    pub automation_state: Account<'info, AutomationState>,
+
+    /// CHECK: Only its owner is checked; the oracle program defines its layout
+    #[account(owner = oracle_program @ HaloError::InvalidOracleAccount)]
+    pub switchboard_queue: UncheckedAccount<'info>,
+
+    pub authority: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct MigrateAutomationState<'info> {
+    /// CHECK: An unmigrated state is too short to deserialize, so it is only
+    /// read once grown; the seeds and owner pin it to the program's
+    /// automation state
+    #[account(
+        mut,
+        seeds = [b"automation_state"],
+        bump,
+        owner = crate::ID
+    )]
+    pub automation_state: UncheckedAccount<'info>,
+
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct SwitchboardAutomationCallback<'info> {
+    #[account(
+        seeds = [b"automation_state"],
+        bump = automation_state.bump
+    )]
+    pub automation_state: Account<'info, AutomationState>,
+
+    /// CHECK: Must be the configured queue, owned by the oracle program
+    #[account(
+        address = automation_state.switchboard_queue @ HaloError::InvalidOracleAccount,
+        owner = automation_state.oracle_program @ HaloError::InvalidOracleAccount
+    )]
+    pub switchboard_queue: UncheckedAccount<'info>,
+
+    /// CHECK: Function account driving this circle, owned by the oracle
+    /// program; its queue is read from its data
+    #[account(owner = automation_state.oracle_program @ HaloError::InvalidOracleAccount)]
+    pub switchboard_function: UncheckedAccount<'info>,
+
+    /// CHECK: Instructions sysvar, needed to read the signed callback
+    #[account(address = INSTRUCTIONS_SYSVAR_ID)]
+    pub instructions_sysvar: AccountInfo<'info>,
+
+    pub halo_program: Program<'info, HaloProtocol>,
+}
+
+#[event]
+pub struct OracleCallbackDispatched {
+    pub circle: Pubkey,
+    pub task: AutomationEventType,
+    pub issued_at: i64,
+}
@@ -0,0 +1,176 @@
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::hash::hash;
+
+use crate::ed25519::load_verified_message;
+use crate::errors::HaloError;
+use crate::state::{EncryptedTrustScore, PrivacyConfig, Treasury, TrustTier};
+
+/// Statement signed by the attestor: the encrypted score with the given hash
+/// belongs to `authority` and is at least `min_tier`. Serialized with borsh
+/// and prefixed by `TIER_CLAIM_DOMAIN` as the ed25519 message.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone)]
+pub struct TrustTierClaim {
+    pub authority: Pubkey,
+    pub encrypted_score_hash: [u8; 32],
+    pub min_tier: u8,
+    pub expires_at: i64,
+}
+
+impl TrustTierClaim {
+    pub const TIER_CLAIM_DOMAIN: &'static [u8] = b"halo:trust-tier-claim:v1";
+}
+
+/// Verify the attested tier claim in the preceding ed25519 instruction and
+/// return the tier it proves for `authority`.
+pub(crate) fn verify_tier_claim(
+    encrypted_trust_score: &EncryptedTrustScore,
+    privacy_config: &PrivacyConfig,
+    instructions_sysvar: &AccountInfo,
+    authority: Pubkey,
+    now: i64,
+) -> Result<TrustTier> {
+    require!(encrypted_trust_score.privacy_enabled, HaloError::PrivacyNotEnabled);
+
+    let (signer, message) = load_verified_message(instructions_sysvar)?;
+    require!(signer == privacy_config.attestor, HaloError::InvalidAttestation);
+
+    let payload = message
+        .strip_prefix(TrustTierClaim::TIER_CLAIM_DOMAIN)
+        .ok_or(HaloError::InvalidAttestation)?;
+    let claim = TrustTierClaim::try_from_slice(payload).map_err(|_| HaloError::InvalidAttestation)?;
+
+    require!(claim.authority == authority, HaloError::InvalidAttestation);
+    require!(
+        claim.encrypted_score_hash == hash(&encrypted_trust_score.encrypted_score).to_bytes(),
+        HaloError::InvalidAttestation
+    );
+    require!(claim.expires_at >= now, HaloError::AttestationExpired);
+
+    TrustTier::from_u8(claim.min_tier).ok_or(HaloError::InvalidTrustTier.into())
+}
+
+pub(crate) fn initialize_privacy_config(ctx: Context<InitializePrivacyConfig>, attestor: Pubkey) -> Result<()> {
+    let privacy_config = &mut ctx.accounts.privacy_config;
+    privacy_config.authority = ctx.accounts.authority.key();
+    privacy_config.attestor = attestor;
+    privacy_config.bump = ctx.bumps.privacy_config;
+
+    msg!("Privacy config initialized with attestor {}", attestor);
+    Ok(())
+}
+
+pub(crate) fn set_trust_attestor(ctx: Context<SetTrustAttestor>, attestor: Pubkey) -> Result<()> {
+    ctx.accounts.privacy_config.attestor = attestor;
+
+    msg!("Trust attestor set to {}", attestor);
+    Ok(())
+}
+
+/// Opt into keeping the raw trust score encrypted
+pub(crate) fn enable_private_trust_score(
+    ctx: Context<EnablePrivateTrustScore>,
+    encrypted_score: Vec<u8>,
+    arcium_compute_key: Pubkey,
+) -> Result<()> {
+    require!(
+        !encrypted_score.is_empty() && encrypted_score.len() <= EncryptedTrustScore::MAX_ENCRYPTED_SCORE_LEN,
+        HaloError::InvalidEncryptedScore
+    );
+
+    let encrypted_trust_score = &mut ctx.accounts.encrypted_trust_score;
+    encrypted_trust_score.authority = ctx.accounts.authority.key();
+    encrypted_trust_score.encrypted_score = encrypted_score;
+    encrypted_trust_score.arcium_compute_key = arcium_compute_key;
+    encrypted_trust_score.privacy_enabled = true;
+    encrypted_trust_score.last_updated = Clock::get()?.unix_timestamp;
+    encrypted_trust_score.bump = ctx.bumps.encrypted_trust_score;
+
+    msg!("Private trust score enabled for {}", encrypted_trust_score.authority);
+    Ok(())
+}
+
+/// Replace the ciphertext after a new MPC computation. Claims signed for the
+/// previous ciphertext stop verifying.
+pub(crate) fn update_encrypted_trust_score(
+    ctx: Context<UpdateEncryptedTrustScore>,
+    encrypted_score: Vec<u8>,
+) -> Result<()> {
+    require!(
+        !encrypted_score.is_empty() && encrypted_score.len() <= EncryptedTrustScore::MAX_ENCRYPTED_SCORE_LEN,
+        HaloError::InvalidEncryptedScore
+    );
+
+    let encrypted_trust_score = &mut ctx.accounts.encrypted_trust_score;
+    encrypted_trust_score.encrypted_score = encrypted_score;
+    encrypted_trust_score.last_updated = Clock::get()?.unix_timestamp;
+
+    msg!("Encrypted trust score updated for {}", encrypted_trust_score.authority);
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct InitializePrivacyConfig<'info> {
+    #[account(
+        init,
+        payer = authority,
+        space = PrivacyConfig::SPACE,
+        seeds = [b"privacy_config"],
+        bump
+    )]
+    pub privacy_config: Account<'info, PrivacyConfig>,
+
+    #[account(
+        seeds = [b"treasury"],
+        bump = treasury.bump,
+        constraint = treasury.authority == authority.key() @ HaloError::UnauthorizedMember
+    )]
+    pub treasury: Account<'info, Treasury>,
+
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct SetTrustAttestor<'info> {
+    #[account(
+        mut,
+        seeds = [b"privacy_config"],
+        bump = privacy_config.bump,
+        has_one = authority @ HaloError::UnauthorizedMember
+    )]
+    pub privacy_config: Account<'info, PrivacyConfig>,
+
+    pub authority: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct EnablePrivateTrustScore<'info> {
+    #[account(
+        init,
+        payer = authority,
+        space = EncryptedTrustScore::space(),
+        seeds = [b"encrypted_trust_score", authority.key().as_ref()],
+        bump
+    )]
+    pub encrypted_trust_score: Account<'info, EncryptedTrustScore>,
+
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct UpdateEncryptedTrustScore<'info> {
+    #[account(
+        mut,
+        seeds = [b"encrypted_trust_score", authority.key().as_ref()],
+        bump = encrypted_trust_score.bump,
+        has_one = authority @ HaloError::UnauthorizedMember
+    )]
+    pub encrypted_trust_score: Account<'info, EncryptedTrustScore>,
+
+    pub authority: Signer<'info>,
+}
@@ -0,0 +1,484 @@
+use anchor_lang::prelude::*;
+
+use crate::errors::HaloError;
+use crate::instructions::{tally_vote, ProposalCreated};
+use crate::state::{
+    AutomationState, GovernanceConfig, GovernanceProposal, ProposalStatus, ProposalType, ProtocolAction,
+    RevenueParams, TallyMode, Treasury, TrustScore, TrustTierThresholds, Vote, VotingPowerSource,
+};
+
+/// Protocol-wide governance realm. Its proposals are `GovernanceProposal`
+/// accounts keyed by the realm instead of a circle, so they share voting,
+/// `change_vote` and `finalize_proposal` with circle governance.
+#[account]
+pub struct ProtocolRealm {
+    /// Authority that manages the voter registry
+    pub authority: Pubkey,
+    /// Number of proposals created; the next proposal's id
+    pub proposal_count: u64,
+    /// Number of registered voters
+    pub voter_count: u8,
+    /// Share of registered voters that must vote, in basis points
+    pub quorum_bps: u16,
+    /// Delay between a proposal succeeding and becoming executable
+    pub timelock_seconds: i64,
+    /// Scores at which trust tiers start
+    pub trust_tier_thresholds: TrustTierThresholds,
+    /// Bump seed for PDA
+    pub bump: u8,
+}
+
+impl ProtocolRealm {
+    pub const SPACE: usize = 8 + // discriminator
+        32 + // authority
+        8 + // proposal_count
+        1 + // voter_count
+        2 + // quorum_bps
+        8 + // timelock_seconds
+        TrustTierThresholds::SPACE + // trust_tier_thresholds
+        1 + // bump
+        50; // padding
+}
+
+/// A voter registered with the protocol realm
+#[account]
+pub struct ProtocolVoter {
+    /// The voter's wallet
+    pub voter: Pubkey,
+    /// Linear voting power on realm proposals
+    pub voting_power: u64,
+    /// Bump seed for PDA
+    pub bump: u8,
+}
+
+impl ProtocolVoter {
+    pub const SPACE: usize = 8 + // discriminator
+        32 + // voter
+        8 + // voting_power
+        1 + // bump
+        50; // padding
+}
+
+pub(crate) fn initialize_protocol_realm(
+    ctx: Context<InitializeProtocolRealm>,
+    quorum_bps: u16,
+    timelock_seconds: i64,
+) -> Result<()> {
+    require!(quorum_bps > 0 && quorum_bps <= 10000, HaloError::InvalidGovernanceConfig);
+    require!(
+        (0..=GovernanceConfig::MAX_TIMELOCK_SECONDS).contains(&timelock_seconds),
+        HaloError::InvalidGovernanceConfig
+    );
+
+    let realm = &mut ctx.accounts.realm;
+    realm.authority = ctx.accounts.authority.key();
+    realm.proposal_count = 0;
+    realm.voter_count = 0;
+    realm.quorum_bps = quorum_bps;
+    realm.timelock_seconds = timelock_seconds;
+    realm.trust_tier_thresholds = TrustTierThresholds::default();
+    realm.bump = ctx.bumps.realm;
+
+    msg!("Protocol realm initialized with quorum {} bps", quorum_bps);
+    Ok(())
+}
+
+pub(crate) fn register_protocol_voter(
+    ctx: Context<RegisterProtocolVoter>,
+    voter: Pubkey,
+    voting_power: u64,
+) -> Result<()> {
+    require!(voting_power > 0, HaloError::InsufficientVotingPower);
+
+    let protocol_voter = &mut ctx.accounts.protocol_voter;
+    protocol_voter.voter = voter;
+    protocol_voter.voting_power = voting_power;
+    protocol_voter.bump = ctx.bumps.protocol_voter;
+
+    let realm = &mut ctx.accounts.realm;
+    realm.voter_count = realm.voter_count
+        .checked_add(1)
+        .ok_or(HaloError::ArithmeticOverflow)?;
+
+    msg!("Protocol voter {} registered with power {}", voter, voting_power);
+    Ok(())
+}
+
+pub(crate) fn remove_protocol_voter(ctx: Context<RemoveProtocolVoter>) -> Result<()> {
+    let realm = &mut ctx.accounts.realm;
+    realm.voter_count = realm.voter_count
+        .checked_sub(1)
+        .ok_or(HaloError::ArithmeticOverflow)?;
+
+    msg!("Protocol voter {} removed", ctx.accounts.protocol_voter.voter);
+    Ok(())
+}
+
+pub(crate) fn create_protocol_proposal(
+    ctx: Context<CreateProtocolProposal>,
+    title: String,
+    description: String,
+    action: ProtocolAction,
+    voting_duration_hours: u16,
+) -> Result<()> {
+    require!(title.len() <= GovernanceProposal::MAX_TITLE_LENGTH, HaloError::InvalidProposalType);
+    require!(description.len() <= GovernanceProposal::MAX_DESCRIPTION_LENGTH, HaloError::InvalidProposalType);
+    require!(voting_duration_hours > 0 && voting_duration_hours <= 7 * 24, HaloError::InvalidVotingPeriod); // Max 7 days
+    action.validate()?;
+
+    let clock = Clock::get()?;
+    let realm = &mut ctx.accounts.realm;
+    let proposal = &mut ctx.accounts.proposal;
+    let voting_end = clock.unix_timestamp + (voting_duration_hours as i64 * 3600);
+
+    proposal.id = realm.proposal_count;
+    proposal.circle = realm.key();
+    proposal.proposer = ctx.accounts.proposer.key();
+    proposal.title = title;
+    proposal.description = description;
+    proposal.proposal_type = ProposalType::Protocol;
+    proposal.status = ProposalStatus::Active;
+    proposal.voting_start = clock.unix_timestamp;
+    proposal.voting_end = voting_end;
+    proposal.execution_threshold = 0;
+    proposal.total_voting_power = 0;
+    proposal.votes_for = 0;
+    proposal.votes_against = 0;
+    proposal.quadratic_votes_for = 0;
+    proposal.quadratic_votes_against = 0;
+    proposal.executed = false;
+    proposal.executed_at = None;
+    proposal.new_interest_rate = None;
+    proposal.bump = ctx.bumps.proposal;
+    // Realm voting power comes from the voter registry and is tallied linearly
+    proposal.voting_power_source = VotingPowerSource::default();
+    proposal.governance_mint = None;
+    proposal.quorum_bps = realm.quorum_bps;
+    proposal.tally_mode = TallyMode::Linear;
+    proposal.timelock_seconds = realm.timelock_seconds;
+    proposal.eligible_voters = realm.voter_count;
+    proposal.voter_count = 0;
+    proposal.executable_at = 0;
+    proposal.delegated_voters = Vec::new();
+    proposal.token_snapshot = Vec::new();
+    proposal.protocol_action = Some(action);
+    proposal.action = None;
+
+    realm.proposal_count = realm.proposal_count
+        .checked_add(1)
+        .ok_or(HaloError::ArithmeticOverflow)?;
+
+    emit!(ProposalCreated {
+        proposal_id: proposal.id,
+        circle: proposal.circle,
+        proposer: proposal.proposer,
+        proposal_type: 3,
+        voting_end,
+    });
+
+    Ok(())
+}
+
+pub(crate) fn cast_protocol_vote(ctx: Context<CastProtocolVote>, support: bool) -> Result<()> {
+    let proposal = &mut ctx.accounts.proposal;
+    let vote = &mut ctx.accounts.vote;
+    let clock = Clock::get()?;
+
+    require!(proposal.is_active(), HaloError::ProposalNotActive);
+    require!(clock.unix_timestamp >= proposal.voting_start, HaloError::VotingPeriodNotStarted);
+    require!(!proposal.voting_ended(clock.unix_timestamp), HaloError::VotingPeriodEnded);
+
+    vote.proposal = proposal.key();
+    vote.voter = ctx.accounts.voter.key();
+    vote.support = support;
+    vote.timestamp = clock.unix_timestamp;
+    vote.bump = ctx.bumps.vote;
+
+    tally_vote(proposal, vote, ctx.accounts.protocol_voter.voting_power, Vec::new(), true)?;
+
+    Ok(())
+}
+
+/// Apply a succeeded realm proposal once its timelock has passed
+pub(crate) fn execute_protocol_proposal(ctx: Context<ExecuteProtocolProposal>) -> Result<()> {
+    let proposal = &mut ctx.accounts.proposal;
+    let clock = Clock::get()?;
+
+    require!(proposal.status == ProposalStatus::Succeeded, HaloError::ProposalNotSucceeded);
+    require!(!proposal.executed, HaloError::ProposalAlreadyExecuted);
+    require!(clock.unix_timestamp >= proposal.executable_at, HaloError::TimelockNotElapsed);
+
+    let action = proposal.protocol_action.as_ref().ok_or(HaloError::InvalidProposalAction)?;
+    match action {
+        ProtocolAction::SetRevenueFees {
+            distribution_fee_rate,
+            yield_fee_rate,
+            management_fee_rate,
+            management_fee_interval,
+        } => {
+            let params = ctx.accounts.revenue_params
+                .as_mut()
+                .ok_or(HaloError::MissingProposalAccount)?;
+            if let Some(rate) = distribution_fee_rate {
+                params.distribution_fee_rate = *rate;
+            }
+            if let Some(rate) = yield_fee_rate {
+                params.yield_fee_rate = *rate;
+            }
+            if let Some(rate) = management_fee_rate {
+                params.management_fee_rate = *rate;
+            }
+            if let Some(interval) = management_fee_interval {
+                params.management_fee_interval = *interval;
+            }
+            params.last_updated = clock.unix_timestamp;
+        },
+        ProtocolAction::SetAutomationSettings { enabled, min_interval } => {
+            let automation_state = ctx.accounts.automation_state
+                .as_mut()
+                .ok_or(HaloError::MissingProposalAccount)?;
+            if let Some(enabled) = enabled {
+                automation_state.enabled = *enabled;
+            }
+            if let Some(interval) = min_interval {
+                automation_state.min_interval = *interval;
+            }
+        },
+        ProtocolAction::SetTrustTierThresholds { thresholds } => {
+            ctx.accounts.realm.trust_tier_thresholds = thresholds.clone();
+        },
+        ProtocolAction::SetFeeSchedule { schedule } => {
+            let params = ctx.accounts.revenue_params
+                .as_mut()
+                .ok_or(HaloError::MissingProposalAccount)?;
+            params.fee_schedule = schedule.clone();
+            params.last_updated = clock.unix_timestamp;
+        },
+    }
+
+    proposal.executed = true;
+    proposal.executed_at = Some(clock.unix_timestamp);
+    proposal.status = ProposalStatus::Executed;
+
+    emit!(ProtocolProposalExecuted {
+        proposal_id: proposal.id,
+        executor: ctx.accounts.executor.key(),
+        executed_at: clock.unix_timestamp,
+    });
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct InitializeProtocolRealm<'info> {
+    #[account(
+        init,
+        payer = authority,
+        space = ProtocolRealm::SPACE,
+        seeds = [b"protocol_realm"],
+        bump
+    )]
+    pub realm: Account<'info, ProtocolRealm>,
+
+    #[account(
+        seeds = [b"treasury"],
+        bump = treasury.bump,
+        constraint = treasury.authority == authority.key() @ HaloError::UnauthorizedRevenueOperation
+    )]
+    pub treasury: Account<'info, Treasury>,
+
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+#[instruction(voter: Pubkey)]
+pub struct RegisterProtocolVoter<'info> {
+    #[account(
+        init,
+        payer = authority,
+        space = ProtocolVoter::SPACE,
+        seeds = [b"protocol_voter", voter.as_ref()],
+        bump
+    )]
+    pub protocol_voter: Account<'info, ProtocolVoter>,
+
+    #[account(
+        mut,
+        seeds = [b"protocol_realm"],
+        bump = realm.bump,
+        has_one = authority @ HaloError::UnauthorizedMember
+    )]
+    pub realm: Account<'info, ProtocolRealm>,
+
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct RemoveProtocolVoter<'info> {
+    #[account(
+        mut,
+        seeds = [b"protocol_voter", protocol_voter.voter.as_ref()],
+        bump = protocol_voter.bump,
+        close = authority
+    )]
+    pub protocol_voter: Account<'info, ProtocolVoter>,
+
+    #[account(
+        mut,
+        seeds = [b"protocol_realm"],
+        bump = realm.bump,
+        has_one = authority @ HaloError::UnauthorizedMember
+    )]
+    pub realm: Account<'info, ProtocolRealm>,
+
+    #[account(mut)]
+    pub authority: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct CreateProtocolProposal<'info> {
+    #[account(
+        init,
+        payer = proposer,
+        space = GovernanceProposal::space(),
+        seeds = [b"proposal", realm.key().as_ref(), &realm.proposal_count.to_le_bytes()],
+        bump
+    )]
+    pub proposal: Account<'info, GovernanceProposal>,
+
+    #[account(
+        mut,
+        seeds = [b"protocol_realm"],
+        bump = realm.bump
+    )]
+    pub realm: Account<'info, ProtocolRealm>,
+
+    /// Only registered voters can propose
+    #[account(
+        seeds = [b"protocol_voter", proposer.key().as_ref()],
+        bump = proposer_voter.bump
+    )]
+    pub proposer_voter: Account<'info, ProtocolVoter>,
+
+    #[account(mut)]
+    pub proposer: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct CastProtocolVote<'info> {
+    #[account(
+        mut,
+        seeds = [b"proposal", realm.key().as_ref(), &proposal.id.to_le_bytes()],
+        bump = proposal.bump
+    )]
+    pub proposal: Account<'info, GovernanceProposal>,
+
+    #[account(
+        seeds = [b"protocol_realm"],
+        bump = realm.bump
+    )]
+    pub realm: Account<'info, ProtocolRealm>,
+
+    #[account(
+        init,
+        payer = voter,
+        space = Vote::space(),
+        seeds = [b"vote", proposal.key().as_ref(), voter.key().as_ref()],
+        bump
+    )]
+    pub vote: Account<'info, Vote>,
+
+    #[account(
+        seeds = [b"protocol_voter", voter.key().as_ref()],
+        bump = protocol_voter.bump
+    )]
+    pub protocol_voter: Account<'info, ProtocolVoter>,
+
+    #[account(mut)]
+    pub voter: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct ExecuteProtocolProposal<'info> {
+    #[account(
+        mut,
+        seeds = [b"proposal", realm.key().as_ref(), &proposal.id.to_le_bytes()],
+        bump = proposal.bump
+    )]
+    pub proposal: Account<'info, GovernanceProposal>,
+
+    #[account(
+        mut,
+        seeds = [b"protocol_realm"],
+        bump = realm.bump
+    )]
+    pub realm: Account<'info, ProtocolRealm>,
+
+    /// Required for `ProtocolAction::SetRevenueFees` and `SetFeeSchedule`
+    #[account(
+        mut,
+        seeds = [b"revenue_params"],
+        bump = revenue_params.bump
+    )]
+    pub revenue_params: Option<Account<'info, RevenueParams>>,
+
+    /// Required for `ProtocolAction::SetAutomationSettings`
+    #[account(
+        mut,
+        seeds = [b"automation_state"],
+        bump = automation_state.bump
+    )]
+    pub automation_state: Option<Account<'info, AutomationState>>,
+
+    /// Only registered voters can execute
+    #[account(
+        seeds = [b"protocol_voter", executor.key().as_ref()],
+        bump = executor_voter.bump
+    )]
+    pub executor_voter: Account<'info, ProtocolVoter>,
+
+    pub executor: Signer<'info>,
+}
+
+/// Anyone may push the realm's current tier thresholds to a trust score, so
+/// a threshold change reaches scores whose owners never refresh them
+pub(crate) fn sync_trust_tier_thresholds(ctx: Context<SyncTrustTierThresholds>) -> Result<()> {
+    let trust_score = &mut ctx.accounts.trust_score;
+    trust_score.sync_tier_thresholds(&ctx.accounts.realm.trust_tier_thresholds);
+
+    msg!("Trust tier thresholds synced for {}: tier {:?}", trust_score.authority, trust_score.tier);
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct SyncTrustTierThresholds<'info> {
+    #[account(
+        mut,
+        seeds = [b"trust_score", trust_score.authority.as_ref()],
+        bump = trust_score.bump
+    )]
+    pub trust_score: Account<'info, TrustScore>,
+
+    #[account(
+        seeds = [b"protocol_realm"],
+        bump = realm.bump
+    )]
+    pub realm: Account<'info, ProtocolRealm>,
+}
+
+#[event]
+pub struct ProtocolProposalExecuted {
+    pub proposal_id: u64,
+    pub executor: Pubkey,
+    pub executed_at: i64,
+}
@@ -2,25 +2,76 @@ use anchor_lang::prelude::*;
 use anchor_spl::token::{self, Token, TokenAccount, Transfer};
 
 use crate::errors::HaloError;
-use crate::state::{Treasury, RevenueParams, RevenueReport};
+use crate::state::{Circle, CircleEscrow, CircleFeeLedger, CircleStatus, Member, MemberStatus, Treasury, RevenueParams, RevenueReport, RevenueEpoch, TierFeeSchedule, TrustTier};
 
 /// Initialize the global treasury account
 pub(crate) fn initialize_treasury(ctx: Context<InitializeTreasury>) -> Result<()> {
     let treasury = &mut ctx.accounts.treasury;
-    let clock = Clock::get()?;
 
     treasury.authority = ctx.accounts.authority.key();
     treasury.total_fees_collected = 0;
     treasury.distribution_fees = 0;
     treasury.yield_fees = 0;
     treasury.management_fees = 0;
-    treasury.last_management_fee_collection = clock.unix_timestamp;
+    treasury.last_management_fee_collection = 0;
     treasury.bump = ctx.bumps.treasury;
+    treasury.keeper_bounty = 0;
+    treasury.keeper_rewards_paid = 0;
+    treasury.revenue_epochs = vec![RevenueEpoch::default(); Treasury::EPOCH_HISTORY];
 
     msg!("Treasury initialized with authority: {}", treasury.authority);
     Ok(())
 }
 
+/// Grow a treasury created before revenue epochs were tracked to the
+/// current layout and give it an empty epoch history. Anyone may call this;
+/// the payer covers the added rent.
+pub(crate) fn migrate_treasury(ctx: Context<MigrateTreasury>) -> Result<()> {
+    let treasury = &mut ctx.accounts.treasury;
+    if treasury.revenue_epochs.len() != Treasury::EPOCH_HISTORY {
+        treasury.revenue_epochs.resize(Treasury::EPOCH_HISTORY, RevenueEpoch::default());
+    }
+
+    msg!("Treasury migrated to {} bytes", Treasury::SPACE);
+    Ok(())
+}
+
+/// Fill in the stake and fee timer of a circle created before management
+/// fees were charged per circle. Remaining accounts are the circle's member
+/// accounts in the order of `circle.members`. Fees are charged from when
+/// the treasury last collected them under the old global timer.
+pub(crate) fn migrate_circle_stake(ctx: Context<MigrateCircleStake>) -> Result<()> {
+    let circle = &mut ctx.accounts.circle;
+    require!(!circle.fee_ledger.is_migrated(), HaloError::CircleAlreadyMigrated);
+    require!(
+        ctx.remaining_accounts.len() == circle.members.len(),
+        HaloError::InvalidMemberAccounts
+    );
+
+    let circle_key = circle.key();
+    let mut total_stake = 0u64;
+    for (info, authority) in ctx.remaining_accounts.iter().zip(circle.members.iter()) {
+        require!(info.owner == &crate::ID, HaloError::InvalidMemberAccounts);
+        let member = Member::try_deserialize(&mut &info.try_borrow_data()?[..])?;
+        require!(
+            member.circle == circle_key && member.authority == *authority,
+            HaloError::InvalidMemberAccounts
+        );
+        if member.status != MemberStatus::Exited {
+            total_stake = total_stake
+                .checked_add(member.stake_amount)
+                .ok_or(HaloError::ArithmeticOverflow)?;
+        }
+    }
+
+    circle.total_stake = total_stake;
+    circle.fee_ledger.last_management_fee_at = circle.created_at
+        .max(ctx.accounts.treasury.last_management_fee_collection);
+
+    msg!("Circle {} migrated with stake {}", circle_key, total_stake);
+    Ok(())
+}
+
 /// Initialize revenue parameters with default values
 pub(crate) fn initialize_revenue_params(ctx: Context<InitializeRevenueParams>) -> Result<()> {
     let params = &mut ctx.accounts.revenue_params;
@@ -35,87 +86,138 @@ pub(crate) fn initialize_revenue_params(ctx: Context<InitializeRevenueParams>) -
     params.management_fee_interval = interval;
     params.last_updated = clock.unix_timestamp;
     params.bump = ctx.bumps.revenue_params;
+    params.fee_schedule = TierFeeSchedule::default();
 
     msg!("Revenue parameters initialized with defaults");
     Ok(())
 }
 
-/// Update revenue parameters (governance only)
-pub(crate) fn update_revenue_params(
-    ctx: Context<UpdateRevenueParams>,
-    distribution_fee_rate: Option<u16>,
-    yield_fee_rate: Option<u16>,
-    management_fee_rate: Option<u16>,
-    management_fee_interval: Option<i64>,
-) -> Result<()> {
-    let params = &mut ctx.accounts.revenue_params;
-    let clock = Clock::get()?;
-    
-    // Validate fee rates don't exceed 10% (1000 basis points)
-    if let Some(rate) = distribution_fee_rate {
-        require!(rate <= 1000, HaloError::InvalidFeeRate);
-        params.distribution_fee_rate = rate;
-    }
-    
-    if let Some(rate) = yield_fee_rate {
-        require!(rate <= 1000, HaloError::InvalidFeeRate);
-        params.yield_fee_rate = rate;
-    }
+/// Collect the management fee accrued on one circle's stake. Fails with
+/// `CircleNotMigrated` until `migrate_circle_stake` has run for the circle.
+pub(crate) fn collect_management_fees(ctx: Context<CollectManagementFees>) -> Result<()> {
+    let accounts = ctx.accounts;
+    require!(accounts.circle.status == CircleStatus::Active, HaloError::CircleNotActive);
+    
+    let management_fee = charge_management_fee(
+        &mut accounts.treasury,
+        &accounts.revenue_params,
+        &mut accounts.circle,
+        &mut accounts.escrow,
+        &accounts.escrow_token_account,
+        &accounts.treasury_token_account.to_account_info(),
+        &accounts.token_program.to_account_info(),
+    )?
+    .ok_or(HaloError::RevenueCollectionTooFrequent)?;
     
-    if let Some(rate) = management_fee_rate {
-        require!(rate <= 1000, HaloError::InvalidFeeRate);
-        params.management_fee_rate = rate;
-    }
+    msg!("Management fees collected: {} tokens", management_fee);
+    Ok(())
+}
+
+/// Collect management fees from several circles in one transaction.
+///
+/// Remaining accounts come in groups of three per circle: the circle, its
+/// escrow and the escrow token account, all writable. Circles that are not
+/// active, not yet migrated or not yet due are skipped.
+pub(crate) fn collect_management_fees_batch<'info>(
+    ctx: Context<'_, '_, 'info, 'info, CollectManagementFeesBatch<'info>>,
+) -> Result<()> {
+    let groups = ctx.remaining_accounts.chunks_exact(3);
+    require!(
+        groups.len() > 0 && groups.remainder().is_empty(),
+        HaloError::InvalidManagementFeeBatch
+    );
     
-    if let Some(interval) = management_fee_interval {
-        require!(interval >= 86400, HaloError::InvalidFeeRate); // At least 1 day
-        params.management_fee_interval = interval;
+    let treasury_token_account = ctx.accounts.treasury_token_account.to_account_info();
+    let token_program = ctx.accounts.token_program.to_account_info();
+    let mut circles_charged: u32 = 0;
+    let mut total_fees: u64 = 0;
+    
+    for group in groups {
+        let mut circle = Account::<Circle>::try_from(&group[0])?;
+        let mut escrow = Account::<CircleEscrow>::try_from(&group[1])?;
+        let escrow_token_account = Account::<TokenAccount>::try_from(&group[2])?;
+        require!(
+            circle.escrow_account == escrow.key() && escrow.circle == circle.key(),
+            HaloError::InvalidManagementFeeBatch
+        );
+        require!(
+            escrow_token_account.owner == escrow.key() &&
+            escrow_token_account.mint == ctx.accounts.treasury_token_account.mint,
+            HaloError::InvalidManagementFeeBatch
+        );
+        if !circle.fee_ledger.is_migrated() {
+            continue;
+        }
+        
+        let Some(fee) = charge_management_fee(
+            &mut ctx.accounts.treasury,
+            &ctx.accounts.revenue_params,
+            &mut circle,
+            &mut escrow,
+            &escrow_token_account,
+            &treasury_token_account,
+            &token_program,
+        )? else {
+            continue;
+        };
+        
+        circle.exit(&crate::ID)?;
+        escrow.exit(&crate::ID)?;
+        circles_charged = circles_charged.checked_add(1).ok_or(HaloError::ArithmeticOverflow)?;
+        total_fees = total_fees.checked_add(fee).ok_or(HaloError::ArithmeticOverflow)?;
     }
     
-    params.last_updated = clock.unix_timestamp;
-    
-    msg!("Revenue parameters updated by governance");
+    msg!("Management fees of {} collected from {} circles", total_fees, circles_charged);
     Ok(())
 }
 
-/// Collect management fees from all active stakes
-pub(crate) fn collect_management_fees(ctx: Context<CollectManagementFees>) -> Result<()> {
-    let treasury = &mut ctx.accounts.treasury;
-    let params = &ctx.accounts.revenue_params;
-    let clock = Clock::get()?;
-    
-    // Check if enough time has passed since last collection
-    let time_since_last = clock.unix_timestamp - treasury.last_management_fee_collection;
-    require!(
-        time_since_last >= params.management_fee_interval,
-        HaloError::RevenueCollectionTooFrequent
-    );
+/// Charge a circle's stake for the management fee accrued since its last
+/// collection. Returns `None` if the circle is inactive or not yet due, and
+/// fails with `CircleNotMigrated` if its fee timer was never started.
+fn charge_management_fee<'info>(
+    treasury: &mut Treasury,
+    params: &RevenueParams,
+    circle: &mut Account<'info, Circle>,
+    escrow: &mut Account<'info, CircleEscrow>,
+    escrow_token_account: &Account<'info, TokenAccount>,
+    treasury_token_account: &AccountInfo<'info>,
+    token_program: &AccountInfo<'info>,
+) -> Result<Option<u64>> {
+    let now = Clock::get()?.unix_timestamp;
+    let elapsed = circle.fee_ledger.management_fee_elapsed(now)?;
+    if circle.status != CircleStatus::Active || elapsed < params.management_fee_interval {
+        return Ok(None);
+    }
     
-    // Calculate management fee based on total stake in escrow
-    let escrow_balance = ctx.accounts.escrow_token_account.amount;
-    let management_fee = params.calculate_management_fee(escrow_balance, time_since_last)?;
+    // Only stake accrues the fee; pots waiting to be paid out are not charged.
+    // Stake is pooled, so the discount is the one for the circle's minimum
+    // tier, which every member has met.
+    let stake = circle.total_stake;
+    let tier = TrustTier::from_u8(circle.min_trust_tier).unwrap_or_default();
+    let (management_fee, fee_discount) = params.calculate_discounted_management_fee(stake, elapsed, &tier)?;
+    let management_fee = management_fee.min(escrow_token_account.amount);
     
     if management_fee > 0 {
-        // Transfer management fee to treasury
         let cpi_accounts = Transfer {
-            from: ctx.accounts.escrow_token_account.to_account_info(),
-            to: ctx.accounts.treasury_token_account.to_account_info(),
-            authority: ctx.accounts.escrow.to_account_info(),
+            from: escrow_token_account.to_account_info(),
+            to: treasury_token_account.clone(),
+            authority: escrow.to_account_info(),
         };
-        let cpi_program = ctx.accounts.token_program.to_account_info();
         
         // Create signer seeds for escrow PDA
-        let circle_key = ctx.accounts.circle.key();
+        let circle_key = circle.key();
         let seeds = &[
             b"escrow".as_ref(),
             circle_key.as_ref(),
-            &[ctx.accounts.escrow.bump],
+            &[escrow.bump],
         ];
         let signer = &[&seeds[..]];
         
-        let cpi_ctx = CpiContext::new_with_signer(cpi_program, cpi_accounts, signer);
+        let cpi_ctx = CpiContext::new_with_signer(token_program.clone(), cpi_accounts, signer);
         token::transfer(cpi_ctx, management_fee)?;
         
+        escrow.total_amount = escrow.total_amount.saturating_sub(management_fee);
+        
         // Update treasury tracking
         treasury.management_fees = treasury.management_fees
             .checked_add(management_fee)
@@ -124,11 +226,22 @@ pub(crate) fn collect_management_fees(ctx: Context<CollectManagementFees>) -> Re
             .checked_add(management_fee)
             .ok_or(HaloError::ArithmeticOverflow)?;
     }
-    
-    treasury.last_management_fee_collection = clock.unix_timestamp;
-    
-    msg!("Management fees collected: {} tokens", management_fee);
-    Ok(())
+    record_fee_activity(
+        treasury,
+        Some(&mut circle.fee_ledger),
+        FeeActivity::Management { stake, fee: management_fee },
+        now,
+    )?;
+    circle.fee_ledger.last_management_fee_at = now;
+    
+    emit!(ManagementFeeCollected {
+        circle: circle.key(),
+        stake,
+        elapsed,
+        fee: management_fee,
+        fee_discount,
+    });
+    Ok(Some(management_fee))
 }
 
 /// Distribute yield with fee collection (for Solend integration)
@@ -137,6 +250,12 @@ pub(crate) fn distribute_yield(ctx: Context<DistributeYield>, yield_amount: u64)
     let params = &ctx.accounts.revenue_params;
     
     require!(yield_amount > 0, HaloError::InvalidContributionAmount);
+    if let Some(circle) = &ctx.accounts.circle {
+        require!(
+            ctx.accounts.recipient_token_account.owner == circle.escrow_account,
+            HaloError::YieldRecipientNotCircleEscrow
+        );
+    }
     
     // Calculate yield fee (0.25% by default)
     let yield_fee = params.calculate_yield_fee(yield_amount)?;
@@ -156,6 +275,12 @@ pub(crate) fn distribute_yield(ctx: Context<DistributeYield>, yield_amount: u64)
             None, // No seeds needed for user authority
         )?;
     }
+    record_fee_activity(
+        treasury,
+        ctx.accounts.circle.as_mut().map(|circle| &mut circle.fee_ledger),
+        FeeActivity::Yield { amount: yield_amount, fee: yield_fee },
+        Clock::get()?.unix_timestamp,
+    )?;
 
     // Transfer remaining yield to recipient
     let cpi_accounts = Transfer {
@@ -171,42 +296,146 @@ pub(crate) fn distribute_yield(ctx: Context<DistributeYield>, yield_amount: u64)
          yield_amount, yield_fee, net_yield_amount);
     Ok(())
 }
+/// Summarise protocol revenue over whole, closed epochs from the treasury's
+/// epoch buckets
 pub(crate) fn create_revenue_report(
     ctx: Context<CreateRevenueReport>,
     period_start: i64,
     period_end: i64,
 ) -> Result<()> {
-    require!(period_start < period_end, HaloError::InvalidRevenueReportPeriod);
+    let clock = Clock::get()?;
+    require!(
+        period_start >= 0 &&
+        period_start < period_end &&
+        period_end <= clock.unix_timestamp &&
+        period_start % Treasury::EPOCH_SECONDS == 0 &&
+        period_end % Treasury::EPOCH_SECONDS == 0,
+        HaloError::InvalidRevenueReportPeriod
+    );
+    
+    let first_epoch = Treasury::epoch_at(period_start);
+    let end_epoch = Treasury::epoch_at(period_end);
+    require!(
+        Treasury::epoch_at(clock.unix_timestamp) - first_epoch < Treasury::EPOCH_HISTORY as u64,
+        HaloError::RevenueHistoryExpired
+    );
     
     let report = &mut ctx.accounts.revenue_report;
     let treasury = &ctx.accounts.treasury;
     
     report.period_start = period_start;
     report.period_end = period_end;
-    
-    // For now, we'll use the current treasury totals as period totals
-    // In a production system, you'd want to track period-specific data
-    report.total_period_fees = treasury.total_fees_collected;
-    report.period_distribution_fees = treasury.distribution_fees;
-    report.period_yield_fees = treasury.yield_fees;
-    report.period_management_fees = treasury.management_fees;
-    
-    // These would be calculated from actual data in production
+    report.total_period_fees = 0;
+    report.period_distribution_fees = 0;
+    report.period_yield_fees = 0;
+    report.period_management_fees = 0;
     report.active_circles = 0;
     report.total_distributions = 0;
     report.total_yield = 0;
     report.total_managed_stake = 0;
     
+    // Epochs without a bucket had no fee activity
+    for bucket in (first_epoch..end_epoch).filter_map(|epoch| treasury.revenue_epoch(epoch)) {
+        report.total_period_fees = bucket.total_fees()
+            .and_then(|fees| report.total_period_fees.checked_add(fees))
+            .ok_or(HaloError::ArithmeticOverflow)?;
+        report.period_distribution_fees = report.period_distribution_fees
+            .checked_add(bucket.distribution_fees)
+            .ok_or(HaloError::ArithmeticOverflow)?;
+        report.period_yield_fees = report.period_yield_fees
+            .checked_add(bucket.yield_fees)
+            .ok_or(HaloError::ArithmeticOverflow)?;
+        report.period_management_fees = report.period_management_fees
+            .checked_add(bucket.management_fees)
+            .ok_or(HaloError::ArithmeticOverflow)?;
+        report.total_distributions = report.total_distributions
+            .checked_add(bucket.total_distributions)
+            .ok_or(HaloError::ArithmeticOverflow)?;
+        report.total_yield = report.total_yield
+            .checked_add(bucket.total_yield)
+            .ok_or(HaloError::ArithmeticOverflow)?;
+        // Circles and stake persist across epochs, so take the peak rather than a sum
+        report.active_circles = report.active_circles.max(bucket.active_circles);
+        report.total_managed_stake = report.total_managed_stake.max(bucket.managed_stake);
+    }
+    
     report.bump = ctx.bumps.revenue_report;
     
-    msg!("Revenue report created for period {} to {}", period_start, period_end);
+    msg!("Revenue report created for period {} to {}: {} in fees", 
+         period_start, period_end, report.total_period_fees);
     Ok(())
 }
 
-/// Internal helper function to collect distribution fees
+/// Fee-bearing activity to record in the treasury's epoch totals
+pub(crate) enum FeeActivity {
+    Distribution { amount: u64, fee: u64 },
+    Yield { amount: u64, fee: u64 },
+    Management { stake: u64, fee: u64 },
+}
+
+/// Record fee-bearing activity in the current epoch bucket and, when the
+/// activity belongs to a circle, in that circle's fee ledger
+pub(crate) fn record_fee_activity(
+    treasury: &mut Treasury,
+    ledger: Option<&mut CircleFeeLedger>,
+    activity: FeeActivity,
+    now: i64,
+) -> Result<()> {
+    let epoch = Treasury::epoch_at(now);
+    let bucket = treasury.revenue_epoch_mut(epoch)?;
+    
+    match activity {
+        FeeActivity::Distribution { amount, fee } => {
+            bucket.distribution_fees = bucket.distribution_fees.checked_add(fee).ok_or(HaloError::ArithmeticOverflow)?;
+            bucket.total_distributions = bucket.total_distributions.checked_add(amount).ok_or(HaloError::ArithmeticOverflow)?;
+        }
+        FeeActivity::Yield { amount, fee } => {
+            bucket.yield_fees = bucket.yield_fees.checked_add(fee).ok_or(HaloError::ArithmeticOverflow)?;
+            bucket.total_yield = bucket.total_yield.checked_add(amount).ok_or(HaloError::ArithmeticOverflow)?;
+        }
+        FeeActivity::Management { fee, .. } => {
+            bucket.management_fees = bucket.management_fees.checked_add(fee).ok_or(HaloError::ArithmeticOverflow)?;
+        }
+    }
+    
+    let Some(ledger) = ledger else {
+        return Ok(());
+    };
+    
+    if ledger.last_active_epoch != epoch {
+        bucket.active_circles = bucket.active_circles.checked_add(1).ok_or(HaloError::ArithmeticOverflow)?;
+        ledger.last_active_epoch = epoch;
+    }
+    
+    match activity {
+        FeeActivity::Distribution { amount, fee } => {
+            ledger.distribution_fees = ledger.distribution_fees.checked_add(fee).ok_or(HaloError::ArithmeticOverflow)?;
+            ledger.total_distributed = ledger.total_distributed.checked_add(amount).ok_or(HaloError::ArithmeticOverflow)?;
+        }
+        FeeActivity::Yield { amount, fee } => {
+            ledger.yield_fees = ledger.yield_fees.checked_add(fee).ok_or(HaloError::ArithmeticOverflow)?;
+            ledger.total_yield = ledger.total_yield.checked_add(amount).ok_or(HaloError::ArithmeticOverflow)?;
+        }
+        FeeActivity::Management { stake, fee } => {
+            ledger.management_fees = ledger.management_fees.checked_add(fee).ok_or(HaloError::ArithmeticOverflow)?;
+            // Replace this circle's earlier assessment in the same epoch instead of adding to it
+            let counted = if ledger.last_management_epoch == epoch { ledger.managed_stake } else { 0 };
+            bucket.managed_stake = bucket.managed_stake
+                .checked_sub(counted)
+                .and_then(|total| total.checked_add(stake))
+                .ok_or(HaloError::ArithmeticOverflow)?;
+            ledger.managed_stake = stake;
+            ledger.last_management_epoch = epoch;
+        }
+    }
+    
+    Ok(())
+}
+
+/// Internal helper function to transfer an already calculated distribution
+/// fee to the treasury
 pub(crate) fn collect_distribution_fee<'info>(
-    distribution_amount: u64,
-    params: &RevenueParams,
+    fee_amount: u64,
     treasury: &mut Treasury,
     from_account: &AccountInfo<'info>,
     to_account: &AccountInfo<'info>,
@@ -214,8 +443,6 @@ pub(crate) fn collect_distribution_fee<'info>(
     token_program: &AccountInfo<'info>,
     authority_seeds: Option<&[&[&[u8]]]>,
 ) -> Result<u64> {
-    let fee_amount = params.calculate_distribution_fee(distribution_amount)?;
-    
     if fee_amount > 0 {
         let cpi_accounts = Transfer {
             from: from_account.clone(),
@@ -302,6 +529,36 @@ pub struct InitializeTreasury<'info> {
     pub system_program: Program<'info, System>,
 }
 
+#[derive(Accounts)]
+pub struct MigrateTreasury<'info> {
+    #[account(
+        mut,
+        seeds = [b"treasury"],
+        bump = treasury.bump,
+        realloc = Treasury::SPACE,
+        realloc::payer = payer,
+        realloc::zero = false
+    )]
+    pub treasury: Account<'info, Treasury>,
+
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct MigrateCircleStake<'info> {
+    #[account(
+        seeds = [b"treasury"],
+        bump = treasury.bump
+    )]
+    pub treasury: Account<'info, Treasury>,
+
+    #[account(mut)]
+    pub circle: Account<'info, Circle>,
+}
+
 #[derive(Accounts)]
 pub struct InitializeRevenueParams<'info> {
     #[account(
@@ -319,19 +576,6 @@ pub struct InitializeRevenueParams<'info> {
     pub system_program: Program<'info, System>,
 }
 
-#[derive(Accounts)]
-pub struct UpdateRevenueParams<'info> {
-    #[account(
-        mut,
-        seeds = [b"revenue_params"],
-        bump = revenue_params.bump,
-        constraint = revenue_params.authority == authority.key() @ HaloError::UnauthorizedRevenueOperation
-    )]
-    pub revenue_params: Account<'info, RevenueParams>,
-    
-    pub authority: Signer<'info>,
-}
-
 #[derive(Accounts)]
 pub struct CollectManagementFees<'info> {
     #[account(
@@ -347,22 +591,59 @@ pub struct CollectManagementFees<'info> {
     )]
     pub revenue_params: Account<'info, RevenueParams>,
     
-    /// Circle account to get escrow reference
-    pub circle: Account<'info, crate::state::Circle>,
+    /// Circle whose stake is charged and whose fee ledger is updated
+    #[account(mut)]
+    pub circle: Account<'info, Circle>,
     
     /// Escrow account that holds member stakes
     #[account(
+        mut,
         seeds = [b"escrow", circle.key().as_ref()],
         bump = escrow.bump
     )]
-    pub escrow: Account<'info, crate::state::CircleEscrow>,
+    pub escrow: Account<'info, CircleEscrow>,
     
     /// Token account holding the escrowed funds
-    #[account(mut)]
+    #[account(
+        mut,
+        constraint = escrow_token_account.owner == escrow.key() @ HaloError::InvalidEscrowTokenAccount,
+        constraint = escrow_token_account.mint == treasury_token_account.mint @ HaloError::InvalidEscrowTokenAccount
+    )]
     pub escrow_token_account: Account<'info, TokenAccount>,
     
     /// Treasury token account to receive fees
-    #[account(mut)]
+    #[account(
+        mut,
+        constraint = treasury_token_account.owner == treasury.key() @ HaloError::InvalidTreasuryTokenAccount
+    )]
+    pub treasury_token_account: Account<'info, TokenAccount>,
+    
+    pub token_program: Program<'info, Token>,
+    
+    /// Authority that can collect management fees (should be automated/governance)
+    pub authority: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct CollectManagementFeesBatch<'info> {
+    #[account(
+        mut,
+        seeds = [b"treasury"],
+        bump = treasury.bump
+    )]
+    pub treasury: Account<'info, Treasury>,
+    
+    #[account(
+        seeds = [b"revenue_params"],
+        bump = revenue_params.bump
+    )]
+    pub revenue_params: Account<'info, RevenueParams>,
+    
+    /// Treasury token account to receive fees
+    #[account(
+        mut,
+        constraint = treasury_token_account.owner == treasury.key() @ HaloError::InvalidTreasuryTokenAccount
+    )]
     pub treasury_token_account: Account<'info, TokenAccount>,
     
     pub token_program: Program<'info, Token>,
@@ -422,8 +703,27 @@ pub struct DistributeYield<'info> {
     pub recipient_token_account: Account<'info, TokenAccount>,
     
     /// Treasury token account to receive fees
-    #[account(mut)]
+    #[account(
+        mut,
+        constraint = treasury_token_account.owner == treasury.key() @ HaloError::InvalidTreasuryTokenAccount
+    )]
     pub treasury_token_account: Account<'info, TokenAccount>,
     
+    /// Circle the yield was earned for; the recipient must be its escrow
+    #[account(mut)]
+    pub circle: Option<Account<'info, Circle>>,
+    
     pub token_program: Program<'info, Token>,
 }
+
+#[event]
+pub struct ManagementFeeCollected {
+    pub circle: Pubkey,
+    /// Stake the fee accrued on
+    pub stake: u64,
+    /// Seconds of accrual charged
+    pub elapsed: i64,
+    pub fee: u64,
+    /// Part of the base fee waived for the circle's trust tier
+    pub fee_discount: u64,
+}
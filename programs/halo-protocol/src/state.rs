@@ -1,5 +1,8 @@
 use anchor_lang::prelude::*;
 
+use crate::errors::HaloError;
+use crate::math::{self, Rounding};
+
 #[account]
 pub struct Circle {
     /// The creator of the circle
@@ -53,6 +56,24 @@ pub struct Circle {
     pub total_yield_earned: u64,
     /// Next payout recipient
     pub next_payout_recipient: Option<Pubkey>,
+    /// Number of governance proposals created; the next proposal's id
+    pub proposal_count: u64,
+    /// How governance votes in this circle are weighted
+    pub governance: GovernanceConfig,
+    /// Days after a contribution is due before it counts as missed
+    pub grace_period_days: u8,
+    /// Set by an emergency proposal; blocks contributions, payouts and yield deposits
+    pub emergency_paused: bool,
+    /// Snapshot taken when a wound-down circle is settled
+    pub wind_down: Option<WindDownSnapshot>,
+    /// Fees this circle has paid the protocol
+    pub fee_ledger: CircleFeeLedger,
+    /// Member stake held in escrow; the base for management fees
+    pub total_stake: u64,
+    /// Start of the current emergency pause, or of the last one once lifted
+    pub paused_at: i64,
+    /// Total length of lifted emergency pauses, in seconds
+    pub paused_seconds: i64,
 }
 
 #[derive(AnchorSerialize, AnchorDeserialize, Clone, PartialEq, Eq)]
@@ -61,6 +82,35 @@ pub enum CircleStatus {
     Active,     // Running
     Completed,  // Finished
     Terminated, // Has defaults
+    WindingDown, // Emergency wind-down, members withdraw their share
+}
+
+/// Funds and positions frozen when an emergency wind-down is settled
+#[derive(AnchorSerialize, AnchorDeserialize, Clone)]
+pub struct WindDownSnapshot {
+    /// Escrow balance to be shared among members
+    pub pool: u64,
+    /// Sum of all members' net positions
+    pub total_position: u64,
+    /// Amount already withdrawn by members
+    pub claimed: u64,
+    /// Timestamp of settlement
+    pub settled_at: i64,
+}
+
+impl WindDownSnapshot {
+    pub const SPACE: usize = 8 + 8 + 8 + 8;
+
+    /// Pro-rata share of the pool for a member with the given net position
+    pub fn share_of(&self, position: u64) -> Option<u64> {
+        if self.total_position == 0 {
+            return Some(0);
+        }
+        (self.pool as u128)
+            .checked_mul(position as u128)?
+            .checked_div(self.total_position as u128)
+            .and_then(|share| u64::try_from(share).ok())
+    }
 }
 
 #[derive(AnchorSerialize, AnchorDeserialize, Clone, PartialEq, Eq)]
@@ -106,9 +156,12 @@ pub struct ContributionRecord {
     pub timestamp: i64,
     pub on_time: bool,
     pub days_late: u8,
+    /// Whether the late penalty for a missed month has been charged
+    pub penalty_charged: bool,
 }
 
 #[account]
+#[derive(Default)]
 pub struct Member {
     /// The member's public key
     pub authority: Pubkey,
@@ -143,6 +196,14 @@ pub struct Member {
     pub insurance_staked: u64,
     /// Detailed contribution records
     pub contribution_records: Vec<ContributionRecord>,
+    /// Portion of stake locked as a guarantee for vouched newcomers
+    pub vouched_stake_locked: u64,
+    /// Guarantor who vouched for this member, if any
+    pub vouched_by: Option<Pubkey>,
+    /// Total pot amounts paid out to this member
+    pub payouts_received: u64,
+    /// Rounds left on the member's approval for automated collection
+    pub auto_contribution_rounds: u8,
 }
 
 #[derive(AnchorSerialize, AnchorDeserialize, Clone, PartialEq, Eq)]
@@ -150,6 +211,48 @@ pub enum MemberStatus {
     Active,
     Defaulted,
     Exited,
+    Removed, // Removed by governance, unlocked stake refunded at removal
+}
+
+impl Member {
+    /// Record a missed contribution for `month`. Returns false if that month
+    /// was already recorded as missed, so repeated checks count it once.
+    pub fn record_missed(&mut self, month: u8, now: i64, days_late: u8) -> bool {
+        if self.contribution_records.iter().any(|r| r.month == month && r.amount == 0) {
+            return false;
+        }
+        self.contribution_records.push(ContributionRecord {
+            month,
+            amount: 0,
+            timestamp: now,
+            on_time: false,
+            days_late,
+            penalty_charged: false,
+        });
+        self.contributions_missed = self.contributions_missed.saturating_add(1);
+        true
+    }
+
+    /// Mark the late penalty for a missed `month` as charged. Returns false
+    /// if the month is not recorded as missed or was already charged, so a
+    /// miss recorded during collection is still charged exactly once.
+    pub fn mark_penalty_charged(&mut self, month: u8) -> bool {
+        match self.contribution_records.iter_mut().find(|r| r.month == month && r.amount == 0) {
+            Some(record) if !record.penalty_charged => {
+                record.penalty_charged = true;
+                true
+            },
+            _ => false,
+        }
+    }
+
+    /// Contributions paid minus payouts received plus stake, floored at zero
+    pub fn net_position(&self) -> u64 {
+        let contributed: u64 = self.contribution_history.iter().fold(0u64, |acc, c| acc.saturating_add(*c));
+        contributed
+            .saturating_add(self.stake_amount)
+            .saturating_sub(self.payouts_received)
+    }
 }
 
 impl Default for MemberStatus {
@@ -198,7 +301,59 @@ pub struct MemberYieldShare {
 impl Circle {
     pub const MAX_MEMBERS: usize = 20;
     pub const MAX_DURATION: u8 = 24; // months
+    pub const DEFAULT_GRACE_PERIOD_DAYS: u8 = 3;
+    pub const MAX_GRACE_PERIOD_DAYS: u8 = 30;
+    /// Missed contributions after which a member is moved to `Defaulted`
+    pub const MAX_MISSED_CONTRIBUTIONS: u8 = 2;
     
+    /// Pause or resume the circle, tracking how long it has been paused
+    pub fn set_emergency_pause(&mut self, paused: bool, now: i64) {
+        if paused && !self.emergency_paused {
+            self.paused_at = now;
+        } else if !paused && self.emergency_paused {
+            let pause = now.saturating_sub(self.paused_at);
+            self.paused_seconds = self.paused_seconds.saturating_add(pause);
+        }
+        self.emergency_paused = paused;
+    }
+
+    /// Seconds the circle has spent paused up to `now`, including a pause
+    /// still in effect
+    pub fn paused_seconds_at(&self, now: i64) -> i64 {
+        if self.emergency_paused {
+            self.paused_seconds.saturating_add(now.saturating_sub(self.paused_at))
+        } else {
+            self.paused_seconds
+        }
+    }
+
+    /// Circle month at `now`, counting only time the circle was not paused
+    pub fn month_at(&self, now: i64) -> u8 {
+        let active = now
+            .saturating_sub(self.created_at)
+            .saturating_sub(self.paused_seconds_at(now))
+            .max(0);
+        let months = u8::try_from(active / (30 * 24 * 60 * 60)).unwrap_or(u8::MAX);
+        std::cmp::min(months, self.duration_months.saturating_sub(1))
+    }
+
+    /// Member due the pot for `month`: the recipient set by the last payout
+    /// round, or else the next one under the circle's payout method
+    pub fn scheduled_payout_recipient(&self, month: u8) -> Option<Pubkey> {
+        if self.next_payout_recipient.is_some() {
+            return self.next_payout_recipient;
+        }
+        match self.payout_method {
+            // Without an explicit queue members are paid in join order
+            PayoutMethod::FixedRotation => self.payout_queue
+                .get(month as usize)
+                .or_else(|| self.members.get(month as usize))
+                .copied(),
+            PayoutMethod::Auction => self.payout_queue.first().copied(),
+            PayoutMethod::Random => None,
+        }
+    }
+
     pub fn space() -> usize {
         8 + // discriminator
         32 + // creator
@@ -227,6 +382,15 @@ impl Circle {
         32 + // escrow_account
         8 + // total_yield_earned
         1 + 32 + // next_payout_recipient (Option<Pubkey>)
+        8 + // proposal_count
+        GovernanceConfig::SPACE + // governance
+        1 + // grace_period_days
+        1 + // emergency_paused
+        1 + WindDownSnapshot::SPACE + // wind_down (Option<WindDownSnapshot>)
+        CircleFeeLedger::SPACE + // fee_ledger
+        8 + // total_stake
+        8 + // paused_at
+        8 + // paused_seconds
         200 // extra space for future fields
     }
 }
@@ -250,7 +414,11 @@ impl Member {
         1 + // payout_claimed
         1 + // payout_position
         8 + // insurance_staked
-        4 + (1 + 8 + 8 + 1 + 1) * Circle::MAX_DURATION as usize + // contribution_records vec
+        4 + (1 + 8 + 8 + 1 + 1 + 1) * Circle::MAX_DURATION as usize + // contribution_records vec
+        8 + // vouched_stake_locked
+        1 + 32 + // vouched_by (Option<Pubkey>)
+        8 + // payouts_received
+        1 + // auto_contribution_rounds
         100 // extra space
     }
 }
@@ -291,18 +459,8 @@ impl CircleEscrow {
             return 0;
         }
 
-        // Annualize the return
-        let seconds_per_year = 31_536_000_i64; // 365 days
-        let annualized_yield = (total_yield as u128)
-            .saturating_mul(seconds_per_year as u128)
-            .checked_div(time_elapsed as u128)
-            .unwrap_or(0) as u64;
-
-        // Return APY in basis points (1% = 100 bps)
-        annualized_yield
-            .saturating_mul(10_000)
-            .checked_div(self.total_amount)
-            .unwrap_or(0)
+        // Annualized return in basis points (1% = 100 bps)
+        math::annualized_bps(total_yield, self.total_amount, time_elapsed as u64)
     }
 
     /// Get Reflect-specific APY (returns basis points)
@@ -317,16 +475,8 @@ impl CircleEscrow {
             return 0;
         }
 
-        let seconds_per_year = 31_536_000_i64;
-        let annualized_yield = (self.reflect_yield_earned as u128)
-            .saturating_mul(seconds_per_year as u128)
-            .checked_div(time_elapsed as u128)
-            .unwrap_or(0) as u64;
-
-        annualized_yield
-            .saturating_mul(10_000)
-            .checked_div(self.total_amount)
-            .unwrap_or(0)
+        // Annualized return in basis points (1% = 100 bps)
+        math::annualized_bps(self.reflect_yield_earned, self.total_amount, time_elapsed as u64)
     }
 
     /// Get Solend-specific APY (returns basis points)
@@ -341,16 +491,8 @@ impl CircleEscrow {
             return 0;
         }
 
-        let seconds_per_year = 31_536_000_i64;
-        let annualized_yield = (self.solend_yield_earned as u128)
-            .saturating_mul(seconds_per_year as u128)
-            .checked_div(time_elapsed as u128)
-            .unwrap_or(0) as u64;
-
-        annualized_yield
-            .saturating_mul(10_000)
-            .checked_div(self.total_amount)
-            .unwrap_or(0)
+        // Annualized return in basis points (1% = 100 bps)
+        math::annualized_bps(self.solend_yield_earned, self.total_amount, time_elapsed as u64)
     }
 }
 
@@ -384,6 +526,54 @@ pub struct TrustScore {
     pub last_updated: i64,
     /// Bump seed for PDA
     pub bump: u8,
+    /// Number of vouched newcomers who defaulted
+    pub guarantor_defaults: u16,
+    /// Number of circles this user defaulted in
+    pub defaults: u16,
+    /// Number of times this user's insurance stake was slashed
+    pub slashings: u16,
+    /// Number of circles this user left before completion
+    pub early_exits: u16,
+    /// Number of circles this user left with penalties still owed
+    pub unpaid_penalties: u16,
+    /// Sequence number the next recorded penalty will get
+    pub penalty_sequence: u32,
+    /// Most recent penalties, oldest first; each can be disputed on its own
+    pub penalty_records: Vec<TrustPenaltyRecord>,
+    /// Tier thresholds last synced from the protocol realm. Zeroed on scores
+    /// created before thresholds were stored, which use the defaults.
+    pub tier_thresholds: TrustTierThresholds,
+}
+
+/// A single negative outcome recorded against a `TrustScore`
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, PartialEq, Eq, Debug)]
+pub struct TrustPenaltyRecord {
+    /// Position of this penalty among all penalties recorded for the user
+    pub sequence: u32,
+    /// Circle the penalty was recorded in
+    pub circle: Pubkey,
+    pub kind: TrustPenaltyKind,
+    pub recorded_at: i64,
+    /// Set once an accepted dispute has reversed the penalty
+    pub reversed: bool,
+}
+
+impl TrustPenaltyRecord {
+    pub const SPACE: usize = 4 + // sequence
+        32 + // circle
+        1 + // kind
+        8 + // recorded_at
+        1; // reversed
+}
+
+/// Negative outcomes that are recorded against a `TrustScore`
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, Debug)]
+pub enum TrustPenaltyKind {
+    Default,
+    Slashing,
+    EarlyExit,
+    UnpaidPenalty,
+    GuarantorDefault,
 }
 
 #[derive(AnchorSerialize, AnchorDeserialize, Clone, PartialEq, Eq, Debug)]
@@ -400,6 +590,69 @@ impl Default for TrustTier {
     }
 }
 
+/// Minimum scores for each tier above Newcomer
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, PartialEq, Eq, Debug)]
+pub struct TrustTierThresholds {
+    pub silver: u16,
+    pub gold: u16,
+    pub platinum: u16,
+}
+
+impl Default for TrustTierThresholds {
+    fn default() -> Self {
+        TrustTierThresholds {
+            silver: 250,
+            gold: 500,
+            platinum: 750,
+        }
+    }
+}
+
+impl TrustTierThresholds {
+    pub const SPACE: usize = 2 + 2 + 2;
+
+    /// Thresholds must be strictly increasing and reachable
+    pub fn is_valid(&self) -> bool {
+        0 < self.silver &&
+        self.silver < self.gold &&
+        self.gold < self.platinum &&
+        self.platinum <= TrustScore::MAX_SCORE
+    }
+}
+
+impl TrustTier {
+    /// Stable numeric encoding (matches `Circle::min_trust_tier`)
+    pub fn as_u8(&self) -> u8 {
+        match self {
+            TrustTier::Newcomer => 0,
+            TrustTier::Silver => 1,
+            TrustTier::Gold => 2,
+            TrustTier::Platinum => 3,
+        }
+    }
+
+    /// Inverse of `as_u8`
+    pub fn from_u8(value: u8) -> Option<TrustTier> {
+        match value {
+            0 => Some(TrustTier::Newcomer),
+            1 => Some(TrustTier::Silver),
+            2 => Some(TrustTier::Gold),
+            3 => Some(TrustTier::Platinum),
+            _ => None,
+        }
+    }
+
+    /// Minimum stake as a percentage of the contribution amount
+    pub fn minimum_stake_multiplier(&self) -> u64 {
+        match self {
+            TrustTier::Newcomer => 200, // 2x base stake
+            TrustTier::Silver => 150,   // 1.5x base stake
+            TrustTier::Gold => 100,     // 1x base stake
+            TrustTier::Platinum => 75,  // 0.75x base stake
+        }
+    }
+}
+
 #[derive(AnchorSerialize, AnchorDeserialize, Clone)]
 pub struct SocialProof {
     /// Type of social proof (Twitter, Discord, etc.)
@@ -414,6 +667,9 @@ pub struct SocialProof {
 
 impl TrustScore {
     pub const MAX_SOCIAL_PROOFS: usize = 5;
+    /// Penalty records kept for disputes before the oldest is dropped
+    pub const MAX_PENALTY_RECORDS: usize = 8;
+    pub const MAX_SCORE: u16 = 1000;
     
     pub fn space() -> usize {
         8 + // discriminator
@@ -431,27 +687,133 @@ impl TrustScore {
         4 + (4 + 32 + 4 + 32 + 1 + 8) * Self::MAX_SOCIAL_PROOFS + // social_proofs vec
         8 + // last_updated
         1 + // bump
+        2 + // guarantor_defaults
+        2 + // defaults
+        2 + // slashings
+        2 + // early_exits
+        2 + // unpaid_penalties
+        4 + // penalty_sequence
+        4 + TrustPenaltyRecord::SPACE * Self::MAX_PENALTY_RECORDS + // penalty_records
+        TrustTierThresholds::SPACE + // tier_thresholds
         100 // extra space
     }
 
+    /// Points deducted for each vouched newcomer that defaulted
+    pub const GUARANTOR_DEFAULT_PENALTY: u16 = 50;
+    /// Points deducted for each circle defaulted in
+    pub const DEFAULT_PENALTY: u16 = 150;
+    /// Points deducted for each insurance slashing
+    pub const SLASHING_PENALTY: u16 = 100;
+    /// Points deducted for each early exit
+    pub const EARLY_EXIT_PENALTY: u16 = 40;
+    /// Points deducted for each exit with penalties still owed
+    pub const UNPAID_PENALTY_PENALTY: u16 = 75;
+
+    /// Total points deducted for recorded negative outcomes
+    pub fn penalty_points(&self) -> u16 {
+        self.defaults.saturating_mul(Self::DEFAULT_PENALTY)
+            .saturating_add(self.slashings.saturating_mul(Self::SLASHING_PENALTY))
+            .saturating_add(self.early_exits.saturating_mul(Self::EARLY_EXIT_PENALTY))
+            .saturating_add(self.unpaid_penalties.saturating_mul(Self::UNPAID_PENALTY_PENALTY))
+            .saturating_add(self.guarantor_defaults.saturating_mul(Self::GUARANTOR_DEFAULT_PENALTY))
+    }
+
+    /// Record a negative outcome and recalculate the score
+    pub fn record_penalty(&mut self, kind: TrustPenaltyKind, circle: Pubkey, now: i64) -> u32 {
+        let counter = self.penalty_counter(kind);
+        *counter = counter.saturating_add(1);
+
+        let sequence = self.penalty_sequence;
+        self.penalty_sequence = sequence.saturating_add(1);
+        if self.penalty_records.len() >= Self::MAX_PENALTY_RECORDS {
+            self.penalty_records.remove(0);
+        }
+        self.penalty_records.push(TrustPenaltyRecord {
+            sequence,
+            circle,
+            kind,
+            recorded_at: now,
+            reversed: false,
+        });
+
+        self.calculate_score();
+        self.last_updated = now;
+        sequence
+    }
+
+    /// Recorded penalty with this sequence number, if still retained
+    pub fn penalty_record(&self, sequence: u32) -> Option<&TrustPenaltyRecord> {
+        self.penalty_records.iter().find(|record| record.sequence == sequence)
+    }
+
+    /// Undo a recorded penalty after an accepted dispute. Returns false if the
+    /// record is no longer retained or was already reversed.
+    pub fn reverse_penalty(&mut self, sequence: u32, now: i64) -> bool {
+        let Some(record) = self.penalty_records
+            .iter_mut()
+            .find(|record| record.sequence == sequence && !record.reversed)
+        else {
+            return false;
+        };
+        record.reversed = true;
+        let kind = record.kind;
+
+        let counter = self.penalty_counter(kind);
+        *counter = counter.saturating_sub(1);
+        self.calculate_score();
+        self.last_updated = now;
+        true
+    }
+
+    /// Number of times a negative outcome of this kind was recorded
+    pub fn penalty_count(&self, kind: TrustPenaltyKind) -> u16 {
+        match kind {
+            TrustPenaltyKind::Default => self.defaults,
+            TrustPenaltyKind::Slashing => self.slashings,
+            TrustPenaltyKind::EarlyExit => self.early_exits,
+            TrustPenaltyKind::UnpaidPenalty => self.unpaid_penalties,
+            TrustPenaltyKind::GuarantorDefault => self.guarantor_defaults,
+        }
+    }
+
+    fn penalty_counter(&mut self, kind: TrustPenaltyKind) -> &mut u16 {
+        match kind {
+            TrustPenaltyKind::Default => &mut self.defaults,
+            TrustPenaltyKind::Slashing => &mut self.slashings,
+            TrustPenaltyKind::EarlyExit => &mut self.early_exits,
+            TrustPenaltyKind::UnpaidPenalty => &mut self.unpaid_penalties,
+            TrustPenaltyKind::GuarantorDefault => &mut self.guarantor_defaults,
+        }
+    }
+
     /// Calculate minimum stake requirement based on trust tier
     pub fn get_minimum_stake_multiplier(&self) -> u64 {
-        match self.tier {
-            TrustTier::Newcomer => 200, // 2x base stake
-            TrustTier::Silver => 150,   // 1.5x base stake
-            TrustTier::Gold => 100,     // 1x base stake
-            TrustTier::Platinum => 75,  // 0.75x base stake
-        }
+        self.tier.minimum_stake_multiplier()
     }
 
-    /// Update trust tier based on current score
+    /// Adopt the tier thresholds set by protocol governance
+    pub fn sync_tier_thresholds(&mut self, thresholds: &TrustTierThresholds) {
+        self.tier_thresholds = thresholds.clone();
+        self.update_tier();
+    }
+
+    /// Update trust tier based on current score and the synced thresholds
     pub fn update_tier(&mut self) {
-        self.tier = match self.score {
-            0..=249 => TrustTier::Newcomer,
-            250..=499 => TrustTier::Silver,
-            500..=749 => TrustTier::Gold,
-            750..=1000 => TrustTier::Platinum,
-            _ => TrustTier::Newcomer, // Default fallback
+        let thresholds = if self.tier_thresholds.is_valid() {
+            self.tier_thresholds.clone()
+        } else {
+            TrustTierThresholds::default()
+        };
+        self.tier = if self.score > TrustScore::MAX_SCORE {
+            TrustTier::Newcomer // Default fallback
+        } else if self.score >= thresholds.platinum {
+            TrustTier::Platinum
+        } else if self.score >= thresholds.gold {
+            TrustTier::Gold
+        } else if self.score >= thresholds.silver {
+            TrustTier::Silver
+        } else {
+            TrustTier::Newcomer
         };
     }
 
@@ -494,16 +856,87 @@ impl TrustScore {
         let verified_proofs = self.social_proofs.iter().filter(|p| p.verified).count();
         self.social_proof_score = std::cmp::min((verified_proofs as u16).saturating_mul(20), 100);
 
-        // Calculate total score
+        // Calculate total score, minus penalties for recorded negative outcomes
         self.score = self.payment_history_score
             .saturating_add(self.completion_score)
             .saturating_add(self.defi_activity_score)
-            .saturating_add(self.social_proof_score);
+            .saturating_add(self.social_proof_score)
+            .saturating_sub(self.penalty_points());
 
         self.update_tier();
     }
 }
 
+/// Soulbound trust credential for partner protocols.
+/// Owned by the program and never transferable. The stored tier and score are
+/// only a snapshot taken at the last `update_trust_score` that passed the
+/// credential: penalties, dispute reversals and threshold syncs do not touch
+/// them, so they can lag the `TrustScore`. Only `get_trust_tier` is
+/// authoritative.
+/// The layout is fixed so integrators can read it
+/// without Anchor (offsets after the 8-byte discriminator):
+/// schema_version @0, authority @1, tier @33, score @34, issued_at @36,
+/// updated_at @44, bump @52
+#[account]
+pub struct TrustCredential {
+    /// Layout version of this credential
+    pub schema_version: u8,
+    /// The user this credential was issued to
+    pub authority: Pubkey,
+    /// Trust tier (0 = Newcomer .. 3 = Platinum) at last sync
+    pub tier: u8,
+    /// Trust score (0-1000) at last sync
+    pub score: u16,
+    /// Timestamp when the credential was issued
+    pub issued_at: i64,
+    /// Timestamp of the last sync with `TrustScore`
+    pub updated_at: i64,
+    /// Bump seed for PDA
+    pub bump: u8,
+}
+
+impl TrustCredential {
+    pub const SCHEMA_VERSION: u8 = 1;
+
+    pub const SPACE: usize = 8 + // discriminator
+        1 + // schema_version
+        32 + // authority
+        1 + // tier
+        2 + // score
+        8 + // issued_at
+        8 + // updated_at
+        1; // bump
+
+    /// Copy tier and score from the owner's trust score
+    pub fn sync(&mut self, trust_score: &TrustScore, current_time: i64) {
+        self.tier = trust_score.tier.as_u8();
+        self.score = trust_score.score;
+        self.updated_at = current_time;
+    }
+
+    /// Return-data view served by `get_trust_tier`, read from the owner's
+    /// current trust score
+    pub fn view(&self, trust_score: &TrustScore) -> TrustTierView {
+        TrustTierView {
+            schema_version: self.schema_version,
+            authority: self.authority,
+            tier: trust_score.tier.as_u8(),
+            score: trust_score.score,
+            updated_at: trust_score.last_updated,
+        }
+    }
+}
+
+/// Versioned return data of the `get_trust_tier` view instruction
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, PartialEq, Eq, Debug)]
+pub struct TrustTierView {
+    pub schema_version: u8,
+    pub authority: Pubkey,
+    pub tier: u8,
+    pub score: u16,
+    pub updated_at: i64,
+}
+
 // Switchboard Oracle Automation Structures
 
 /// Global automation configuration and state
@@ -517,12 +950,16 @@ pub struct AutomationState {
     pub enabled: bool,
     /// Number of active automation jobs
     pub active_jobs: u32,
-    /// Minimum interval between automation checks (in seconds)
+    /// Minimum interval between a circle's oracle callbacks (in seconds)
     pub min_interval: i64,
-    /// Last global automation check timestamp
+    /// Deprecated: callback timing is now tracked per circle
     pub last_check: i64,
     /// Bump seed for PDA
     pub bump: u8,
+    /// Program that must own the queue and function accounts
+    pub oracle_program: Pubkey,
+    /// Enclave or oracle key that signs callback payloads
+    pub oracle_signer: Pubkey,
 }
 
 impl AutomationState {
@@ -534,6 +971,8 @@ impl AutomationState {
         8 + // min_interval
         8 + // last_check
         1 + // bump
+        32 + // oracle_program
+        32 + // oracle_signer
         50; // padding
 }
 
@@ -566,6 +1005,28 @@ pub struct CircleAutomation {
     pub circle_created_at: i64,
     /// Bump seed for PDA
     pub bump: u8,
+    /// Last scheduled slot a keeper was rewarded for, per task
+    pub last_rewarded_slots: [i64; 3],
+    /// Process every missed round in order instead of only the latest
+    pub catch_up_enabled: bool,
+    /// Start of the circle's current pause, or 0 while it is running
+    pub paused_at: i64,
+    /// Total time the schedules have been pushed back by pauses
+    pub paused_seconds: i64,
+    /// Issue time of the circle's last accepted oracle callback, to reject replays
+    pub last_callback_at: i64,
+    /// When the circle's last oracle callback ran, for `min_interval`
+    pub last_callback_run: i64,
+}
+
+/// A scheduled round picked for processing
+pub struct DueRound {
+    /// Index of the round in the schedule, which is the circle month
+    pub month: u8,
+    /// Time the round was scheduled for
+    pub scheduled_at: i64,
+    /// Earlier due rounds passed over to reach this one
+    pub skipped: u8,
 }
 
 impl CircleAutomation {
@@ -585,6 +1046,12 @@ impl CircleAutomation {
         8 + // last_penalty_check
         8 + // circle_created_at
         1 + // bump
+        8 * 3 + // last_rewarded_slots
+        1 + // catch_up_enabled
+        8 + // paused_at
+        8 + // paused_seconds
+        8 + // last_callback_at
+        8 + // last_callback_run
         100; // padding
     
     /// Generate contribution schedule for a circle
@@ -628,6 +1095,108 @@ impl CircleAutomation {
         schedule
     }
     
+    /// Whether any task is automated, which is what counts as an active job
+    pub fn is_active(&self) -> bool {
+        self.auto_collect_enabled || self.auto_distribute_enabled || self.auto_penalty_enabled
+    }
+
+    /// Next round of `task` to process at `current_time`. Due rounds that
+    /// have not been processed are taken oldest first in catch-up mode;
+    /// otherwise the latest is taken and the older ones count as skipped.
+    pub fn next_due_round(&self, task: &AutomationEventType, current_time: i64) -> Option<DueRound> {
+        let (schedule, last_check) = match task {
+            AutomationEventType::ContributionCollection => (&self.contribution_schedule, self.last_contribution_check),
+            AutomationEventType::PayoutDistribution => (&self.distribution_schedule, self.last_distribution_check),
+            AutomationEventType::PenaltyEnforcement => (&self.penalty_schedule, self.last_penalty_check),
+            AutomationEventType::ScheduleUpdate => return None,
+        };
+        let first = schedule.iter().position(|&scheduled_time| scheduled_time > last_check)?;
+        let due = schedule[first..]
+            .iter()
+            .take_while(|&&scheduled_time| scheduled_time <= current_time)
+            .count();
+        if due == 0 {
+            return None;
+        }
+
+        let index = if self.catch_up_enabled { first } else { first + due - 1 };
+        Some(DueRound {
+            month: index as u8,
+            scheduled_at: schedule[index],
+            skipped: (index - first) as u8,
+        })
+    }
+
+    /// Bring the schedules in line with the circle's current timing. Once a
+    /// pause is lifted every round still ahead of its start moves back by
+    /// the pause time recorded on the circle since the last sync. Rounds are
+    /// then added or dropped to match the circle's duration. Returns whether
+    /// anything changed.
+    pub fn sync_with_circle(&mut self, circle: &Circle) -> bool {
+        let mut changed = false;
+
+        if circle.paused_seconds > self.paused_seconds {
+            let paused_at = circle.paused_at;
+            let pause = circle.paused_seconds - self.paused_seconds;
+            for schedule in [
+                &mut self.contribution_schedule,
+                &mut self.distribution_schedule,
+                &mut self.penalty_schedule,
+            ] {
+                for scheduled_time in schedule.iter_mut().filter(|t| **t > paused_at) {
+                    *scheduled_time = scheduled_time.saturating_add(pause);
+                }
+            }
+            self.paused_seconds = circle.paused_seconds;
+            changed = true;
+        }
+        let paused_at = if circle.emergency_paused { circle.paused_at } else { 0 };
+        if self.paused_at != paused_at {
+            self.paused_at = paused_at;
+            changed = true;
+        }
+
+        let rounds = std::cmp::min(circle.duration_months as usize, Self::MAX_SCHEDULE_ITEMS) as u8;
+        if self.contribution_schedule.len() != rounds as usize {
+            let start = self.circle_created_at.saturating_add(self.paused_seconds);
+            Self::resize_schedule(&mut self.contribution_schedule, rounds, start, Self::generate_contribution_schedule);
+            Self::resize_schedule(&mut self.distribution_schedule, rounds, start, Self::generate_distribution_schedule);
+            Self::resize_schedule(&mut self.penalty_schedule, rounds, start, Self::generate_penalty_schedule);
+            changed = true;
+        }
+
+        changed
+    }
+
+    /// Truncate or extend a schedule to `rounds`, keeping monthly spacing
+    fn resize_schedule(schedule: &mut Vec<i64>, rounds: u8, start: i64, generate: fn(i64, u8) -> Vec<i64>) {
+        schedule.truncate(rounds as usize);
+        let Some(&last) = schedule.last() else {
+            *schedule = generate(start, rounds);
+            return;
+        };
+        let month_duration = 30 * 24 * 60 * 60;
+        for month in 1..=(rounds as usize - schedule.len()) {
+            schedule.push(last + month as i64 * month_duration);
+        }
+    }
+
+    /// Record that a keeper was rewarded for `slot`. Returns false if that
+    /// slot, or a later one, was already rewarded for the task.
+    pub fn mark_rewarded(&mut self, task: &AutomationEventType, slot: i64) -> bool {
+        let index = match task {
+            AutomationEventType::ContributionCollection => 0,
+            AutomationEventType::PayoutDistribution => 1,
+            AutomationEventType::PenaltyEnforcement => 2,
+            AutomationEventType::ScheduleUpdate => return false,
+        };
+        if slot <= self.last_rewarded_slots[index] {
+            return false;
+        }
+        self.last_rewarded_slots[index] = slot;
+        true
+    }
+
     /// Check if it's time for contribution collection
     pub fn should_collect_contributions(&self, current_time: i64) -> bool {
         if !self.auto_collect_enabled {
@@ -674,43 +1243,104 @@ impl CircleAutomation {
     }
 }
 
-/// Event log for automation actions
+/// Fixed-size ring buffer of a circle's recent automation results
 #[account]
-pub struct AutomationEvent {
-    /// The circle this event relates to
+pub struct AutomationLog {
+    /// The circle this log belongs to
     pub circle: Pubkey,
-    /// Type of automation event
-    pub event_type: AutomationEventType,
-    /// Timestamp when event occurred
-    pub timestamp: i64,
-    /// Event details/data
-    pub data: Vec<u8>,
-    /// Whether event was successful
-    pub success: bool,
-    /// Error message if failed
-    pub error_message: Option<String>,
+    /// Slot the next record is written to once the log is full
+    pub head: u16,
+    /// Records ever written; the oldest are overwritten past `CAPACITY`
+    pub total_records: u64,
+    /// Stored records, oldest first until the buffer wraps at `head`
+    pub records: Vec<AutomationRecord>,
     /// Bump seed for PDA
     pub bump: u8,
 }
 
-#[derive(AnchorSerialize, AnchorDeserialize, Clone, PartialEq, Eq)]
-pub enum AutomationEventType {
-    ContributionCollection,
-    PayoutDistribution, 
-    PenaltyEnforcement,
-    ScheduleUpdate,
+/// One automation result, kept compact for the ring buffer
+#[derive(AnchorSerialize, AnchorDeserialize, Clone)]
+pub struct AutomationRecord {
+    /// Task that ran
+    pub event_type: AutomationEventType,
+    /// Schedule round (circle month) the task served
+    pub round: u8,
+    /// How the round went
+    pub outcome: AutomationOutcome,
+    /// Tokens moved: collected, paid out or charged as penalties
+    pub amount: u64,
+    /// Time the record was written
+    pub timestamp: i64,
 }
 
-impl AutomationEvent {
+/// Result of an automated round
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, PartialEq, Eq)]
+pub enum AutomationOutcome {
+    /// Every member was handled normally
+    Completed,
+    /// Some members missed their contribution or were penalized
+    Missed,
+    /// The round was passed over to reach a later one
+    Skipped,
+}
+
+impl AutomationLog {
+    pub const CAPACITY: usize = 32;
+
     pub const SPACE: usize = 8 + // discriminator
         32 + // circle
-        1 + // event_type
-        8 + // timestamp
-        4 + 100 + // data vec (max 100 bytes)
-        1 + // success
-        4 + 100 + // error_message (max 100 chars)
+        2 + // head
+        8 + // total_records
+        4 + (1 + 1 + 1 + 8 + 8) * Self::CAPACITY + // records
         1 + // bump
         50; // padding
+
+    /// Append a record, overwriting the oldest once the log is full.
+    /// Returns the record's sequence number.
+    pub fn append(&mut self, record: AutomationRecord) -> u64 {
+        if self.records.len() < Self::CAPACITY {
+            self.records.push(record);
+        } else {
+            self.records[self.head as usize] = record;
+            self.head = ((self.head as usize + 1) % Self::CAPACITY) as u16;
+        }
+        let sequence = self.total_records;
+        self.total_records = self.total_records.saturating_add(1);
+        sequence
+    }
+}
+
+/// Per-member result of an automated penalty run
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum PenaltyOutcome {
+    /// Contributed for the month
+    Paid,
+    /// Late penalty added
+    Penalized,
+    /// Moved to `Defaulted`, with a late penalty added if not yet charged
+    Defaulted,
+    /// Not active in the circle, or the month's penalty was already charged
+    Skipped,
+}
+
+impl PenaltyOutcome {
+    /// Stable numeric encoding, used in penalty events
+    pub fn as_u8(&self) -> u8 {
+        match self {
+            PenaltyOutcome::Paid => 0,
+            PenaltyOutcome::Penalized => 1,
+            PenaltyOutcome::Defaulted => 2,
+            PenaltyOutcome::Skipped => 3,
+        }
+    }
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, PartialEq, Eq)]
+pub enum AutomationEventType {
+    ContributionCollection,
+    PayoutDistribution, 
+    PenaltyEnforcement,
+    ScheduleUpdate,
 }
 
 // Revenue Module Structures
@@ -728,13 +1358,26 @@ pub struct Treasury {
     pub yield_fees: u64,
     /// Management fees from staked amounts (2% annual)
     pub management_fees: u64,
-    /// Last time management fees were collected
+    /// Deprecated: management fees are timed per circle by
+    /// `CircleFeeLedger::last_management_fee_at`. Kept so the layout of
+    /// existing treasuries does not shift; read only by `migrate_circle_stake`.
     pub last_management_fee_collection: i64,
     /// Bump seed for PDA
     pub bump: u8,
+    /// Lamports paid per crank when a circle's keeper budget cannot cover it
+    pub keeper_bounty: u64,
+    /// Total lamports paid to keepers from the treasury
+    pub keeper_rewards_paid: u64,
+    /// Per-epoch protocol totals, a ring indexed by epoch number
+    pub revenue_epochs: Vec<RevenueEpoch>,
 }
 
 impl Treasury {
+    /// Length of a revenue epoch (one week)
+    pub const EPOCH_SECONDS: i64 = 7 * 24 * 60 * 60;
+    /// Number of epochs kept before their bucket is reused (about a year)
+    pub const EPOCH_HISTORY: usize = 52;
+
     pub const SPACE: usize = 8 + // discriminator
         32 + // authority
         8 + // total_fees_collected
@@ -743,7 +1386,124 @@ impl Treasury {
         8 + // management_fees
         8 + // last_management_fee_collection
         1 + // bump
+        8 + // keeper_bounty
+        8 + // keeper_rewards_paid
+        4 + RevenueEpoch::SPACE * Self::EPOCH_HISTORY + // revenue_epochs
         100; // padding
+
+    /// Revenue epoch containing `timestamp`
+    pub fn epoch_at(timestamp: i64) -> u64 {
+        (timestamp.max(0) / Self::EPOCH_SECONDS) as u64
+    }
+
+    fn epoch_slot(epoch: u64) -> usize {
+        (epoch % Self::EPOCH_HISTORY as u64) as usize
+    }
+
+    /// Totals for `epoch`, if its bucket has not been reused since
+    pub fn revenue_epoch(&self, epoch: u64) -> Option<&RevenueEpoch> {
+        self.revenue_epochs
+            .get(Self::epoch_slot(epoch))
+            .filter(|bucket| bucket.epoch == epoch)
+    }
+
+    /// Bucket for `epoch`, cleared first if it still holds an older epoch.
+    /// Fails on treasuries created before epochs were tracked until they
+    /// are migrated.
+    pub fn revenue_epoch_mut(&mut self, epoch: u64) -> Result<&mut RevenueEpoch> {
+        let bucket = self.revenue_epochs
+            .get_mut(Self::epoch_slot(epoch))
+            .ok_or(HaloError::TreasuryNotMigrated)?;
+        if bucket.epoch != epoch {
+            *bucket = RevenueEpoch { epoch, ..RevenueEpoch::default() };
+        }
+        Ok(bucket)
+    }
+}
+
+/// Protocol revenue recorded during one epoch
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Default)]
+pub struct RevenueEpoch {
+    /// Epoch number (timestamp / `Treasury::EPOCH_SECONDS`)
+    pub epoch: u64,
+    /// Distribution fees collected
+    pub distribution_fees: u64,
+    /// Yield fees collected
+    pub yield_fees: u64,
+    /// Management fees collected
+    pub management_fees: u64,
+    /// Gross pot amount distributed
+    pub total_distributions: u64,
+    /// Gross yield distributed
+    pub total_yield: u64,
+    /// Stake assessed for management fees, counted once per circle
+    pub managed_stake: u64,
+    /// Circles that paid fees in this epoch
+    pub active_circles: u32,
+}
+
+impl RevenueEpoch {
+    pub const SPACE: usize = 8 + // epoch
+        8 + // distribution_fees
+        8 + // yield_fees
+        8 + // management_fees
+        8 + // total_distributions
+        8 + // total_yield
+        8 + // managed_stake
+        4; // active_circles
+
+    pub fn total_fees(&self) -> Option<u64> {
+        self.distribution_fees
+            .checked_add(self.yield_fees)?
+            .checked_add(self.management_fees)
+    }
+}
+
+/// Fees a circle has paid the protocol and the activity they were charged on
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Default)]
+pub struct CircleFeeLedger {
+    /// Distribution fees taken from this circle's pots
+    pub distribution_fees: u64,
+    /// Yield fees taken from yield paid into this circle
+    pub yield_fees: u64,
+    /// Management fees taken from this circle's stake
+    pub management_fees: u64,
+    /// Gross pot amount distributed
+    pub total_distributed: u64,
+    /// Gross yield paid into the circle
+    pub total_yield: u64,
+    /// Stake assessed at the last management fee collection
+    pub managed_stake: u64,
+    /// Last revenue epoch in which the circle paid fees
+    pub last_active_epoch: u64,
+    /// Revenue epoch of the last management fee collection
+    pub last_management_epoch: u64,
+    /// When management fees last accrued for this circle
+    pub last_management_fee_at: i64,
+}
+
+impl CircleFeeLedger {
+    pub const SPACE: usize = 8 + // distribution_fees
+        8 + // yield_fees
+        8 + // management_fees
+        8 + // total_distributed
+        8 + // total_yield
+        8 + // managed_stake
+        8 + // last_active_epoch
+        8 + // last_management_epoch
+        8; // last_management_fee_at
+
+    /// Whether `migrate_circle_stake` has started the management fee timer.
+    /// Circles created before per-circle fees have no timer yet.
+    pub fn is_migrated(&self) -> bool {
+        self.last_management_fee_at != 0
+    }
+
+    /// Seconds of management fees accrued since the last collection
+    pub fn management_fee_elapsed(&self, now: i64) -> Result<i64> {
+        require!(self.is_migrated(), crate::errors::HaloError::CircleNotMigrated);
+        Ok(now - self.last_management_fee_at)
+    }
 }
 
 /// Revenue parameters that can be adjusted by governance
@@ -763,9 +1523,14 @@ pub struct RevenueParams {
     pub last_updated: i64,
     /// Bump seed for PDA
     pub bump: u8,
+    /// Fee discounts by trust tier
+    pub fee_schedule: TierFeeSchedule,
 }
 
 impl RevenueParams {
+    pub const MAX_FEE_RATE: u16 = 1000; // 10%
+    pub const MIN_MANAGEMENT_FEE_INTERVAL: i64 = 86400; // 1 day
+
     pub const SPACE: usize = 8 + // discriminator
         32 + // authority
         2 + // distribution_fee_rate
@@ -774,6 +1539,7 @@ impl RevenueParams {
         8 + // management_fee_interval
         8 + // last_updated
         1 + // bump
+        TierFeeSchedule::SPACE + // fee_schedule
         50; // padding
     
     /// Default revenue parameters
@@ -783,33 +1549,121 @@ impl RevenueParams {
     
     /// Calculate distribution fee amount
     pub fn calculate_distribution_fee(&self, amount: u64) -> Result<u64> {
-        amount
-            .checked_mul(self.distribution_fee_rate as u64)
-            .and_then(|result| result.checked_div(10000))
+        math::bps_of(amount, self.distribution_fee_rate as u64, Rounding::Down)
             .ok_or_else(|| anchor_lang::error!(crate::errors::HaloError::ArithmeticOverflow))
     }
     
     /// Calculate yield fee amount
     pub fn calculate_yield_fee(&self, amount: u64) -> Result<u64> {
-        amount
-            .checked_mul(self.yield_fee_rate as u64)
-            .and_then(|result| result.checked_div(10000))
+        math::bps_of(amount, self.yield_fee_rate as u64, Rounding::Down)
             .ok_or_else(|| anchor_lang::error!(crate::errors::HaloError::ArithmeticOverflow))
     }
     
     /// Calculate annual management fee for a given stake amount and time period
     pub fn calculate_management_fee(&self, stake_amount: u64, time_elapsed_seconds: i64) -> Result<u64> {
-        let annual_fee = stake_amount
-            .checked_mul(self.management_fee_rate as u64)
-            .and_then(|result| result.checked_div(10000))
+        let annual_fee = math::bps_of(stake_amount, self.management_fee_rate as u64, Rounding::Down)
             .ok_or_else(|| anchor_lang::error!(crate::errors::HaloError::ArithmeticOverflow))?;
-        
-        let seconds_in_year = 365 * 24 * 60 * 60;
-        annual_fee
-            .checked_mul(time_elapsed_seconds as u64)
-            .and_then(|result| result.checked_div(seconds_in_year))
+        let time_elapsed_seconds = u64::try_from(time_elapsed_seconds)
+            .map_err(|_| anchor_lang::error!(crate::errors::HaloError::ArithmeticOverflow))?;
+
+        math::mul_div(annual_fee, time_elapsed_seconds, math::SECONDS_PER_YEAR, Rounding::Down)
             .ok_or_else(|| anchor_lang::error!(crate::errors::HaloError::ArithmeticOverflow))
     }
+
+    /// Distribution fee owed by a recipient of `tier`, and the part of the
+    /// base fee waived by their discount
+    pub fn calculate_discounted_distribution_fee(&self, amount: u64, tier: &TrustTier) -> Result<(u64, u64)> {
+        let base_fee = self.calculate_distribution_fee(amount)?;
+        self.fee_schedule.discount_for(tier).apply(base_fee, FeeKind::Distribution)
+    }
+
+    /// Management fee owed on stake held by `tier` members, and the part of
+    /// the base fee waived by their discount
+    pub fn calculate_discounted_management_fee(
+        &self,
+        stake_amount: u64,
+        time_elapsed_seconds: i64,
+        tier: &TrustTier,
+    ) -> Result<(u64, u64)> {
+        let base_fee = self.calculate_management_fee(stake_amount, time_elapsed_seconds)?;
+        self.fee_schedule.discount_for(tier).apply(base_fee, FeeKind::Management)
+    }
+}
+
+/// Fee that a trust tier discount applies to
+pub enum FeeKind {
+    Distribution,
+    Management,
+}
+
+/// Discount off the base fee, in basis points (10000 waives the fee)
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, PartialEq, Eq, Debug, Default)]
+pub struct TierFeeDiscount {
+    pub distribution_bps: u16,
+    pub management_bps: u16,
+}
+
+impl TierFeeDiscount {
+    pub const SPACE: usize = 2 + 2;
+
+    /// Split `base_fee` into the fee charged and the amount waived
+    pub fn apply(&self, base_fee: u64, kind: FeeKind) -> Result<(u64, u64)> {
+        let discount_bps = match kind {
+            FeeKind::Distribution => self.distribution_bps,
+            FeeKind::Management => self.management_bps,
+        };
+        let discount = math::bps_of(base_fee, discount_bps as u64, Rounding::Down)
+            .ok_or_else(|| anchor_lang::error!(crate::errors::HaloError::ArithmeticOverflow))?;
+        let fee = base_fee
+            .checked_sub(discount)
+            .ok_or_else(|| anchor_lang::error!(crate::errors::HaloError::ArithmeticOverflow))?;
+        Ok((fee, discount))
+    }
+}
+
+/// Fee discounts for each trust tier
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, PartialEq, Eq, Debug)]
+pub struct TierFeeSchedule {
+    pub newcomer: TierFeeDiscount,
+    pub silver: TierFeeDiscount,
+    pub gold: TierFeeDiscount,
+    pub platinum: TierFeeDiscount,
+}
+
+impl Default for TierFeeSchedule {
+    fn default() -> Self {
+        TierFeeSchedule {
+            newcomer: TierFeeDiscount::default(),
+            silver: TierFeeDiscount { distribution_bps: 1000, management_bps: 1000 }, // 10% off
+            gold: TierFeeDiscount { distribution_bps: 2500, management_bps: 2500 },   // 25% off
+            platinum: TierFeeDiscount { distribution_bps: 5000, management_bps: 5000 }, // 50% off
+        }
+    }
+}
+
+impl TierFeeSchedule {
+    pub const SPACE: usize = TierFeeDiscount::SPACE * 4;
+
+    pub fn discount_for(&self, tier: &TrustTier) -> &TierFeeDiscount {
+        match tier {
+            TrustTier::Newcomer => &self.newcomer,
+            TrustTier::Silver => &self.silver,
+            TrustTier::Gold => &self.gold,
+            TrustTier::Platinum => &self.platinum,
+        }
+    }
+
+    /// Discounts cannot exceed the fee and must not shrink as tiers rise
+    pub fn is_valid(&self) -> bool {
+        let tiers = [&self.newcomer, &self.silver, &self.gold, &self.platinum];
+        tiers.iter().all(|d| {
+            d.distribution_bps as u64 <= math::BPS_DENOMINATOR && d.management_bps as u64 <= math::BPS_DENOMINATOR
+        }) &&
+        tiers.windows(2).all(|pair| {
+            pair[0].distribution_bps <= pair[1].distribution_bps &&
+            pair[0].management_bps <= pair[1].management_bps
+        })
+    }
 }
 
 /// Revenue report structure for tracking and analytics
@@ -827,13 +1681,13 @@ pub struct RevenueReport {
     pub period_yield_fees: u64,
     /// Management fees in this period
     pub period_management_fees: u64,
-    /// Number of circles active in this period
+    /// Most circles that paid fees in any one epoch of the period
     pub active_circles: u32,
     /// Total amount distributed in this period
     pub total_distributions: u64,
     /// Total yield generated in this period
     pub total_yield: u64,
-    /// Total stake amount subject to management fees
+    /// Largest stake assessed for management fees in any one epoch of the period
     pub total_managed_stake: u64,
     /// Bump seed for PDA
     pub bump: u8,
@@ -891,10 +1745,156 @@ pub struct GovernanceProposal {
     pub executed: bool,
     /// Execution timestamp
     pub executed_at: Option<i64>,
-    /// New interest rate (for interest rate proposals)
+    /// New interest rate (for interest rate proposals). Superseded by
+    /// `action`; kept in place for the account layout and mirrored from
+    /// `ProposalAction::SetPenaltyRate`
     pub new_interest_rate: Option<u16>,
     /// Bump seed for PDA
     pub bump: u8,
+    /// Voting power source snapshotted from the circle at creation
+    pub voting_power_source: VotingPowerSource,
+    /// Governance mint snapshotted from the circle at creation
+    pub governance_mint: Option<Pubkey>,
+    /// Quorum in basis points of `eligible_voters`, snapshotted at creation
+    pub quorum_bps: u16,
+    /// Tally mode snapshotted at creation
+    pub tally_mode: TallyMode,
+    /// Timelock snapshotted at creation
+    pub timelock_seconds: i64,
+    /// Circle members when the proposal was created
+    pub eligible_voters: u8,
+    /// Number of members who voted
+    pub voter_count: u16,
+    /// Earliest execution time, set when the proposal succeeds
+    pub executable_at: i64,
+    /// Members whose power a delegate has already cast on this proposal
+    pub delegated_voters: Vec<Pubkey>,
+    /// Change applied by a protocol realm proposal
+    pub protocol_action: Option<ProtocolAction>,
+    /// Governance token balances of the circle's members when the proposal
+    /// was created, counted for `VotingPowerSource::TokenBalance`
+    pub token_snapshot: Vec<TokenBalanceSnapshot>,
+    /// Typed change applied on execution
+    pub action: Option<ProposalAction>,
+}
+
+/// A member's governance token balance captured at proposal creation
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy)]
+pub struct TokenBalanceSnapshot {
+    pub member: Pubkey,
+    pub amount: u64,
+}
+
+/// Where a voter's voting power comes from
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, PartialEq, Eq, Debug, Default)]
+pub enum VotingPowerSource {
+    #[default]
+    OneMemberOneVote, // Every active member has 1 vote
+    StakeWeighted,    // Member::stake_amount
+    TrustWeighted,    // TrustScore::score
+    TokenBalance,     // Balance of the circle's governance mint at proposal creation
+}
+
+/// How votes are summed when deciding a proposal's outcome
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, PartialEq, Eq, Debug, Default)]
+pub enum TallyMode {
+    Linear,    // Compare raw voting power
+    #[default]
+    Quadratic, // Compare sqrt(voting power) per voter
+}
+
+/// Per-circle governance settings, chosen by the circle creator
+#[derive(AnchorSerialize, AnchorDeserialize, Clone)]
+pub struct GovernanceConfig {
+    /// Source of each voter's voting power
+    pub voting_power_source: VotingPowerSource,
+    /// SPL mint counted for `VotingPowerSource::TokenBalance`
+    pub governance_mint: Option<Pubkey>,
+    /// Share of members that must vote, in basis points
+    pub quorum_bps: u16,
+    /// Linear or quadratic outcome evaluation
+    pub tally_mode: TallyMode,
+    /// Delay between success and execution, during which the creator can veto
+    pub timelock_seconds: i64,
+}
+
+impl Default for GovernanceConfig {
+    fn default() -> Self {
+        GovernanceConfig {
+            voting_power_source: VotingPowerSource::default(),
+            governance_mint: None,
+            quorum_bps: Self::DEFAULT_QUORUM_BPS,
+            tally_mode: TallyMode::default(),
+            timelock_seconds: Self::DEFAULT_TIMELOCK_SECONDS,
+        }
+    }
+}
+
+impl GovernanceConfig {
+    pub const DEFAULT_QUORUM_BPS: u16 = 5000; // 50% of members
+    pub const DEFAULT_TIMELOCK_SECONDS: i64 = 24 * 60 * 60; // 1 day
+    pub const MAX_TIMELOCK_SECONDS: i64 = 7 * 24 * 60 * 60; // 7 days
+
+    pub const SPACE: usize = 1 + // voting_power_source
+        1 + 32 + // governance_mint (Option<Pubkey>)
+        2 + // quorum_bps
+        1 + // tally_mode
+        8; // timelock_seconds
+}
+
+/// Typed change a proposal applies to its circle when executed
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, PartialEq, Eq)]
+pub enum ProposalAction {
+    SetPenaltyRate { rate: u16 },
+    SetGracePeriod { days: u8 },
+    SetPayoutMethod { method: PayoutMethod },
+    SetMinTrustTier { tier: u8 },
+    SetAutoCollection { auto_collect: bool, auto_distribute: bool, auto_penalty: bool },
+    RemoveMember { member: Pubkey },
+    ExtendDuration { additional_months: u8 },
+    SetEmergencyPause { paused: bool },
+    EmergencyWindDown,
+}
+
+impl ProposalAction {
+    /// Largest serialized variant (RemoveMember)
+    pub const SPACE: usize = 1 + 32;
+
+    /// Actions only an emergency proposal may carry
+    pub fn is_emergency(&self) -> bool {
+        matches!(self, ProposalAction::SetEmergencyPause { .. } | ProposalAction::EmergencyWindDown)
+    }
+
+    /// Check the action against the circle's current state at creation time
+    pub fn validate(&self, circle: &Circle) -> Result<()> {
+        match self {
+            ProposalAction::SetPenaltyRate { rate } => {
+                require!(*rate <= 10000, crate::errors::HaloError::InvalidProposalAction); // Max 100%
+            },
+            ProposalAction::SetGracePeriod { days } => {
+                require!(*days <= Circle::MAX_GRACE_PERIOD_DAYS, crate::errors::HaloError::InvalidProposalAction);
+            },
+            ProposalAction::SetPayoutMethod { .. } => {},
+            ProposalAction::SetMinTrustTier { tier } => {
+                require!(TrustTier::from_u8(*tier).is_some(), crate::errors::HaloError::InvalidProposalAction);
+            },
+            ProposalAction::SetAutoCollection { .. } => {},
+            ProposalAction::RemoveMember { member } => {
+                require!(circle.members.contains(member), crate::errors::HaloError::InvalidProposalAction);
+            },
+            ProposalAction::ExtendDuration { additional_months } => {
+                let new_duration = circle.duration_months.checked_add(*additional_months);
+                require!(
+                    *additional_months > 0 && new_duration.is_some_and(|d| d <= Circle::MAX_DURATION),
+                    crate::errors::HaloError::InvalidProposalAction
+                );
+            },
+            ProposalAction::SetEmergencyPause { .. } | ProposalAction::EmergencyWindDown => {
+                require!(circle.status == CircleStatus::Active, crate::errors::HaloError::InvalidProposalAction);
+            },
+        }
+        Ok(())
+    }
 }
 
 #[derive(AnchorSerialize, AnchorDeserialize, Clone, PartialEq, Eq)]
@@ -902,6 +1902,59 @@ pub enum ProposalType {
     InterestRateChange,
     CircleParameter,
     Emergency,
+    Protocol, // Protocol realm proposal, see `ProtocolAction`
+}
+
+/// Protocol-wide change a realm proposal applies when executed
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, PartialEq, Eq)]
+pub enum ProtocolAction {
+    SetRevenueFees {
+        distribution_fee_rate: Option<u16>,
+        yield_fee_rate: Option<u16>,
+        management_fee_rate: Option<u16>,
+        management_fee_interval: Option<i64>,
+    },
+    SetAutomationSettings { enabled: Option<bool>, min_interval: Option<i64> },
+    SetTrustTierThresholds { thresholds: TrustTierThresholds },
+    SetFeeSchedule { schedule: TierFeeSchedule },
+}
+
+impl ProtocolAction {
+    /// Largest serialized variant (SetRevenueFees)
+    pub const SPACE: usize = 1 + 3 * (1 + 2) + (1 + 8);
+
+    pub fn validate(&self) -> Result<()> {
+        match self {
+            ProtocolAction::SetRevenueFees {
+                distribution_fee_rate,
+                yield_fee_rate,
+                management_fee_rate,
+                management_fee_interval,
+            } => {
+                for rate in [distribution_fee_rate, yield_fee_rate, management_fee_rate].into_iter().flatten() {
+                    require!(*rate <= RevenueParams::MAX_FEE_RATE, crate::errors::HaloError::InvalidFeeRate);
+                }
+                if let Some(interval) = management_fee_interval {
+                    require!(
+                        *interval >= RevenueParams::MIN_MANAGEMENT_FEE_INTERVAL,
+                        crate::errors::HaloError::InvalidFeeRate
+                    );
+                }
+            },
+            ProtocolAction::SetAutomationSettings { min_interval, .. } => {
+                if let Some(interval) = min_interval {
+                    require!(*interval > 0, crate::errors::HaloError::InvalidAutomationConfig);
+                }
+            },
+            ProtocolAction::SetTrustTierThresholds { thresholds } => {
+                require!(thresholds.is_valid(), crate::errors::HaloError::InvalidProposalAction);
+            },
+            ProtocolAction::SetFeeSchedule { schedule } => {
+                require!(schedule.is_valid(), crate::errors::HaloError::InvalidFeeRate);
+            },
+        }
+        Ok(())
+    }
 }
 
 #[derive(AnchorSerialize, AnchorDeserialize, Clone, PartialEq, Eq)]
@@ -911,6 +1964,7 @@ pub enum ProposalStatus {
     Defeated,
     Executed,
     Cancelled,
+    Vetoed,
 }
 
 impl Default for ProposalStatus {
@@ -935,6 +1989,15 @@ pub struct Vote {
     pub timestamp: i64,
     /// Bump seed for PDA
     pub bump: u8,
+    /// Power cast on behalf of delegators, included in voting_power
+    pub delegations: Vec<DelegatedPower>,
+}
+
+/// Voting power a delegate cast for one delegator
+#[derive(AnchorSerialize, AnchorDeserialize, Clone)]
+pub struct DelegatedPower {
+    pub delegator: Pubkey,
+    pub power: u64,
 }
 
 #[account]
@@ -965,6 +2028,8 @@ pub struct Auction {
     pub bid_count: u32,
     /// Bump seed for PDA
     pub bump: u8,
+    /// Circle month whose pot this auction decides
+    pub month: u8,
 }
 
 #[derive(AnchorSerialize, AnchorDeserialize, Clone, PartialEq, Eq)]
@@ -1001,6 +2066,8 @@ pub struct Bid {
 impl GovernanceProposal {
     pub const MAX_TITLE_LENGTH: usize = 200;
     pub const MAX_DESCRIPTION_LENGTH: usize = 1000;
+    /// Share of votes an emergency proposal needs to pass (2/3)
+    pub const SUPERMAJORITY_BPS: u64 = 6667;
     
     pub fn space() -> usize {
         8 + // discriminator
@@ -1023,9 +2090,29 @@ impl GovernanceProposal {
         1 + 8 + // executed_at (Option<i64>)
         1 + 2 + // new_interest_rate (Option<u16>)
         1 + // bump
+        1 + // voting_power_source
+        1 + 32 + // governance_mint (Option<Pubkey>)
+        2 + // quorum_bps
+        1 + // tally_mode
+        8 + // timelock_seconds
+        1 + // eligible_voters
+        2 + // voter_count
+        8 + // executable_at
+        4 + 32 * Circle::MAX_MEMBERS + // delegated_voters vec
+        1 + ProtocolAction::SPACE + // protocol_action (Option<ProtocolAction>)
+        4 + (32 + 8) * Circle::MAX_MEMBERS + // token_snapshot vec
+        1 + ProposalAction::SPACE + // action (Option<ProposalAction>)
         100 // extra space
     }
 
+    /// Governance token balance `member` held when the proposal was created
+    pub fn snapshot_balance(&self, member: &Pubkey) -> u64 {
+        self.token_snapshot
+            .iter()
+            .find(|snapshot| snapshot.member == *member)
+            .map_or(0, |snapshot| snapshot.amount)
+    }
+
     /// Check if proposal is currently active for voting
     pub fn is_active(&self) -> bool {
         self.status == ProposalStatus::Active
@@ -1036,9 +2123,27 @@ impl GovernanceProposal {
         current_time >= self.voting_end
     }
 
+    /// Check if enough members voted
+    pub fn quorum_reached(&self) -> bool {
+        (self.voter_count as u64).saturating_mul(10000) >=
+            (self.quorum_bps as u64).saturating_mul(self.eligible_voters as u64)
+    }
+
     /// Calculate if proposal has enough votes to pass
     pub fn has_passed(&self) -> bool {
-        self.quadratic_votes_for > self.quadratic_votes_against &&
+        let (votes_for, votes_against) = match self.tally_mode {
+            TallyMode::Linear => (self.votes_for, self.votes_against),
+            TallyMode::Quadratic => (self.quadratic_votes_for, self.quadratic_votes_against),
+        };
+        let majority = if self.proposal_type == ProposalType::Emergency {
+            (votes_for as u128).saturating_mul(10000) >=
+                (Self::SUPERMAJORITY_BPS as u128).saturating_mul(votes_for as u128 + votes_against as u128)
+        } else {
+            votes_for > votes_against
+        };
+        self.quorum_reached() &&
+        votes_for > 0 &&
+        majority &&
         self.total_voting_power >= self.execution_threshold
     }
 }
@@ -1053,6 +2158,7 @@ impl Vote {
         1 + // support
         8 + // timestamp
         1 + // bump
+        4 + (32 + 8) * Circle::MAX_MEMBERS + // delegations vec
         50 // extra space
     }
 }
@@ -1073,6 +2179,7 @@ impl Auction {
         1 + // settled
         4 + // bid_count
         1 + // bump
+        1 + // month
         50 // extra space
     }
 
@@ -1141,10 +2248,12 @@ pub struct EncryptedTrustScore {
 }
 
 impl EncryptedTrustScore {
+    pub const MAX_ENCRYPTED_SCORE_LEN: usize = 256;
+
     pub fn space() -> usize {
         8 + // discriminator
         32 + // authority
-        4 + 256 + // encrypted_score (vec with max 256 bytes)
+        4 + Self::MAX_ENCRYPTED_SCORE_LEN + // encrypted_score (vec with max 256 bytes)
         32 + // arcium_compute_key
         1 + // privacy_enabled
         8 + // last_updated
@@ -1153,6 +2262,27 @@ impl EncryptedTrustScore {
     }
 }
 
+/// Protocol-wide configuration for private trust scores.
+/// The attestor is the off-chain MPC/attestor service whose ed25519 key signs
+/// threshold claims about encrypted scores; it can be rotated or replaced by
+/// a local stand-in for testing.
+#[account]
+pub struct PrivacyConfig {
+    /// Treasury authority that manages this config
+    pub authority: Pubkey,
+    /// Ed25519 key of the trusted attestor service
+    pub attestor: Pubkey,
+    /// Bump seed for PDA
+    pub bump: u8,
+}
+
+impl PrivacyConfig {
+    pub const SPACE: usize = 8 + // discriminator
+        32 + // authority
+        32 + // attestor
+        1; // bump
+}
+
 /// Private circle with Arcium privacy features
 /// Supports anonymous participation and encrypted member data
 #[account]
@@ -1339,19 +2469,8 @@ impl ReflectYieldTracking {
         }
 
         let total_yield = self.calculate_total_yield();
-
-        // Calculate annualized return
-        let seconds_per_year = 31_536_000_u64; // 365 days
-        let annualized_yield = (total_yield as u128)
-            .saturating_mul(seconds_per_year as u128)
-            .checked_div(time_elapsed_seconds as u128)
-            .unwrap_or(0) as u64;
-
-        // Return APY in basis points (1% = 100 bps)
-        annualized_yield
-            .saturating_mul(10_000)
-            .checked_div(total_deposited)
-            .unwrap_or(0)
+        // Annualized return in basis points (1% = 100 bps)
+        math::annualized_bps(total_yield, total_deposited, time_elapsed_seconds as u64)
     }
 
     /// Calculate Reflect-specific APY
@@ -1360,19 +2479,9 @@ impl ReflectYieldTracking {
             return 0;
         }
 
-        // Price appreciation percentage
+        // Annualized price appreciation
         let price_increase = self.current_reflect_price.saturating_sub(self.reflect_price_at_deposit);
-        let appreciation_bps = price_increase
-            .saturating_mul(10_000)
-            .checked_div(self.reflect_price_at_deposit)
-            .unwrap_or(0);
-
-        // Annualize the return
-        let seconds_per_year = 31_536_000_u64;
-        appreciation_bps
-            .saturating_mul(seconds_per_year)
-            .checked_div(time_elapsed_seconds as u64)
-            .unwrap_or(0)
+        math::annualized_bps(price_increase, self.reflect_price_at_deposit, time_elapsed_seconds as u64)
     }
 
     /// Calculate Solend-specific APY
@@ -1386,15 +2495,51 @@ impl ReflectYieldTracking {
             return 0;
         }
 
-        let seconds_per_year = 31_536_000_u64;
-        let annualized_yield = (self.solend_yield_earned as u128)
-            .saturating_mul(seconds_per_year as u128)
-            .checked_div(time_elapsed_seconds as u128)
-            .unwrap_or(0) as u64;
+        // Annualized return in basis points (1% = 100 bps)
+        math::annualized_bps(self.solend_yield_earned, total_deposited, time_elapsed_seconds as u64)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
 
-        annualized_yield
-            .saturating_mul(10_000)
-            .checked_div(total_deposited)
-            .unwrap_or(0)
+    #[test]
+    fn penalty_is_charged_once_after_collection_records_the_miss() {
+        let mut member = Member::default();
+
+        // Automated collection records the miss first
+        assert!(member.record_missed(0, 100, 0));
+        assert_eq!(member.contributions_missed, 1);
+
+        // Enforcement on the same round does not count the miss again
+        // but still charges the penalty, exactly once
+        assert!(!member.record_missed(0, 200, 3));
+        assert!(member.mark_penalty_charged(0));
+        assert!(!member.mark_penalty_charged(0));
+        assert_eq!(member.contributions_missed, 1);
+    }
+
+    #[test]
+    fn penalty_needs_a_recorded_miss() {
+        let mut member = Member::default();
+        assert!(!member.mark_penalty_charged(0));
+
+        assert!(member.record_missed(1, 100, 2));
+        assert!(!member.mark_penalty_charged(0));
+        assert!(member.mark_penalty_charged(1));
     }
-}
\ No newline at end of file
+
+    #[test]
+    fn unmigrated_circle_accrues_no_management_fee() {
+        // A circle created before per-circle fees has no timer, so a
+        // collection must fail instead of charging since the Unix epoch
+        let mut ledger = CircleFeeLedger::default();
+        assert!(!ledger.is_migrated());
+        assert!(ledger.management_fee_elapsed(1_700_000_000).is_err());
+
+        ledger.last_management_fee_at = 1_700_000_000;
+        assert!(ledger.is_migrated());
+        assert_eq!(ledger.management_fee_elapsed(1_700_086_400).unwrap(), 86_400);
+    }
+}
@@ -0,0 +1,296 @@
+use anchor_lang::prelude::*;
+
+use crate::errors::HaloError;
+use crate::instructions::apply_trust_penalty;
+use crate::state::{Circle, CircleStatus, Member, MemberStatus, TrustPenaltyKind, TrustScore, TrustTier};
+
+/// Guarantee a Gold/Platinum member gives for a newcomer joining their circle
+#[account]
+pub struct Vouch {
+    /// The circle the newcomer is joining
+    pub circle: Pubkey,
+    /// Guarantor locking part of their stake
+    pub voucher: Pubkey,
+    /// Newcomer being vouched for
+    pub newcomer: Pubkey,
+    /// Amount of the voucher's stake locked as guarantee
+    pub locked_amount: u64,
+    /// Lifecycle status of the vouch
+    pub status: VouchStatus,
+    /// Timestamp when the vouch was created
+    pub created_at: i64,
+    /// Bump seed for PDA
+    pub bump: u8,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, PartialEq, Eq, Default)]
+pub enum VouchStatus {
+    #[default]
+    Pending,  // Newcomer has not joined yet
+    Active,   // Newcomer joined using this vouch
+    Slashed,  // Newcomer defaulted, guarantee was slashed
+}
+
+impl Vouch {
+    pub fn space() -> usize {
+        8 + // discriminator
+        32 + // circle
+        32 + // voucher
+        32 + // newcomer
+        8 + // locked_amount
+        1 + // status
+        8 + // created_at
+        1 + // bump
+        50 // extra space
+    }
+}
+
+#[derive(Accounts)]
+#[instruction(newcomer: Pubkey)]
+pub struct VouchForNewcomer<'info> {
+    #[account(
+        init,
+        payer = voucher,
+        space = Vouch::space(),
+        seeds = [b"vouch", circle.key().as_ref(), newcomer.as_ref(), voucher.key().as_ref()],
+        bump
+    )]
+    pub vouch: Account<'info, Vouch>,
+
+    pub circle: Account<'info, Circle>,
+
+    #[account(
+        mut,
+        seeds = [b"member", circle.key().as_ref(), voucher.key().as_ref()],
+        bump = voucher_member.bump
+    )]
+    pub voucher_member: Account<'info, Member>,
+
+    #[account(
+        seeds = [b"trust_score", voucher.key().as_ref()],
+        bump = voucher_trust_score.bump
+    )]
+    pub voucher_trust_score: Account<'info, TrustScore>,
+
+    #[account(mut)]
+    pub voucher: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct ReleaseVouch<'info> {
+    #[account(
+        mut,
+        close = voucher,
+        seeds = [b"vouch", circle.key().as_ref(), vouch.newcomer.as_ref(), vouch.voucher.as_ref()],
+        bump = vouch.bump,
+        constraint = vouch.circle == circle.key() @ HaloError::InvalidVouch
+    )]
+    pub vouch: Account<'info, Vouch>,
+
+    pub circle: Account<'info, Circle>,
+
+    #[account(
+        mut,
+        seeds = [b"member", circle.key().as_ref(), vouch.voucher.as_ref()],
+        bump = voucher_member.bump
+    )]
+    pub voucher_member: Account<'info, Member>,
+
+    /// Required once the newcomer has joined
+    #[account(
+        seeds = [b"member", circle.key().as_ref(), vouch.newcomer.as_ref()],
+        bump = newcomer_member.bump
+    )]
+    pub newcomer_member: Option<Account<'info, Member>>,
+
+    /// CHECK: Receives the vouch rent back; must be the original voucher
+    #[account(mut, address = vouch.voucher @ HaloError::InvalidVouch)]
+    pub voucher: AccountInfo<'info>,
+
+    pub authority: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct SlashVouch<'info> {
+    #[account(
+        mut,
+        seeds = [b"vouch", circle.key().as_ref(), vouch.newcomer.as_ref(), vouch.voucher.as_ref()],
+        bump = vouch.bump,
+        constraint = vouch.circle == circle.key() @ HaloError::InvalidVouch
+    )]
+    pub vouch: Account<'info, Vouch>,
+
+    #[account(mut)]
+    pub circle: Account<'info, Circle>,
+
+    #[account(
+        seeds = [b"member", circle.key().as_ref(), vouch.newcomer.as_ref()],
+        bump = newcomer_member.bump
+    )]
+    pub newcomer_member: Account<'info, Member>,
+
+    #[account(
+        mut,
+        seeds = [b"member", circle.key().as_ref(), vouch.voucher.as_ref()],
+        bump = voucher_member.bump
+    )]
+    pub voucher_member: Account<'info, Member>,
+
+    #[account(
+        mut,
+        seeds = [b"trust_score", vouch.voucher.as_ref()],
+        bump = voucher_trust_score.bump
+    )]
+    pub voucher_trust_score: Account<'info, TrustScore>,
+
+    pub authority: Signer<'info>,
+}
+
+pub(crate) fn vouch_for_newcomer(
+    ctx: Context<VouchForNewcomer>,
+    newcomer: Pubkey,
+    amount: u64,
+) -> Result<()> {
+    let circle = &ctx.accounts.circle;
+    let voucher_member = &mut ctx.accounts.voucher_member;
+    let voucher_trust_score = &ctx.accounts.voucher_trust_score;
+
+    require!(circle.status == CircleStatus::Active, HaloError::CircleNotActive);
+    require!(voucher_member.status == MemberStatus::Active, HaloError::MemberInDefault);
+    require!(
+        voucher_trust_score.tier.as_u8() >= TrustTier::Gold.as_u8(),
+        HaloError::VoucherTierTooLow
+    );
+    require!(newcomer != ctx.accounts.voucher.key(), HaloError::InvalidVouch);
+    require!(!circle.members.contains(&newcomer), HaloError::MemberAlreadyExists);
+    require!(amount > 0, HaloError::InvalidVouch);
+
+    // Only stake that is not already guaranteeing someone else can be locked
+    let unlocked_stake = voucher_member.stake_amount
+        .checked_sub(voucher_member.vouched_stake_locked)
+        .ok_or(HaloError::ArithmeticOverflow)?;
+    require!(amount <= unlocked_stake, HaloError::InsufficientUnlockedStake);
+
+    voucher_member.vouched_stake_locked = voucher_member.vouched_stake_locked
+        .checked_add(amount)
+        .ok_or(HaloError::ArithmeticOverflow)?;
+
+    let vouch = &mut ctx.accounts.vouch;
+    vouch.circle = circle.key();
+    vouch.voucher = ctx.accounts.voucher.key();
+    vouch.newcomer = newcomer;
+    vouch.locked_amount = amount;
+    vouch.status = VouchStatus::Pending;
+    vouch.created_at = Clock::get()?.unix_timestamp;
+    vouch.bump = ctx.bumps.vouch;
+
+    emit!(VouchCreated {
+        circle: vouch.circle,
+        voucher: vouch.voucher,
+        newcomer,
+        locked_amount: amount,
+    });
+
+    Ok(())
+}
+
+/// Unlock the guarantee once the newcomer completed the circle without
+/// defaulting, or cancel a vouch the newcomer never used.
+pub(crate) fn release_vouch(ctx: Context<ReleaseVouch>) -> Result<()> {
+    let vouch = &ctx.accounts.vouch;
+    let circle = &ctx.accounts.circle;
+
+    match vouch.status {
+        VouchStatus::Pending => {
+            require!(
+                ctx.accounts.authority.key() == vouch.voucher,
+                HaloError::UnauthorizedMember
+            );
+        },
+        VouchStatus::Active => {
+            let newcomer_member = ctx.accounts.newcomer_member
+                .as_ref()
+                .ok_or(HaloError::MemberNotFound)?;
+            require!(
+                circle.status == CircleStatus::Completed &&
+                newcomer_member.status != MemberStatus::Defaulted,
+                HaloError::VouchNotReleasable
+            );
+        },
+        VouchStatus::Slashed => return Err(HaloError::VouchNotReleasable.into()),
+    }
+
+    let voucher_member = &mut ctx.accounts.voucher_member;
+    voucher_member.vouched_stake_locked = voucher_member.vouched_stake_locked
+        .checked_sub(vouch.locked_amount)
+        .ok_or(HaloError::ArithmeticOverflow)?;
+
+    msg!("Vouch for {} released, {} stake unlocked", vouch.newcomer, vouch.locked_amount);
+    Ok(())
+}
+
+/// Slash the guarantor's locked stake after the vouched newcomer defaulted.
+/// The slashed stake stays in escrow and is credited to the circle pot.
+pub(crate) fn slash_vouch(ctx: Context<SlashVouch>) -> Result<()> {
+    let vouch = &mut ctx.accounts.vouch;
+    let circle = &mut ctx.accounts.circle;
+    let voucher_member = &mut ctx.accounts.voucher_member;
+    let voucher_trust_score = &mut ctx.accounts.voucher_trust_score;
+    let clock = Clock::get()?;
+
+    require!(vouch.status == VouchStatus::Active, HaloError::InvalidVouch);
+    require!(
+        ctx.accounts.newcomer_member.status == MemberStatus::Defaulted,
+        HaloError::NewcomerNotDefaulted
+    );
+
+    let slashed_amount = vouch.locked_amount;
+    voucher_member.vouched_stake_locked = voucher_member.vouched_stake_locked
+        .checked_sub(slashed_amount)
+        .ok_or(HaloError::ArithmeticOverflow)?;
+    voucher_member.stake_amount = voucher_member.stake_amount
+        .checked_sub(slashed_amount)
+        .ok_or(HaloError::ArithmeticOverflow)?;
+    circle.total_stake = circle.total_stake.saturating_sub(slashed_amount);
+    circle.total_pot = circle.total_pot
+        .checked_add(slashed_amount)
+        .ok_or(HaloError::ArithmeticOverflow)?;
+
+    apply_trust_penalty(
+        voucher_trust_score,
+        voucher_member,
+        TrustPenaltyKind::GuarantorDefault,
+        clock.unix_timestamp,
+    );
+
+    vouch.status = VouchStatus::Slashed;
+
+    emit!(VouchSlashed {
+        circle: vouch.circle,
+        voucher: vouch.voucher,
+        newcomer: vouch.newcomer,
+        slashed_amount,
+        voucher_score: voucher_trust_score.score,
+    });
+
+    Ok(())
+}
+
+#[event]
+pub struct VouchCreated {
+    pub circle: Pubkey,
+    pub voucher: Pubkey,
+    pub newcomer: Pubkey,
+    pub locked_amount: u64,
+}
+
+#[event]
+pub struct VouchSlashed {
+    pub circle: Pubkey,
+    pub voucher: Pubkey,
+    pub newcomer: Pubkey,
+    pub slashed_amount: u64,
+    pub voucher_score: u16,
+}
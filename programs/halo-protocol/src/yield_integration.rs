@@ -1,9 +1,81 @@
 use anchor_lang::prelude::*;
+use anchor_lang::solana_program::instruction::{AccountMeta, Instruction};
+use anchor_lang::solana_program::program::invoke_signed;
 use anchor_spl::token::{self, Token, TokenAccount, Transfer};
 
 use crate::errors::HaloError;
 use crate::state::{Circle, CircleEscrow, MemberYieldShare};
 
+/// Solend lending program
+pub const SOLEND_PROGRAM_ID: Pubkey = pubkey!("So1endDq2YkqhipRh3WViPa8hdiSpxWy6z3Z6tMCpAo");
+
+/// Instruction tag of Solend's `RedeemReserveCollateral`
+const SOLEND_REDEEM_RESERVE_COLLATERAL: u8 = 5;
+
+/// Accounts of Solend's `RedeemReserveCollateral`, in instruction order
+pub(crate) struct SolendRedeemAccounts<'a, 'info> {
+    pub source_collateral: &'a AccountInfo<'info>,
+    pub destination_liquidity: &'a AccountInfo<'info>,
+    pub reserve: &'a AccountInfo<'info>,
+    pub reserve_collateral_mint: &'a AccountInfo<'info>,
+    pub reserve_liquidity_supply: &'a AccountInfo<'info>,
+    pub lending_market: &'a AccountInfo<'info>,
+    pub lending_market_authority: &'a AccountInfo<'info>,
+    pub transfer_authority: &'a AccountInfo<'info>,
+    pub token_program: &'a AccountInfo<'info>,
+}
+
+/// Redeem cTokens for the reserve's liquidity through Solend. The transfer
+/// authority owns the collateral account and signs with `signer_seeds`;
+/// Solend checks that the reserve, mint, supply and market belong together.
+pub(crate) fn redeem_solend_collateral<'info>(
+    solend_program: &AccountInfo<'info>,
+    accounts: SolendRedeemAccounts<'_, 'info>,
+    collateral_amount: u64,
+    signer_seeds: &[&[&[u8]]],
+) -> Result<()> {
+    require_keys_eq!(solend_program.key(), SOLEND_PROGRAM_ID, HaloError::YieldAccountsRequired);
+
+    let mut data = Vec::with_capacity(9);
+    data.push(SOLEND_REDEEM_RESERVE_COLLATERAL);
+    data.extend_from_slice(&collateral_amount.to_le_bytes());
+
+    let instruction = Instruction {
+        program_id: SOLEND_PROGRAM_ID,
+        accounts: vec![
+            AccountMeta::new(accounts.source_collateral.key(), false),
+            AccountMeta::new(accounts.destination_liquidity.key(), false),
+            AccountMeta::new(accounts.reserve.key(), false),
+            AccountMeta::new(accounts.reserve_collateral_mint.key(), false),
+            AccountMeta::new(accounts.reserve_liquidity_supply.key(), false),
+            AccountMeta::new_readonly(accounts.lending_market.key(), false),
+            AccountMeta::new_readonly(accounts.lending_market_authority.key(), false),
+            AccountMeta::new_readonly(accounts.transfer_authority.key(), true),
+            AccountMeta::new_readonly(accounts.token_program.key(), false),
+        ],
+        data,
+    };
+
+    invoke_signed(
+        &instruction,
+        &[
+            accounts.source_collateral.clone(),
+            accounts.destination_liquidity.clone(),
+            accounts.reserve.clone(),
+            accounts.reserve_collateral_mint.clone(),
+            accounts.reserve_liquidity_supply.clone(),
+            accounts.lending_market.clone(),
+            accounts.lending_market_authority.clone(),
+            accounts.transfer_authority.clone(),
+            accounts.token_program.clone(),
+            solend_program.clone(),
+        ],
+        signer_seeds,
+    )?;
+
+    Ok(())
+}
+
 #[derive(Accounts)]
 pub struct DepositToSolend<'info> {
     #[account(mut)]
@@ -102,6 +174,8 @@ pub(crate) fn deposit_to_solend(
     ctx: Context<DepositToSolend>,
     amount: u64,
 ) -> Result<()> {
+    require!(!ctx.accounts.circle.emergency_paused, HaloError::CirclePaused);
+
     // Validate minimum deposit threshold (e.g., $1000)
     let min_deposit = 100_000_000; // 100 USDC (6 decimals)
     require!(amount >= min_deposit, HaloError::BelowMinimumDeposit);
@@ -0,0 +1,83 @@
+use anchor_lang::prelude::*;
+
+declare_id!("3q5YCFSY3SbW4HH3j1vWZWi9DN5WANwbgrr7wpSJ2A5N");
+
+/// Minimal oracle program for local tests. It only owns queue and function
+/// accounts so Halo's ownership checks have something real to inspect; the
+/// enclave signature itself is produced off-chain by the test's keypair.
+#[program]
+pub mod mock_oracle {
+    use super::*;
+
+    pub fn initialize_queue(ctx: Context<InitializeQueue>, enclave_signer: Pubkey) -> Result<()> {
+        let queue = &mut ctx.accounts.queue;
+        queue.authority = ctx.accounts.authority.key();
+        queue.enclave_signer = enclave_signer;
+
+        msg!("Mock oracle queue created with enclave signer {}", enclave_signer);
+        Ok(())
+    }
+
+    pub fn initialize_function(ctx: Context<InitializeFunction>) -> Result<()> {
+        let function = &mut ctx.accounts.function;
+        function.queue = ctx.accounts.queue.key();
+        function.authority = ctx.accounts.authority.key();
+
+        msg!("Mock oracle function created on queue {}", function.queue);
+        Ok(())
+    }
+}
+
+/// Queue whose enclave signs automation callbacks
+#[account]
+pub struct OracleQueue {
+    /// Wallet that created the queue
+    pub authority: Pubkey,
+    /// Key the enclave signs callback payloads with
+    pub enclave_signer: Pubkey,
+}
+
+impl OracleQueue {
+    pub const SPACE: usize = 8 + // discriminator
+        32 + // authority
+        32; // enclave_signer
+}
+
+/// Function registered on a queue for one circle's automation
+#[account]
+pub struct OracleFunction {
+    /// Queue the function runs on
+    pub queue: Pubkey,
+    /// Wallet that created the function
+    pub authority: Pubkey,
+}
+
+impl OracleFunction {
+    pub const SPACE: usize = 8 + // discriminator
+        32 + // queue
+        32; // authority
+}
+
+#[derive(Accounts)]
+pub struct InitializeQueue<'info> {
+    #[account(init, payer = authority, space = OracleQueue::SPACE)]
+    pub queue: Account<'info, OracleQueue>,
+
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct InitializeFunction<'info> {
+    #[account(init, payer = authority, space = OracleFunction::SPACE)]
+    pub function: Account<'info, OracleFunction>,
+
+    pub queue: Account<'info, OracleQueue>,
+
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
@@ -0,0 +1,927 @@
+import * as anchor from "@coral-xyz/anchor";
+import { Program, BN, web3 } from "@coral-xyz/anchor";
+import { TOKEN_PROGRAM_ID, approve, getAccount } from "@solana/spl-token";
+import { expect } from "chai";
+
+import {
+  airdropSol,
+  createTestMint,
+  createTokenAccount,
+  findTreasuryPDA,
+  findRevenueParamsPDA,
+  findAutomationStatePDA,
+  findCircleAutomationPDA,
+  findAutomationLogPDA,
+  fetchAutomationLog,
+  findKeeperPDA,
+  findKeeperBudgetPDA,
+  buildAutomationCallbackInstruction,
+  initializeCircle,
+  joinCircle,
+  initializeRevenueAccounts,
+  initializeTrustScore,
+  expectError,
+  CircleContext,
+} from "./helpers";
+
+type MemberJoin = {
+  memberKey: web3.PublicKey;
+  memberTokenAccount: web3.PublicKey;
+  trustScoreKey?: web3.PublicKey;
+};
+
+describe("halo-protocol: automation", () => {
+  // -------------------------------------------------------------------------
+  // Provider & program setup
+  // -------------------------------------------------------------------------
+  const provider = anchor.AnchorProvider.env();
+  anchor.setProvider(provider);
+
+  const program = anchor.workspace.HaloProtocol as Program<any>;
+  const connection = provider.connection;
+
+  let creator: web3.Keypair;
+  let mint: web3.PublicKey;
+  let automationStateKey: web3.PublicKey;
+  let treasuryKey: web3.PublicKey;
+  let revenueParamsKey: web3.PublicKey;
+
+  before(async () => {
+    creator = web3.Keypair.generate();
+    await airdropSol(connection, creator.publicKey);
+    mint = await createTestMint(connection, creator);
+
+    [treasuryKey] = findTreasuryPDA();
+    [revenueParamsKey] = findRevenueParamsPDA();
+    if ((await program.account.treasury.fetchNullable(treasuryKey)) === null) {
+      await initializeRevenueAccounts(program, creator);
+    }
+
+    // The automation state is a global singleton; another suite may own it
+    [automationStateKey] = findAutomationStatePDA();
+    const existing = await program.account.automationState.fetchNullable(
+      automationStateKey
+    );
+    if (existing === null) {
+      await program.methods
+        .initializeAutomationState(new BN(0))
+        .accounts({
+          automationState: automationStateKey,
+          switchboardQueue: web3.Keypair.generate().publicKey,
+          authority: creator.publicKey,
+          systemProgram: web3.SystemProgram.programId,
+        })
+        .signers([creator])
+        .rpc();
+    }
+  });
+
+  // =========================================================================
+  // automated_contribution_collection
+  // =========================================================================
+
+  describe("automated_contribution_collection", () => {
+    const contributionAmount = new BN(1_000_000);
+    let autoCircle: CircleContext;
+    let circleAutomationKey: web3.PublicKey;
+    let payer: web3.Keypair;
+    let lapsed: web3.Keypair;
+    let manual: web3.Keypair;
+    let payerJoin: MemberJoin;
+    let lapsedJoin: MemberJoin;
+    let manualJoin: MemberJoin;
+    let keeperKey: web3.PublicKey;
+    let keeperBudgetKey: web3.PublicKey;
+    const bounty = 5_000;
+
+    before(async () => {
+      autoCircle = await initializeCircle(program, creator, mint, {
+        contributionAmount,
+        durationMonths: 3,
+        maxMembers: 5,
+        penaltyRate: 500,
+      });
+
+      payer = web3.Keypair.generate();
+      lapsed = web3.Keypair.generate();
+      manual = web3.Keypair.generate();
+      for (const kp of [payer, lapsed, manual]) {
+        await airdropSol(connection, kp.publicKey);
+      }
+      payerJoin = await joinCircle(program, autoCircle, payer, new BN(2_000_000));
+      lapsedJoin = await joinCircle(program, autoCircle, lapsed, new BN(2_000_000));
+      manualJoin = await joinCircle(program, autoCircle, manual, new BN(2_000_000));
+      for (const [kp, join] of [
+        [payer, payerJoin],
+        [lapsed, lapsedJoin],
+        [manual, manualJoin],
+      ] as [web3.Keypair, MemberJoin][]) {
+        join.trustScoreKey = await initializeTrustScore(program, kp);
+      }
+
+      [circleAutomationKey] = findCircleAutomationPDA(autoCircle.circleKey);
+      await program.methods
+        .setupCircleAutomation(true, false, false, false)
+        .accounts({
+          circleAutomation: circleAutomationKey,
+          automationLog: findAutomationLogPDA(autoCircle.circleKey)[0],
+          automationState: automationStateKey,
+          circle: autoCircle.circleKey,
+          switchboardJob: web3.Keypair.generate().publicKey,
+          authority: creator.publicKey,
+          systemProgram: web3.SystemProgram.programId,
+        })
+        .signers([creator])
+        .rpc();
+    });
+
+    it("registers a keeper and funds the circle's keeper budget", async () => {
+      [keeperKey] = findKeeperPDA(creator.publicKey);
+      if ((await program.account.keeper.fetchNullable(keeperKey)) === null) {
+        await program.methods
+          .registerKeeper()
+          .accounts({
+            keeper: keeperKey,
+            authority: creator.publicKey,
+            systemProgram: web3.SystemProgram.programId,
+          })
+          .signers([creator])
+          .rpc();
+      }
+
+      [keeperBudgetKey] = findKeeperBudgetPDA(autoCircle.circleKey);
+      await program.methods
+        .initializeKeeperBudget(new BN(bounty))
+        .accounts({
+          keeperBudget: keeperBudgetKey,
+          circle: autoCircle.circleKey,
+          creator: creator.publicKey,
+          systemProgram: web3.SystemProgram.programId,
+        })
+        .signers([creator])
+        .rpc();
+
+      await program.methods
+        .fundKeeperBudget(new BN(10 * bounty))
+        .accounts({
+          keeperBudget: keeperBudgetKey,
+          funder: creator.publicKey,
+          systemProgram: web3.SystemProgram.programId,
+        })
+        .signers([creator])
+        .rpc();
+
+      const budget = await program.account.keeperBudget.fetch(keeperBudgetKey);
+      expect(budget.totalFunded.toNumber()).to.equal(10 * bounty);
+    });
+
+    it("approves the escrow for a number of rounds", async () => {
+      const optIns: [web3.Keypair, MemberJoin][] = [
+        [payer, payerJoin],
+        [lapsed, lapsedJoin],
+      ];
+      for (const [kp, join] of optIns) {
+        await program.methods
+          .enableAutoContribution(2)
+          .accounts({
+            circle: autoCircle.circleKey,
+            member: join.memberKey,
+            escrow: autoCircle.escrowKey,
+            memberTokenAccount: join.memberTokenAccount,
+            memberAuthority: kp.publicKey,
+            tokenProgram: TOKEN_PROGRAM_ID,
+          })
+          .signers([kp])
+          .rpc();
+      }
+
+      const tokenAccount = await getAccount(connection, payerJoin.memberTokenAccount);
+      expect(tokenAccount.delegate?.toBase58()).to.equal(autoCircle.escrowKey.toBase58());
+      expect(Number(tokenAccount.delegatedAmount)).to.equal(2_000_000);
+
+      const memberAccount = await program.account.member.fetch(payerJoin.memberKey);
+      expect(memberAccount.autoContributionRounds).to.equal(2);
+    });
+
+    it("rejects a batch that leaves out a member", async () => {
+      const remainingAccounts = [payerJoin, lapsedJoin].flatMap((join) => [
+        { pubkey: join.memberKey, isSigner: false, isWritable: true },
+        { pubkey: join.memberTokenAccount, isSigner: false, isWritable: true },
+        { pubkey: join.trustScoreKey!, isSigner: false, isWritable: true },
+      ]);
+
+      await expectError(
+        program.methods
+          .automatedContributionCollection()
+          .accounts({
+            circleAutomation: circleAutomationKey,
+            circle: autoCircle.circleKey,
+            escrow: autoCircle.escrowKey,
+            escrowTokenAccount: autoCircle.escrowTokenAccount,
+            tokenProgram: TOKEN_PROGRAM_ID,
+            automationLog: findAutomationLogPDA(autoCircle.circleKey)[0],
+            keeper: null,
+            keeperBudget: null,
+            treasury: null,
+            payer: creator.publicKey,
+          })
+          .remainingAccounts(remainingAccounts)
+          .signers([creator])
+          .rpc(),
+        "InvalidMemberAccounts"
+      );
+    });
+
+    it("collects from approved members and records opted-in shortfalls as missed", async () => {
+      // The lapsed member shrinks their allowance below one contribution
+      await approve(
+        connection,
+        lapsed,
+        lapsedJoin.memberTokenAccount,
+        autoCircle.escrowKey,
+        lapsed,
+        1
+      );
+
+      const [automationLogKey] = findAutomationLogPDA(autoCircle.circleKey);
+      const remainingAccounts = [payerJoin, lapsedJoin, manualJoin].flatMap((join) => [
+        { pubkey: join.memberKey, isSigner: false, isWritable: true },
+        { pubkey: join.memberTokenAccount, isSigner: false, isWritable: true },
+        { pubkey: join.trustScoreKey!, isSigner: false, isWritable: true },
+      ]);
+
+      await program.methods
+        .automatedContributionCollection()
+        .accounts({
+          circleAutomation: circleAutomationKey,
+          circle: autoCircle.circleKey,
+          escrow: autoCircle.escrowKey,
+          escrowTokenAccount: autoCircle.escrowTokenAccount,
+          tokenProgram: TOKEN_PROGRAM_ID,
+          automationLog: automationLogKey,
+          keeper: keeperKey,
+          keeperBudget: keeperBudgetKey,
+          treasury: null,
+          payer: creator.publicKey,
+        })
+        .remainingAccounts(remainingAccounts)
+        .signers([creator])
+        .rpc();
+
+      const paid = await program.account.member.fetch(payerJoin.memberKey);
+      expect(paid.contributionHistory[0].toNumber()).to.equal(1_000_000);
+      expect(paid.autoContributionRounds).to.equal(1);
+
+      const missed = await program.account.member.fetch(lapsedJoin.memberKey);
+      expect(missed.contributionHistory[0].toNumber()).to.equal(0);
+      expect(missed.contributionsMissed).to.equal(1);
+
+      // Members who never opted in are left to penalty enforcement
+      const manualMember = await program.account.member.fetch(manualJoin.memberKey);
+      expect(manualMember.contributionsMissed).to.equal(0);
+
+      // Trust scores follow the collection like a manual contribution
+      const paidScore = await program.account.trustScore.fetch(payerJoin.trustScoreKey!);
+      expect(paidScore.totalContributions.toNumber()).to.equal(1_000_000);
+      const missedScore = await program.account.trustScore.fetch(lapsedJoin.trustScoreKey!);
+      expect(missedScore.missedContributions).to.equal(1);
+      expect(missed.trustScore).to.equal(missedScore.score);
+
+      const circleAccount = await program.account.circle.fetch(autoCircle.circleKey);
+      expect(circleAccount.totalPot.toNumber()).to.equal(1_000_000);
+
+      // The keeper is paid once for this contribution slot
+      const keeper = await program.account.keeper.fetch(keeperKey);
+      expect(keeper.successfulCranks.toNumber()).to.be.greaterThan(0);
+      const budget = await program.account.keeperBudget.fetch(keeperBudgetKey);
+      expect(budget.totalPaid.toNumber()).to.equal(bounty);
+
+      // The round is logged as missed because members did not pay
+      const log = await fetchAutomationLog(program, autoCircle.circleKey);
+      expect(log.totalRecords).to.equal(1);
+      const [record] = log.records;
+      expect(JSON.stringify(record.eventType)).to.include("contributionCollection");
+      expect(record.round).to.equal(0);
+      expect(JSON.stringify(record.outcome)).to.include("missed");
+      expect(record.amount.toNumber()).to.equal(1_000_000);
+    });
+
+    it("lets a keeper report a crank for a slot already served", async () => {
+      const before = await program.account.keeper.fetch(keeperKey);
+      await program.methods
+        .reportFailedCrank({ contributionCollection: {} })
+        .accounts({
+          keeper: keeperKey,
+          circleAutomation: circleAutomationKey,
+          authority: creator.publicKey,
+        })
+        .signers([creator])
+        .rpc();
+
+      const keeper = await program.account.keeper.fetch(keeperKey);
+      expect(keeper.failedCranks.toNumber()).to.equal(before.failedCranks.toNumber() + 1);
+    });
+
+    it("returns the keeper budget to the creator on close", async () => {
+      await expectError(
+        program.methods
+          .closeKeeperBudget()
+          .accounts({
+            keeperBudget: keeperBudgetKey,
+            circle: autoCircle.circleKey,
+            creator: payer.publicKey,
+          })
+          .signers([payer])
+          .rpc(),
+        "UnauthorizedMember"
+      );
+
+      const budgetLamports = await connection.getBalance(keeperBudgetKey);
+      const creatorBefore = await connection.getBalance(creator.publicKey);
+      await program.methods
+        .closeKeeperBudget()
+        .accounts({
+          keeperBudget: keeperBudgetKey,
+          circle: autoCircle.circleKey,
+          creator: creator.publicKey,
+        })
+        .signers([creator])
+        .rpc();
+
+      expect(await program.account.keeperBudget.fetchNullable(keeperBudgetKey)).to.be.null;
+      const creatorAfter = await connection.getBalance(creator.publicKey);
+      expect(creatorAfter).to.be.greaterThan(creatorBefore + budgetLamports - 10_000);
+    });
+  });
+
+  // =========================================================================
+  // automated_payout_distribution
+  // =========================================================================
+
+  describe("automated_payout_distribution", () => {
+    let payoutCircle: CircleContext;
+    let circleAutomationKey: web3.PublicKey;
+    let first: web3.Keypair;
+    let second: web3.Keypair;
+    let firstJoin: MemberJoin;
+    let secondJoin: MemberJoin;
+    let treasuryTokenAccount: web3.PublicKey;
+
+    before(async () => {
+      payoutCircle = await initializeCircle(program, creator, mint, {
+        contributionAmount: new BN(1_000_000),
+        durationMonths: 2,
+        maxMembers: 5,
+        penaltyRate: 500,
+      });
+
+      first = web3.Keypair.generate();
+      second = web3.Keypair.generate();
+      await airdropSol(connection, first.publicKey);
+      await airdropSol(connection, second.publicKey);
+      firstJoin = await joinCircle(program, payoutCircle, first, new BN(2_000_000));
+      secondJoin = await joinCircle(program, payoutCircle, second, new BN(2_000_000));
+
+      treasuryTokenAccount = await createTokenAccount(
+        connection,
+        creator,
+        mint,
+        treasuryKey
+      );
+
+      [circleAutomationKey] = findCircleAutomationPDA(payoutCircle.circleKey);
+      await program.methods
+        .setupCircleAutomation(false, true, false, false)
+        .accounts({
+          circleAutomation: circleAutomationKey,
+          automationLog: findAutomationLogPDA(payoutCircle.circleKey)[0],
+          automationState: automationStateKey,
+          circle: payoutCircle.circleKey,
+          switchboardJob: web3.Keypair.generate().publicKey,
+          authority: creator.publicKey,
+          systemProgram: web3.SystemProgram.programId,
+        })
+        .signers([creator])
+        .rpc();
+    });
+
+    function distribute(recipient: web3.PublicKey, join: MemberJoin) {
+      const [automationLogKey] = findAutomationLogPDA(payoutCircle.circleKey);
+      return program.methods
+        .automatedPayoutDistribution(recipient)
+        .accounts({
+          circleAutomation: circleAutomationKey,
+          automationLog: automationLogKey,
+          distributePotAccounts: {
+            circle: payoutCircle.circleKey,
+            recipientMember: join.memberKey,
+            escrow: payoutCircle.escrowKey,
+            treasury: treasuryKey,
+            revenueParams: revenueParamsKey,
+            authority: creator.publicKey,
+            recipientTokenAccount: join.memberTokenAccount,
+            escrowTokenAccount: payoutCircle.escrowTokenAccount,
+            treasuryTokenAccount,
+            tokenProgram: TOKEN_PROGRAM_ID,
+            recipientTrustScore: null,
+          },
+          auction: null,
+          keeper: null,
+          keeperBudget: null,
+          payer: creator.publicKey,
+        })
+        .signers([creator])
+        .rpc();
+    }
+
+    it("rejects a recipient other than the scheduled one", async () => {
+      // Fixed rotation pays members in join order, so month 0 belongs to `first`
+      await expectError(
+        distribute(second.publicKey, secondJoin),
+        "PayoutRecipientMismatch"
+      );
+
+      // Naming the right recipient does not help with someone else's accounts
+      await expectError(
+        distribute(first.publicKey, secondJoin),
+        "PayoutRecipientMismatch"
+      );
+    });
+
+    it("waits for the distribution window for the scheduled recipient", async () => {
+      await expectError(
+        distribute(first.publicKey, firstJoin),
+        "AutomationNotScheduled"
+      );
+    });
+  });
+
+  // =========================================================================
+  // automated_penalty_enforcement
+  // =========================================================================
+
+  describe("automated_penalty_enforcement", () => {
+    it("waits for the penalty window before charging late members", async () => {
+      const penaltyCircle = await initializeCircle(program, creator, mint, {
+        contributionAmount: new BN(1_000_000),
+        durationMonths: 2,
+        maxMembers: 5,
+        penaltyRate: 500,
+      });
+      const late = web3.Keypair.generate();
+      await airdropSol(connection, late.publicKey);
+      const lateJoin = await joinCircle(program, penaltyCircle, late, new BN(2_000_000));
+      const lateTrustScore = await initializeTrustScore(program, late);
+
+      const [circleAutomationKey] = findCircleAutomationPDA(penaltyCircle.circleKey);
+      await program.methods
+        .setupCircleAutomation(false, false, true, false)
+        .accounts({
+          circleAutomation: circleAutomationKey,
+          automationLog: findAutomationLogPDA(penaltyCircle.circleKey)[0],
+          automationState: automationStateKey,
+          circle: penaltyCircle.circleKey,
+          switchboardJob: web3.Keypair.generate().publicKey,
+          authority: creator.publicKey,
+          systemProgram: web3.SystemProgram.programId,
+        })
+        .signers([creator])
+        .rpc();
+
+      const [automationLogKey] = findAutomationLogPDA(penaltyCircle.circleKey);
+      await expectError(
+        program.methods
+          .automatedPenaltyEnforcement()
+          .accounts({
+            circleAutomation: circleAutomationKey,
+            circle: penaltyCircle.circleKey,
+            automationLog: automationLogKey,
+            keeper: null,
+            keeperBudget: null,
+            treasury: null,
+            payer: creator.publicKey,
+          })
+          .remainingAccounts([
+            { pubkey: lateJoin.memberKey, isSigner: false, isWritable: true },
+            { pubkey: lateTrustScore, isSigner: false, isWritable: true },
+          ])
+          .signers([creator])
+          .rpc(),
+        "AutomationNotScheduled"
+      );
+
+      const memberAccount = await program.account.member.fetch(lateJoin.memberKey);
+      expect(memberAccount.penalties.toNumber()).to.equal(0);
+    });
+  });
+
+  // =========================================================================
+  // schedule sync and catch-up
+  // =========================================================================
+
+  describe("sync_automation_schedule", () => {
+    let syncCircle: CircleContext;
+    let circleAutomationKey: web3.PublicKey;
+
+    before(async () => {
+      syncCircle = await initializeCircle(program, creator, mint, {
+        contributionAmount: new BN(1_000_000),
+        durationMonths: 2,
+        maxMembers: 5,
+        penaltyRate: 500,
+      });
+
+      [circleAutomationKey] = findCircleAutomationPDA(syncCircle.circleKey);
+      await program.methods
+        .setupCircleAutomation(true, false, false, true)
+        .accounts({
+          circleAutomation: circleAutomationKey,
+          automationLog: findAutomationLogPDA(syncCircle.circleKey)[0],
+          automationState: automationStateKey,
+          circle: syncCircle.circleKey,
+          switchboardJob: web3.Keypair.generate().publicKey,
+          authority: creator.publicKey,
+          systemProgram: web3.SystemProgram.programId,
+        })
+        .signers([creator])
+        .rpc();
+    });
+
+    function collect() {
+      const [automationLogKey] = findAutomationLogPDA(syncCircle.circleKey);
+      return program.methods
+        .automatedContributionCollection()
+        .accounts({
+          circleAutomation: circleAutomationKey,
+          circle: syncCircle.circleKey,
+          escrow: syncCircle.escrowKey,
+          escrowTokenAccount: syncCircle.escrowTokenAccount,
+          tokenProgram: TOKEN_PROGRAM_ID,
+          automationLog: automationLogKey,
+          keeper: null,
+          keeperBudget: null,
+          treasury: null,
+          payer: creator.publicKey,
+        })
+        .signers([creator])
+        .rpc();
+    }
+
+    it("leaves schedules alone while the circle's timing is unchanged", async () => {
+      const before = await program.account.circleAutomation.fetch(circleAutomationKey);
+      expect(before.catchUpEnabled).to.be.true;
+      expect(before.contributionSchedule.length).to.equal(2);
+
+      await program.methods
+        .syncAutomationSchedule()
+        .accounts({
+          circleAutomation: circleAutomationKey,
+          circle: syncCircle.circleKey,
+        })
+        .rpc();
+
+      const after = await program.account.circleAutomation.fetch(circleAutomationKey);
+      expect(after.pausedAt.toNumber()).to.equal(0);
+      expect(after.pausedSeconds.toNumber()).to.equal(0);
+      expect(after.contributionSchedule.map((t: BN) => t.toNumber())).to.deep.equal(
+        before.contributionSchedule.map((t: BN) => t.toNumber())
+      );
+    });
+
+    it("serves each scheduled round once", async () => {
+      await collect();
+
+      // The check marker lands on the round served, not the crank time
+      const automation = await program.account.circleAutomation.fetch(circleAutomationKey);
+      expect(automation.lastContributionCheck.toNumber()).to.equal(
+        automation.contributionSchedule[0].toNumber()
+      );
+
+      // The next round is a month away
+      await expectError(collect(), "AutomationNotScheduled");
+
+      const log = await fetchAutomationLog(program, syncCircle.circleKey);
+      expect(log.records.map((record) => record.round)).to.deep.equal([0]);
+      expect(JSON.stringify(log.records[0].outcome)).to.include("completed");
+    });
+  });
+
+  // =========================================================================
+  // update_circle_automation / teardown_circle_automation
+  // =========================================================================
+
+  describe("update and teardown", () => {
+    let managedCircle: CircleContext;
+    let circleAutomationKey: web3.PublicKey;
+    let automationLogKey: web3.PublicKey;
+
+    async function activeJobs(): Promise<number> {
+      const state = await program.account.automationState.fetch(automationStateKey);
+      return state.activeJobs;
+    }
+
+    function update(authority: web3.Keypair, autoCollect: boolean | null) {
+      return program.methods
+        .updateCircleAutomation(autoCollect, null, null, null)
+        .accounts({
+          circleAutomation: circleAutomationKey,
+          automationState: automationStateKey,
+          circle: managedCircle.circleKey,
+          switchboardJob: null,
+          authority: authority.publicKey,
+        })
+        .signers([authority])
+        .rpc();
+    }
+
+    before(async () => {
+      managedCircle = await initializeCircle(program, creator, mint, {
+        contributionAmount: new BN(1_000_000),
+        durationMonths: 2,
+        maxMembers: 5,
+        penaltyRate: 500,
+      });
+      [circleAutomationKey] = findCircleAutomationPDA(managedCircle.circleKey);
+      [automationLogKey] = findAutomationLogPDA(managedCircle.circleKey);
+    });
+
+    it("counts a circle with automated tasks as an active job", async () => {
+      const before = await activeJobs();
+      await program.methods
+        .setupCircleAutomation(true, false, false, false)
+        .accounts({
+          circleAutomation: circleAutomationKey,
+          automationLog: automationLogKey,
+          automationState: automationStateKey,
+          circle: managedCircle.circleKey,
+          switchboardJob: web3.Keypair.generate().publicKey,
+          authority: creator.publicKey,
+          systemProgram: web3.SystemProgram.programId,
+        })
+        .signers([creator])
+        .rpc();
+      expect(await activeJobs()).to.equal(before + 1);
+    });
+
+    it("only lets the creator or automation authority change it", async () => {
+      const stranger = web3.Keypair.generate();
+      await airdropSol(connection, stranger.publicKey);
+      await expectError(update(stranger, false), "UnauthorizedAutomationChange");
+    });
+
+    it("stops counting the job once every task is off", async () => {
+      const before = await activeJobs();
+      await update(creator, false);
+
+      const automation = await program.account.circleAutomation.fetch(circleAutomationKey);
+      expect(automation.autoCollectEnabled).to.be.false;
+      expect(await activeJobs()).to.equal(before - 1);
+
+      // Turning it back on counts it again
+      await update(creator, true);
+      expect(await activeJobs()).to.equal(before);
+    });
+
+    it("closes the config and log on teardown", async () => {
+      const before = await activeJobs();
+      await program.methods
+        .teardownCircleAutomation()
+        .accounts({
+          circleAutomation: circleAutomationKey,
+          automationLog: automationLogKey,
+          automationState: automationStateKey,
+          circle: managedCircle.circleKey,
+          authority: creator.publicKey,
+        })
+        .signers([creator])
+        .rpc();
+
+      expect(await activeJobs()).to.equal(before - 1);
+      expect(await program.account.circleAutomation.fetchNullable(circleAutomationKey)).to.be.null;
+      expect(await program.account.automationLog.fetchNullable(automationLogKey)).to.be.null;
+    });
+  });
+
+  // =========================================================================
+  // switchboard_automation_callback
+  // =========================================================================
+
+  describe("switchboard_automation_callback", () => {
+    const mockOracle = anchor.workspace.MockOracle as Program<any>;
+    const contributionAmount = new BN(1_000_000);
+    let enclave: web3.Keypair;
+    let queue: web3.Keypair;
+    let functionAccount: web3.Keypair;
+    let oracleCircle: CircleContext;
+    let circleAutomationKey: web3.PublicKey;
+    let payer: web3.Keypair;
+    let payerJoin: MemberJoin;
+
+    before(async () => {
+      enclave = web3.Keypair.generate();
+      queue = web3.Keypair.generate();
+      functionAccount = web3.Keypair.generate();
+
+      await mockOracle.methods
+        .initializeQueue(enclave.publicKey)
+        .accounts({
+          queue: queue.publicKey,
+          authority: creator.publicKey,
+          systemProgram: web3.SystemProgram.programId,
+        })
+        .signers([creator, queue])
+        .rpc();
+      await mockOracle.methods
+        .initializeFunction()
+        .accounts({
+          function: functionAccount.publicKey,
+          queue: queue.publicKey,
+          authority: creator.publicKey,
+          systemProgram: web3.SystemProgram.programId,
+        })
+        .signers([creator, functionAccount])
+        .rpc();
+
+      await program.methods
+        .configureAutomationOracle(mockOracle.programId, enclave.publicKey)
+        .accounts({
+          automationState: automationStateKey,
+          switchboardQueue: queue.publicKey,
+          authority: creator.publicKey,
+        })
+        .signers([creator])
+        .rpc();
+
+      oracleCircle = await initializeCircle(program, creator, mint, {
+        contributionAmount,
+        durationMonths: 3,
+        maxMembers: 5,
+        penaltyRate: 500,
+      });
+      payer = web3.Keypair.generate();
+      await airdropSol(connection, payer.publicKey);
+      payerJoin = await joinCircle(program, oracleCircle, payer, new BN(2_000_000));
+      payerJoin.trustScoreKey = await initializeTrustScore(program, payer);
+      await program.methods
+        .enableAutoContribution(1)
+        .accounts({
+          circle: oracleCircle.circleKey,
+          member: payerJoin.memberKey,
+          escrow: oracleCircle.escrowKey,
+          memberTokenAccount: payerJoin.memberTokenAccount,
+          memberAuthority: payer.publicKey,
+          tokenProgram: TOKEN_PROGRAM_ID,
+        })
+        .signers([payer])
+        .rpc();
+
+      [circleAutomationKey] = findCircleAutomationPDA(oracleCircle.circleKey);
+      await program.methods
+        .setupCircleAutomation(true, false, false, false)
+        .accounts({
+          circleAutomation: circleAutomationKey,
+          automationLog: findAutomationLogPDA(oracleCircle.circleKey)[0],
+          automationState: automationStateKey,
+          circle: oracleCircle.circleKey,
+          switchboardJob: functionAccount.publicKey,
+          authority: creator.publicKey,
+          systemProgram: web3.SystemProgram.programId,
+        })
+        .signers([creator])
+        .rpc();
+    });
+
+    async function clusterTime(): Promise<BN> {
+      const blockTime = await connection.getBlockTime(await connection.getSlot());
+      return new BN(blockTime ?? Math.floor(Date.now() / 1000));
+    }
+
+    /** Send a signed collection callback; the task's accounts ride along as remaining accounts */
+    async function collectViaCallback(
+      signer: web3.Keypair,
+      issuedAt: BN,
+      functionKey: web3.PublicKey
+    ) {
+      const [automationLogKey] = findAutomationLogPDA(oracleCircle.circleKey);
+      const taskIx = await program.methods
+        .automatedContributionCollection()
+        .accounts({
+          circleAutomation: circleAutomationKey,
+          circle: oracleCircle.circleKey,
+          escrow: oracleCircle.escrowKey,
+          escrowTokenAccount: oracleCircle.escrowTokenAccount,
+          tokenProgram: TOKEN_PROGRAM_ID,
+          automationLog: automationLogKey,
+          keeper: null,
+          keeperBudget: null,
+          treasury: null,
+          payer: creator.publicKey,
+        })
+        .remainingAccounts([
+          { pubkey: payerJoin.memberKey, isSigner: false, isWritable: true },
+          { pubkey: payerJoin.memberTokenAccount, isSigner: false, isWritable: true },
+          { pubkey: payerJoin.trustScoreKey!, isSigner: false, isWritable: true },
+        ])
+        .instruction();
+
+      const callbackIx = buildAutomationCallbackInstruction(
+        signer,
+        oracleCircle.circleKey,
+        0,
+        web3.PublicKey.default,
+        issuedAt
+      );
+      return program.methods
+        .switchboardAutomationCallback()
+        .accounts({
+          automationState: automationStateKey,
+          switchboardQueue: queue.publicKey,
+          switchboardFunction: functionKey,
+          instructionsSysvar: web3.SYSVAR_INSTRUCTIONS_PUBKEY,
+          haloProgram: program.programId,
+        })
+        .remainingAccounts(taskIx.keys)
+        .preInstructions([callbackIx])
+        .signers([creator])
+        .rpc();
+    }
+
+    it("leaves an up-to-date automation state alone on migration", async () => {
+      const before = await program.account.automationState.fetch(automationStateKey);
+      await program.methods
+        .migrateAutomationState()
+        .accounts({
+          automationState: automationStateKey,
+          payer: creator.publicKey,
+          systemProgram: web3.SystemProgram.programId,
+        })
+        .signers([creator])
+        .rpc();
+
+      const after = await program.account.automationState.fetch(automationStateKey);
+      expect(after.oracleProgram.toBase58()).to.equal(mockOracle.programId.toBase58());
+      expect(after.oracleSigner.toBase58()).to.equal(before.oracleSigner.toBase58());
+      expect(after.minInterval.toNumber()).to.equal(before.minInterval.toNumber());
+    });
+
+    it("rejects callbacks not signed by the queue's enclave", async () => {
+      await expectError(
+        collectViaCallback(web3.Keypair.generate(), await clusterTime(), functionAccount.publicKey),
+        "InvalidOracleCallback"
+      );
+    });
+
+    it("rejects function accounts the oracle program does not own", async () => {
+      await expectError(
+        collectViaCallback(enclave, await clusterTime(), web3.Keypair.generate().publicKey),
+        "InvalidOracleAccount"
+      );
+    });
+
+    it("rejects function accounts on another queue", async () => {
+      const otherQueue = web3.Keypair.generate();
+      const otherFunction = web3.Keypair.generate();
+      await mockOracle.methods
+        .initializeQueue(enclave.publicKey)
+        .accounts({
+          queue: otherQueue.publicKey,
+          authority: creator.publicKey,
+          systemProgram: web3.SystemProgram.programId,
+        })
+        .signers([creator, otherQueue])
+        .rpc();
+      await mockOracle.methods
+        .initializeFunction()
+        .accounts({
+          function: otherFunction.publicKey,
+          queue: otherQueue.publicKey,
+          authority: creator.publicKey,
+          systemProgram: web3.SystemProgram.programId,
+        })
+        .signers([creator, otherFunction])
+        .rpc();
+
+      await expectError(
+        collectViaCallback(enclave, await clusterTime(), otherFunction.publicKey),
+        "InvalidOracleAccount"
+      );
+    });
+
+    it("dispatches the signed task and rejects its replay", async () => {
+      const issuedAt = await clusterTime();
+      await collectViaCallback(enclave, issuedAt, functionAccount.publicKey);
+
+      const memberAccount = await program.account.member.fetch(payerJoin.memberKey);
+      expect(memberAccount.contributionHistory[0].toNumber()).to.equal(
+        contributionAmount.toNumber()
+      );
+      const automation = await program.account.circleAutomation.fetch(circleAutomationKey);
+      expect(automation.lastCallbackAt.toNumber()).to.equal(issuedAt.toNumber());
+
+      await expectError(
+        collectViaCallback(enclave, issuedAt, functionAccount.publicKey),
+        "StaleOracleCallback"
+      );
+    });
+  });
+});
@@ -12,6 +12,11 @@ import {
   findEscrowPDA,
   findMemberPDA,
   findTrustScorePDA,
+  findVouchPDA,
+  findEncryptedTrustScorePDA,
+  findPrivacyConfigPDA,
+  buildTierClaimInstruction,
+  findTrustDisputePDA,
   findTreasuryPDA,
   findRevenueParamsPDA,
   initializeCircle,
@@ -102,6 +107,8 @@ describe("halo-protocol: circle lifecycle", () => {
       expect(circleAccount.totalPot.toNumber()).to.equal(0);
       expect(circleAccount.members).to.have.lengthOf(0);
       expect(circleAccount.isPublic).to.be.true;
+      expect(circleAccount.pausedAt.toNumber()).to.equal(0);
+      expect(circleAccount.pausedSeconds.toNumber()).to.equal(0);
 
       // Verify escrow was initialized
       const escrowAccount = await program.account.circleEscrow.fetch(
@@ -350,6 +357,10 @@ describe("halo-protocol: circle lifecycle", () => {
             escrow: freshCircle.escrowKey,
             memberAuthority: member3.publicKey,
             trustScore: null,
+            vouch: null,
+            encryptedTrustScore: null,
+            privacyConfig: null,
+            instructionsSysvar: null,
             memberTokenAccount: memberTokenAccount,
             escrowTokenAccount: freshCircle.escrowTokenAccount,
             systemProgram: web3.SystemProgram.programId,
@@ -403,6 +414,10 @@ describe("halo-protocol: circle lifecycle", () => {
             escrow: stakeCircle.escrowKey,
             memberAuthority: poorMember.publicKey,
             trustScore: null,
+            vouch: null,
+            encryptedTrustScore: null,
+            privacyConfig: null,
+            instructionsSysvar: null,
             memberTokenAccount: memberTokenAccount,
             escrowTokenAccount: stakeCircle.escrowTokenAccount,
             systemProgram: web3.SystemProgram.programId,
@@ -457,6 +472,10 @@ describe("halo-protocol: circle lifecycle", () => {
             escrow: dupeCircle.escrowKey,
             memberAuthority: dupeUser.publicKey,
             trustScore: null,
+            vouch: null,
+            encryptedTrustScore: null,
+            privacyConfig: null,
+            instructionsSysvar: null,
             memberTokenAccount: memberTokenAccount2,
             escrowTokenAccount: dupeCircle.escrowTokenAccount,
             systemProgram: web3.SystemProgram.programId,
@@ -468,6 +487,95 @@ describe("halo-protocol: circle lifecycle", () => {
         "already in use"
       );
     });
+
+    it("joins with an attested tier claim instead of a plaintext trust score", async () => {
+      // Local keypair standing in for the attestor service
+      const attestor = web3.Keypair.generate();
+      const [privacyConfigKey] = findPrivacyConfigPDA();
+      await program.methods
+        .initializePrivacyConfig(attestor.publicKey)
+        .accounts({
+          privacyConfig: privacyConfigKey,
+          treasury: treasuryKey,
+          authority: creator.publicKey,
+          systemProgram: web3.SystemProgram.programId,
+        })
+        .signers([creator])
+        .rpc();
+
+      const privateCircle = await initializeCircle(program, creator, mint, {
+        contributionAmount: new BN(1_000_000),
+        durationMonths: 3,
+        maxMembers: 5,
+        penaltyRate: 500,
+      });
+
+      const privateMember = web3.Keypair.generate();
+      await airdropSol(connection, privateMember.publicKey);
+
+      const encryptedScore = Buffer.from("ciphertext-from-mpc");
+      const [encryptedTrustScoreKey] = findEncryptedTrustScorePDA(
+        privateMember.publicKey
+      );
+      await program.methods
+        .enablePrivateTrustScore(encryptedScore, web3.Keypair.generate().publicKey)
+        .accounts({
+          encryptedTrustScore: encryptedTrustScoreKey,
+          authority: privateMember.publicKey,
+          systemProgram: web3.SystemProgram.programId,
+        })
+        .signers([privateMember])
+        .rpc();
+
+      const memberTokenAccount = await createTokenAccount(
+        connection,
+        creator,
+        mint,
+        privateMember.publicKey
+      );
+      await mintTokens(connection, creator, mint, memberTokenAccount, 2_000_000);
+
+      // Gold tier claim -> 1x stake instead of the 2x newcomer stake
+      const slot = await connection.getSlot();
+      const now = await connection.getBlockTime(slot);
+      const claimIx = buildTierClaimInstruction(
+        attestor,
+        privateMember.publicKey,
+        encryptedScore,
+        2,
+        new BN(now! + 3600)
+      );
+
+      const [memberKey] = findMemberPDA(
+        privateCircle.circleKey,
+        privateMember.publicKey
+      );
+      await program.methods
+        .joinCircle(new BN(1_000_000))
+        .accounts({
+          circle: privateCircle.circleKey,
+          member: memberKey,
+          escrow: privateCircle.escrowKey,
+          memberAuthority: privateMember.publicKey,
+          trustScore: null,
+          vouch: null,
+          encryptedTrustScore: encryptedTrustScoreKey,
+          privacyConfig: privacyConfigKey,
+          instructionsSysvar: web3.SYSVAR_INSTRUCTIONS_PUBKEY,
+          memberTokenAccount,
+          escrowTokenAccount: privateCircle.escrowTokenAccount,
+          systemProgram: web3.SystemProgram.programId,
+          tokenProgram: TOKEN_PROGRAM_ID,
+        })
+        .preInstructions([claimIx])
+        .signers([privateMember])
+        .rpc();
+
+      const memberAccount = await program.account.member.fetch(memberKey);
+      expect(JSON.stringify(memberAccount.trustTier)).to.include("gold");
+      expect(memberAccount.trustScore).to.equal(0);
+      expect(memberAccount.stakeAmount.toNumber()).to.equal(1_000_000);
+    });
   });
 
   // =========================================================================
@@ -578,11 +686,74 @@ describe("halo-protocol: circle lifecycle", () => {
     });
   });
 
+  // =========================================================================
+  // vouch_for_newcomer
+  // =========================================================================
+
+  describe("vouch_for_newcomer", () => {
+    it("fails when the voucher is below Gold tier", async () => {
+      const vouchCircle = await initializeCircle(program, creator, mint, {
+        contributionAmount: new BN(1_000_000),
+        durationMonths: 3,
+        maxMembers: 5,
+        penaltyRate: 500,
+      });
+
+      const voucher = web3.Keypair.generate();
+      await airdropSol(connection, voucher.publicKey);
+
+      const [voucherTrustScore] = findTrustScorePDA(voucher.publicKey);
+      await program.methods
+        .initializeTrustScore()
+        .accounts({
+          trustScore: voucherTrustScore,
+          authority: voucher.publicKey,
+          systemProgram: web3.SystemProgram.programId,
+        })
+        .signers([voucher])
+        .rpc();
+
+      const joinResult = await joinCircle(
+        program,
+        vouchCircle,
+        voucher,
+        new BN(2_000_000),
+        voucherTrustScore
+      );
+
+      const newcomer = web3.Keypair.generate();
+      const [vouchKey] = findVouchPDA(
+        vouchCircle.circleKey,
+        newcomer.publicKey,
+        voucher.publicKey
+      );
+
+      await expectError(
+        program.methods
+          .vouchForNewcomer(newcomer.publicKey, new BN(500_000))
+          .accounts({
+            vouch: vouchKey,
+            circle: vouchCircle.circleKey,
+            voucherMember: joinResult.memberKey,
+            voucherTrustScore,
+            voucher: voucher.publicKey,
+            systemProgram: web3.SystemProgram.programId,
+          })
+          .signers([voucher])
+          .rpc(),
+        "VoucherTierTooLow"
+      );
+    });
+  });
+
   // =========================================================================
   // leave_circle
   // =========================================================================
 
   describe("leave_circle", () => {
+    let exitedCircle: CircleContext;
+    let exitedMember: web3.Keypair;
+
     it("member exits and stake is returned (month 0)", async () => {
       // Create a circle and join
       const leaveCircle = await initializeCircle(program, creator, mint, {
@@ -595,12 +766,25 @@ describe("halo-protocol: circle lifecycle", () => {
       const leavingMember = web3.Keypair.generate();
       await airdropSol(connection, leavingMember.publicKey);
 
+      // The trust score is mandatory so the early exit is always recorded
+      const [trustScoreKey] = findTrustScorePDA(leavingMember.publicKey);
+      await program.methods
+        .initializeTrustScore()
+        .accounts({
+          trustScore: trustScoreKey,
+          authority: leavingMember.publicKey,
+          systemProgram: web3.SystemProgram.programId,
+        })
+        .signers([leavingMember])
+        .rpc();
+
       const stakeAmount = new BN(2_000_000);
       const joinResult = await joinCircle(
         program,
         leaveCircle,
         leavingMember,
-        stakeAmount
+        stakeAmount,
+        trustScoreKey
       );
 
       // Leave immediately (month 0 -- allowed)
@@ -610,6 +794,7 @@ describe("halo-protocol: circle lifecycle", () => {
           circle: leaveCircle.circleKey,
           member: joinResult.memberKey,
           escrow: leaveCircle.escrowKey,
+          trustScore: trustScoreKey,
           memberAuthority: leavingMember.publicKey,
           memberTokenAccount: joinResult.memberTokenAccount,
           escrowTokenAccount: leaveCircle.escrowTokenAccount,
@@ -624,6 +809,16 @@ describe("halo-protocol: circle lifecycle", () => {
       );
       expect(JSON.stringify(memberAccount.status)).to.include("exited");
 
+      // Verify the early exit was recorded against the trust score
+      const trustScoreAccount = await program.account.trustScore.fetch(
+        trustScoreKey
+      );
+      expect(trustScoreAccount.earlyExits).to.equal(1);
+      expect(trustScoreAccount.unpaidPenalties).to.equal(0);
+
+      exitedCircle = leaveCircle;
+      exitedMember = leavingMember;
+
       // Verify circle membership updated
       const circleAccount = await program.account.circle.fetch(
         leaveCircle.circleKey
@@ -637,6 +832,66 @@ describe("halo-protocol: circle lifecycle", () => {
       );
       expect(escrowAccount.totalAmount.toNumber()).to.equal(0);
     });
+    it("files a dispute against the recorded early exit", async () => {
+      // The early exit is the first penalty recorded for this member
+      const [disputeKey] = findTrustDisputePDA(exitedMember.publicKey, 0);
+      const [memberKey] = findMemberPDA(
+        exitedCircle.circleKey,
+        exitedMember.publicKey
+      );
+      const [trustScoreKey] = findTrustScorePDA(exitedMember.publicKey);
+      const evidenceHash = Array.from(Buffer.alloc(32, 7));
+
+      await program.methods
+        .fileTrustDispute(0, [evidenceHash])
+        .accounts({
+          dispute: disputeKey,
+          circle: exitedCircle.circleKey,
+          member: memberKey,
+          trustScore: trustScoreKey,
+          disputant: exitedMember.publicKey,
+          systemProgram: web3.SystemProgram.programId,
+        })
+        .signers([exitedMember])
+        .rpc();
+
+      const disputeAccount = await program.account.trustDispute.fetch(
+        disputeKey
+      );
+      expect(disputeAccount.disputant.toBase58()).to.equal(
+        exitedMember.publicKey.toBase58()
+      );
+      expect(JSON.stringify(disputeAccount.kind)).to.include("earlyExit");
+      expect(disputeAccount.penaltySequence).to.equal(0);
+      expect(JSON.stringify(disputeAccount.status)).to.include("open");
+      expect(disputeAccount.evidenceHashes).to.have.lengthOf(1);
+      expect(disputeAccount.votesFor).to.equal(0);
+    });
+
+    it("fails to dispute a penalty that was never recorded", async () => {
+      const [disputeKey] = findTrustDisputePDA(exitedMember.publicKey, 5);
+      const [memberKey] = findMemberPDA(
+        exitedCircle.circleKey,
+        exitedMember.publicKey
+      );
+      const [trustScoreKey] = findTrustScorePDA(exitedMember.publicKey);
+
+      await expectError(
+        program.methods
+          .fileTrustDispute(5, [Array.from(Buffer.alloc(32, 1))])
+          .accounts({
+            dispute: disputeKey,
+            circle: exitedCircle.circleKey,
+            member: memberKey,
+            trustScore: trustScoreKey,
+            disputant: exitedMember.publicKey,
+            systemProgram: web3.SystemProgram.programId,
+          })
+          .signers([exitedMember])
+          .rpc(),
+        "NothingToDispute"
+      );
+    });
   });
 
   // =========================================================================
@@ -706,6 +961,28 @@ describe("halo-protocol: circle lifecycle", () => {
       // Create recipient token account for member1
       const recipientTokenAccount = join1.memberTokenAccount;
 
+      // Fees can only be sent to a token account the treasury owns
+      await expectError(
+        program.methods
+          .distributePot()
+          .accounts({
+            circle: distCircle.circleKey,
+            recipientMember: join1.memberKey,
+            escrow: distCircle.escrowKey,
+            treasury: treasuryKey,
+            revenueParams: revenueParamsKey,
+            authority: creator.publicKey,
+            recipientTokenAccount: recipientTokenAccount,
+            escrowTokenAccount: distCircle.escrowTokenAccount,
+            treasuryTokenAccount: join2.memberTokenAccount,
+            tokenProgram: TOKEN_PROGRAM_ID,
+            recipientTrustScore: null,
+          })
+          .signers([creator])
+          .rpc(),
+        "InvalidTreasuryTokenAccount"
+      );
+
       // Distribute pot to member1
       await program.methods
         .distributePot()
@@ -720,6 +997,7 @@ describe("halo-protocol: circle lifecycle", () => {
           escrowTokenAccount: distCircle.escrowTokenAccount,
           treasuryTokenAccount: treasuryTokenAccount,
           tokenProgram: TOKEN_PROGRAM_ID,
+          recipientTrustScore: null,
         })
         .signers([creator])
         .rpc();
@@ -729,6 +1007,208 @@ describe("halo-protocol: circle lifecycle", () => {
         join1.memberKey
       );
       expect(memberAccount.hasReceivedPot).to.be.true;
+
+      // Pot and fee are recorded in the circle's ledger and this epoch's bucket
+      const circleAccount = await program.account.circle.fetch(
+        distCircle.circleKey
+      );
+      const ledger = circleAccount.feeLedger;
+      expect(ledger.totalDistributed.toNumber()).to.equal(2_000_000);
+      expect(ledger.distributionFees.toNumber()).to.equal(10_000);
+
+      const treasury = await program.account.treasury.fetch(treasuryKey);
+      const epoch = ledger.lastActiveEpoch.toNumber();
+      const bucket = treasury.revenueEpochs.find(
+        (b: any) => b.epoch.toNumber() === epoch
+      );
+      expect(bucket).to.not.be.undefined;
+      expect(bucket.totalDistributions.toNumber()).to.be.at.least(2_000_000);
+      expect(bucket.activeCircles).to.be.at.least(1);
+    });
+
+    it("leaves an up-to-date treasury's history alone on migration", async () => {
+      const before = await program.account.treasury.fetch(treasuryKey);
+      await program.methods
+        .migrateTreasury()
+        .accounts({
+          treasury: treasuryKey,
+          payer: creator.publicKey,
+          systemProgram: web3.SystemProgram.programId,
+        })
+        .signers([creator])
+        .rpc();
+
+      const after = await program.account.treasury.fetch(treasuryKey);
+      expect(after.revenueEpochs).to.have.lengthOf(before.revenueEpochs.length);
+      expect(after.totalFeesCollected.toNumber()).to.equal(
+        before.totalFeesCollected.toNumber()
+      );
+    });
+  });
+
+  // =========================================================================
+  // collect_management_fees
+  // =========================================================================
+
+  describe("collect_management_fees", () => {
+    let feeCircle: CircleContext;
+
+    before(async () => {
+      feeCircle = await initializeCircle(program, creator, mint, {
+        contributionAmount: new BN(1_000_000),
+        durationMonths: 3,
+        maxMembers: 5,
+        penaltyRate: 500,
+      });
+      const member = web3.Keypair.generate();
+      await airdropSol(connection, member.publicKey);
+      await joinCircle(program, feeCircle, member, new BN(2_000_000));
+    });
+
+    it("tracks stake and the fee timer on the circle", async () => {
+      const circleAccount = await program.account.circle.fetch(
+        feeCircle.circleKey
+      );
+      expect(circleAccount.totalStake.toNumber()).to.equal(2_000_000);
+      expect(
+        circleAccount.feeLedger.lastManagementFeeAt.toNumber()
+      ).to.equal(circleAccount.createdAt.toNumber());
+    });
+
+    it("does not re-migrate a circle that already tracks its stake", async () => {
+      await expectError(
+        program.methods
+          .migrateCircleStake()
+          .accounts({
+            treasury: treasuryKey,
+            circle: feeCircle.circleKey,
+          })
+          .rpc(),
+        "CircleAlreadyMigrated"
+      );
+    });
+
+    it("rejects collection before the circle's interval has elapsed", async () => {
+      await expectError(
+        program.methods
+          .collectManagementFees()
+          .accounts({
+            treasury: treasuryKey,
+            revenueParams: revenueParamsKey,
+            circle: feeCircle.circleKey,
+            escrow: feeCircle.escrowKey,
+            escrowTokenAccount: feeCircle.escrowTokenAccount,
+            treasuryTokenAccount: treasuryTokenAccount,
+            tokenProgram: TOKEN_PROGRAM_ID,
+            authority: creator.publicKey,
+          })
+          .signers([creator])
+          .rpc(),
+        "RevenueCollectionTooFrequent"
+      );
+    });
+
+    it("skips circles that are not due in a batch", async () => {
+      await program.methods
+        .collectManagementFeesBatch()
+        .accounts({
+          treasury: treasuryKey,
+          revenueParams: revenueParamsKey,
+          treasuryTokenAccount: treasuryTokenAccount,
+          tokenProgram: TOKEN_PROGRAM_ID,
+          authority: creator.publicKey,
+        })
+        .remainingAccounts([
+          { pubkey: feeCircle.circleKey, isSigner: false, isWritable: true },
+          { pubkey: feeCircle.escrowKey, isSigner: false, isWritable: true },
+          { pubkey: feeCircle.escrowTokenAccount, isSigner: false, isWritable: true },
+        ])
+        .signers([creator])
+        .rpc();
+
+      const circleAccount = await program.account.circle.fetch(
+        feeCircle.circleKey
+      );
+      expect(circleAccount.feeLedger.managementFees.toNumber()).to.equal(0);
+    });
+
+    it("rejects a batch with an incomplete account group", async () => {
+      await expectError(
+        program.methods
+          .collectManagementFeesBatch()
+          .accounts({
+            treasury: treasuryKey,
+            revenueParams: revenueParamsKey,
+            treasuryTokenAccount: treasuryTokenAccount,
+            tokenProgram: TOKEN_PROGRAM_ID,
+            authority: creator.publicKey,
+          })
+          .remainingAccounts([
+            { pubkey: feeCircle.circleKey, isSigner: false, isWritable: true },
+            { pubkey: feeCircle.escrowKey, isSigner: false, isWritable: true },
+          ])
+          .signers([creator])
+          .rpc(),
+        "InvalidManagementFeeBatch"
+      );
+    });
+
+    it("rejects a batch whose token account is not the escrow's", async () => {
+      await expectError(
+        program.methods
+          .collectManagementFeesBatch()
+          .accounts({
+            treasury: treasuryKey,
+            revenueParams: revenueParamsKey,
+            treasuryTokenAccount: treasuryTokenAccount,
+            tokenProgram: TOKEN_PROGRAM_ID,
+            authority: creator.publicKey,
+          })
+          .remainingAccounts([
+            { pubkey: feeCircle.circleKey, isSigner: false, isWritable: true },
+            { pubkey: feeCircle.escrowKey, isSigner: false, isWritable: true },
+            { pubkey: treasuryTokenAccount, isSigner: false, isWritable: true },
+          ])
+          .signers([creator])
+          .rpc(),
+        "InvalidManagementFeeBatch"
+      );
+    });
+
+    it("rejects collection into a token account the escrow does not own", async () => {
+      await expectError(
+        program.methods
+          .collectManagementFees()
+          .accounts({
+            treasury: treasuryKey,
+            revenueParams: revenueParamsKey,
+            circle: feeCircle.circleKey,
+            escrow: feeCircle.escrowKey,
+            escrowTokenAccount: treasuryTokenAccount,
+            treasuryTokenAccount: treasuryTokenAccount,
+            tokenProgram: TOKEN_PROGRAM_ID,
+            authority: creator.publicKey,
+          })
+          .signers([creator])
+          .rpc(),
+        "InvalidEscrowTokenAccount"
+      );
+    });
+  });
+
+  // =========================================================================
+  // Trust tier fee schedule
+  // =========================================================================
+
+  describe("trust tier fee schedule", () => {
+    it("starts with larger discounts for higher tiers", async () => {
+      const params = await program.account.revenueParams.fetch(
+        revenueParamsKey
+      );
+      expect(params.feeSchedule.newcomer.distributionBps).to.equal(0);
+      expect(params.feeSchedule.platinum.distributionBps).to.be.greaterThan(
+        params.feeSchedule.silver.distributionBps
+      );
     });
   });
 });
@@ -13,6 +13,10 @@ import {
   findMemberPDA,
   findProposalPDA,
   findVotePDA,
+  findDelegationPDA,
+  findProtocolRealmPDA,
+  findProtocolVoterPDA,
+  findTrustScorePDA,
   findAuctionPDA,
   findBidPDA,
   initializeCircle,
@@ -36,6 +40,7 @@ describe("halo-protocol: governance and auctions", () => {
   let creator: web3.Keypair;
   let mint: web3.PublicKey;
   let circleCtx: CircleContext;
+  let treasuryKey: web3.PublicKey;
 
   // Members for governance tests
   let member1: web3.Keypair;
@@ -54,7 +59,7 @@ describe("halo-protocol: governance and auctions", () => {
     await airdropSol(connection, creator.publicKey);
     mint = await createTestMint(connection, creator);
 
-    await initializeRevenueAccounts(program, creator);
+    ({ treasuryKey } = await initializeRevenueAccounts(program, creator));
 
     // Create a circle with members for governance tests
     circleCtx = await initializeCircle(program, creator, mint, {
@@ -94,7 +99,7 @@ describe("halo-protocol: governance and auctions", () => {
 
   describe("create_proposal", () => {
     it("creates a proposal successfully", async () => {
-      const [proposalKey] = findProposalPDA(circleCtx.circleKey);
+      const [proposalKey] = findProposalPDA(circleCtx.circleKey, new BN(0));
 
       await program.methods
         .createProposal(
@@ -103,7 +108,7 @@ describe("halo-protocol: governance and auctions", () => {
           0, // InterestRateChange
           48, // 48 hours voting
           new BN(100), // execution threshold
-          300 // new rate: 3%
+          { setPenaltyRate: { rate: 300 } } // new rate: 3%
         )
         .accounts({
           proposal: proposalKey,
@@ -139,10 +144,76 @@ describe("halo-protocol: governance and auctions", () => {
       expect(proposalAccount.totalVotingPower.toNumber()).to.equal(0);
       expect(proposalAccount.executed).to.be.false;
       expect(proposalAccount.executionThreshold.toNumber()).to.equal(100);
-      expect(proposalAccount.newInterestRate).to.equal(300);
+      expect(proposalAccount.action.setPenaltyRate.rate).to.equal(300);
       expect(proposalAccount.votingEnd.toNumber()).to.be.greaterThan(
         proposalAccount.votingStart.toNumber()
       );
+      expect(proposalAccount.id.toNumber()).to.equal(0);
+    });
+
+    it("creates a second concurrent proposal with the next id", async () => {
+      const [proposalKey] = findProposalPDA(circleCtx.circleKey, new BN(1));
+
+      await program.methods
+        .createProposal(
+          "Adjust parameters",
+          "A second proposal while the first is still active",
+          1, // CircleParameter
+          48,
+          new BN(100),
+          { setGracePeriod: { days: 5 } }
+        )
+        .accounts({
+          proposal: proposalKey,
+          circle: circleCtx.circleKey,
+          proposer: member2.publicKey,
+          systemProgram: web3.SystemProgram.programId,
+        })
+        .signers([member2])
+        .rpc();
+
+      const proposalAccount = await program.account.governanceProposal.fetch(
+        proposalKey
+      );
+      expect(proposalAccount.id.toNumber()).to.equal(1);
+
+      const circleAccount = await program.account.circle.fetch(
+        circleCtx.circleKey
+      );
+      expect(circleAccount.proposalCount.toNumber()).to.equal(2);
+    });
+
+    it("lets only the proposer cancel an active proposal", async () => {
+      const [proposalKey] = findProposalPDA(circleCtx.circleKey, new BN(1));
+
+      // The proposer is still a member, so others cannot cancel
+      await expectError(
+        program.methods
+          .cancelProposal()
+          .accounts({
+            proposal: proposalKey,
+            circle: circleCtx.circleKey,
+            authority: member1.publicKey,
+          })
+          .signers([member1])
+          .rpc(),
+        "UnauthorizedMember"
+      );
+
+      await program.methods
+        .cancelProposal()
+        .accounts({
+          proposal: proposalKey,
+          circle: circleCtx.circleKey,
+          authority: member2.publicKey,
+        })
+        .signers([member2])
+        .rpc();
+
+      const proposalAccount = await program.account.governanceProposal.fetch(
+        proposalKey
+      );
+      expect(JSON.stringify(proposalAccount.status)).to.include("cancelled");
     });
 
     it("fails if proposer is not a circle member", async () => {
@@ -157,7 +228,7 @@ describe("halo-protocol: governance and auctions", () => {
       const nonMember = web3.Keypair.generate();
       await airdropSol(connection, nonMember.publicKey);
 
-      const [proposalKey] = findProposalPDA(freshCircle.circleKey);
+      const [proposalKey] = findProposalPDA(freshCircle.circleKey, new BN(0));
 
       await expectError(
         program.methods
@@ -167,7 +238,7 @@ describe("halo-protocol: governance and auctions", () => {
             1, // CircleParameter
             24,
             new BN(100),
-            null // no new interest rate
+            { setGracePeriod: { days: 5 } }
           )
           .accounts({
             proposal: proposalKey,
@@ -192,7 +263,7 @@ describe("halo-protocol: governance and auctions", () => {
       // Add member1 to this circle so they can propose
       await joinCircle(program, freshCircle2, member1, new BN(2_000_000));
 
-      const [proposalKey] = findProposalPDA(freshCircle2.circleKey);
+      const [proposalKey] = findProposalPDA(freshCircle2.circleKey, new BN(0));
 
       await expectError(
         program.methods
@@ -202,7 +273,7 @@ describe("halo-protocol: governance and auctions", () => {
             3, // invalid
             24,
             new BN(100),
-            null
+            { setGracePeriod: { days: 5 } }
           )
           .accounts({
             proposal: proposalKey,
@@ -226,7 +297,7 @@ describe("halo-protocol: governance and auctions", () => {
 
       await joinCircle(program, freshCircle3, member1, new BN(2_000_000));
 
-      const [proposalKey] = findProposalPDA(freshCircle3.circleKey);
+      const [proposalKey] = findProposalPDA(freshCircle3.circleKey, new BN(0));
 
       await expectError(
         program.methods
@@ -236,7 +307,7 @@ describe("halo-protocol: governance and auctions", () => {
             1,
             0, // invalid: 0 hours
             new BN(100),
-            null
+            { setGracePeriod: { days: 5 } }
           )
           .accounts({
             proposal: proposalKey,
@@ -249,6 +320,124 @@ describe("halo-protocol: governance and auctions", () => {
         "InvalidVotingPeriod"
       );
     });
+
+    it("fails when a parameter proposal carries no action", async () => {
+      const freshCircle4 = await initializeCircle(program, creator, mint, {
+        contributionAmount: new BN(1_000_000),
+        durationMonths: 3,
+        maxMembers: 5,
+        penaltyRate: 500,
+      });
+
+      await joinCircle(program, freshCircle4, member1, new BN(2_000_000));
+
+      const [proposalKey] = findProposalPDA(freshCircle4.circleKey, new BN(0));
+
+      await expectError(
+        program.methods
+          .createProposal(
+            "No action",
+            "A rate change without a rate",
+            0, // InterestRateChange
+            24,
+            new BN(100),
+            null
+          )
+          .accounts({
+            proposal: proposalKey,
+            circle: freshCircle4.circleKey,
+            proposer: member1.publicKey,
+            systemProgram: web3.SystemProgram.programId,
+          })
+          .signers([member1])
+          .rpc(),
+        "InvalidProposalAction"
+      );
+    });
+
+    it("creates an emergency pause proposal", async () => {
+      const freshCircle5 = await initializeCircle(program, creator, mint, {
+        contributionAmount: new BN(1_000_000),
+        durationMonths: 3,
+        maxMembers: 5,
+        penaltyRate: 500,
+      });
+
+      await joinCircle(program, freshCircle5, member1, new BN(2_000_000));
+
+      const [proposalKey] = findProposalPDA(freshCircle5.circleKey, new BN(0));
+
+      // Emergency proposals only accept emergency actions
+      await expectError(
+        program.methods
+          .createProposal(
+            "Pause",
+            "Emergency with a parameter action",
+            2, // Emergency
+            24,
+            new BN(100),
+            { setPenaltyRate: { rate: 100 } }
+          )
+          .accounts({
+            proposal: proposalKey,
+            circle: freshCircle5.circleKey,
+            proposer: member1.publicKey,
+            systemProgram: web3.SystemProgram.programId,
+          })
+          .signers([member1])
+          .rpc(),
+        "InvalidProposalAction"
+      );
+
+      await program.methods
+        .createProposal(
+          "Pause",
+          "Suspected exploit, pause the circle",
+          2, // Emergency
+          24,
+          new BN(100),
+          { setEmergencyPause: { paused: true } }
+        )
+        .accounts({
+          proposal: proposalKey,
+          circle: freshCircle5.circleKey,
+          proposer: member1.publicKey,
+          systemProgram: web3.SystemProgram.programId,
+        })
+        .signers([member1])
+        .rpc();
+
+      const proposalAccount = await program.account.governanceProposal.fetch(
+        proposalKey
+      );
+      expect(JSON.stringify(proposalAccount.proposalType)).to.include(
+        "emergency"
+      );
+      expect(proposalAccount.action.setEmergencyPause.paused).to.be.true;
+
+      // Nothing to settle until a wind-down proposal has executed
+      await expectError(
+        program.methods
+          .settleWindDown()
+          .accounts({
+            circle: freshCircle5.circleKey,
+            escrow: freshCircle5.escrowKey,
+            escrowTokenAccount: freshCircle5.escrowTokenAccount,
+            solendCollateralAccount: null,
+            solendReserve: null,
+            solendReserveCollateralMint: null,
+            solendReserveLiquiditySupply: null,
+            solendLendingMarket: null,
+            solendLendingMarketAuthority: null,
+            solendProgram: null,
+            authority: member1.publicKey,
+            tokenProgram: TOKEN_PROGRAM_ID,
+          })
+          .signers([member1])
+          .rpc(),
+        "CircleNotWindingDown"
+      );
+    });
   });
 
   // =========================================================================
@@ -259,7 +448,7 @@ describe("halo-protocol: governance and auctions", () => {
     let voteCircle: CircleContext;
     let proposalKey: web3.PublicKey;
     let voter: web3.Keypair;
-    let voterTokenAccount: web3.PublicKey;
+    let voterMemberKey: web3.PublicKey;
 
     before(async () => {
       voteCircle = await initializeCircle(program, creator, mint, {
@@ -277,10 +466,26 @@ describe("halo-protocol: governance and auctions", () => {
         voter,
         new BN(2_000_000)
       );
-      voterTokenAccount = voterJoin.memberTokenAccount;
+      voterMemberKey = voterJoin.memberKey;
+
+      // Weight votes by stake before the proposal snapshots the source
+      await program.methods
+        .configureCircleGovernance(
+          { stakeWeighted: {} },
+          null,
+          5000, // 50% quorum
+          { quadratic: {} },
+          new BN(24 * 60 * 60) // 1 day timelock
+        )
+        .accounts({
+          circle: voteCircle.circleKey,
+          creator: creator.publicKey,
+        })
+        .signers([creator])
+        .rpc();
 
       // Create proposal
-      [proposalKey] = findProposalPDA(voteCircle.circleKey);
+      [proposalKey] = findProposalPDA(voteCircle.circleKey, new BN(0));
 
       await program.methods
         .createProposal(
@@ -289,7 +494,7 @@ describe("halo-protocol: governance and auctions", () => {
           1, // CircleParameter
           48,
           new BN(50),
-          null
+          { setMinTrustTier: { tier: 1 } }
         )
         .accounts({
           proposal: proposalKey,
@@ -301,17 +506,18 @@ describe("halo-protocol: governance and auctions", () => {
         .rpc();
     });
 
-    it("casts vote with quadratic weight", async () => {
+    it("casts vote with stake-derived power and quadratic weight", async () => {
       const [voteKey] = findVotePDA(proposalKey, voter.publicKey);
-      const votingPower = new BN(100);
 
       await program.methods
-        .castVote(true, votingPower)
+        .castVote(true)
         .accounts({
           proposal: proposalKey,
           vote: voteKey,
           voter: voter.publicKey,
-          voterTokenAccount: voterTokenAccount,
+          voterMember: voterMemberKey,
+          trustScore: null,
+          delegateVote: null,
           systemProgram: web3.SystemProgram.programId,
         })
         .signers([voter])
@@ -325,18 +531,19 @@ describe("halo-protocol: governance and auctions", () => {
       expect(voteAccount.voter.toBase58()).to.equal(
         voter.publicKey.toBase58()
       );
-      expect(voteAccount.votingPower.toNumber()).to.equal(100);
-      // sqrt(100) = 10
-      expect(voteAccount.quadraticWeight.toNumber()).to.equal(10);
+      // Voting power is the member's stake
+      expect(voteAccount.votingPower.toNumber()).to.equal(2_000_000);
+      // floor(sqrt(2_000_000)) = 1414
+      expect(voteAccount.quadraticWeight.toNumber()).to.equal(1414);
       expect(voteAccount.support).to.be.true;
 
       // Verify proposal tallies updated
       const proposalAccount =
         await program.account.governanceProposal.fetch(proposalKey);
-      expect(proposalAccount.votesFor.toNumber()).to.equal(100);
+      expect(proposalAccount.votesFor.toNumber()).to.equal(2_000_000);
       expect(proposalAccount.votesAgainst.toNumber()).to.equal(0);
-      expect(proposalAccount.quadraticVotesFor.toNumber()).to.equal(10);
-      expect(proposalAccount.totalVotingPower.toNumber()).to.equal(100);
+      expect(proposalAccount.quadraticVotesFor.toNumber()).to.equal(1414);
+      expect(proposalAccount.totalVotingPower.toNumber()).to.equal(2_000_000);
     });
 
     it("second voter casts against vote", async () => {
@@ -350,15 +557,16 @@ describe("halo-protocol: governance and auctions", () => {
       );
 
       const [voteKey2] = findVotePDA(proposalKey, voter2.publicKey);
-      const votingPower = new BN(49);
 
       await program.methods
-        .castVote(false, votingPower)
+        .castVote(false)
         .accounts({
           proposal: proposalKey,
           vote: voteKey2,
           voter: voter2.publicKey,
-          voterTokenAccount: voter2Join.memberTokenAccount,
+          voterMember: voter2Join.memberKey,
+          trustScore: null,
+          delegateVote: null,
           systemProgram: web3.SystemProgram.programId,
         })
         .signers([voter2])
@@ -366,18 +574,17 @@ describe("halo-protocol: governance and auctions", () => {
 
       const voteAccount = await program.account.vote.fetch(voteKey2);
       expect(voteAccount.support).to.be.false;
-      expect(voteAccount.votingPower.toNumber()).to.equal(49);
-      // sqrt(49) = 7
-      expect(voteAccount.quadraticWeight.toNumber()).to.equal(7);
+      expect(voteAccount.votingPower.toNumber()).to.equal(2_000_000);
+      expect(voteAccount.quadraticWeight.toNumber()).to.equal(1414);
 
       // Verify proposal tallies
       const proposalAccount =
         await program.account.governanceProposal.fetch(proposalKey);
-      expect(proposalAccount.votesFor.toNumber()).to.equal(100);
-      expect(proposalAccount.votesAgainst.toNumber()).to.equal(49);
-      expect(proposalAccount.quadraticVotesFor.toNumber()).to.equal(10);
-      expect(proposalAccount.quadraticVotesAgainst.toNumber()).to.equal(7);
-      expect(proposalAccount.totalVotingPower.toNumber()).to.equal(149);
+      expect(proposalAccount.votesFor.toNumber()).to.equal(2_000_000);
+      expect(proposalAccount.votesAgainst.toNumber()).to.equal(2_000_000);
+      expect(proposalAccount.quadraticVotesFor.toNumber()).to.equal(1414);
+      expect(proposalAccount.quadraticVotesAgainst.toNumber()).to.equal(1414);
+      expect(proposalAccount.totalVotingPower.toNumber()).to.equal(4_000_000);
     });
 
     it("fails to vote twice (PDA already initialized)", async () => {
@@ -385,12 +592,14 @@ describe("halo-protocol: governance and auctions", () => {
 
       await expectError(
         program.methods
-          .castVote(true, new BN(50))
+          .castVote(true)
           .accounts({
             proposal: proposalKey,
             vote: voteKey,
             voter: voter.publicKey,
-            voterTokenAccount: voterTokenAccount,
+            voterMember: voterMemberKey,
+            trustScore: null,
+            delegateVote: null,
             systemProgram: web3.SystemProgram.programId,
           })
           .signers([voter])
@@ -399,32 +608,185 @@ describe("halo-protocol: governance and auctions", () => {
       );
     });
 
-    it("fails with zero voting power", async () => {
-      const voter3 = web3.Keypair.generate();
-      await airdropSol(connection, voter3.publicKey);
-      const voter3Join = await joinCircle(
-        program,
-        voteCircle,
-        voter3,
-        new BN(2_000_000)
-      );
+    it("fails when the voter is not a circle member", async () => {
+      const outsider = web3.Keypair.generate();
+      await airdropSol(connection, outsider.publicKey);
 
-      const [voteKey3] = findVotePDA(proposalKey, voter3.publicKey);
+      const [voteKey] = findVotePDA(proposalKey, outsider.publicKey);
+      const [outsiderMemberKey] = findMemberPDA(
+        voteCircle.circleKey,
+        outsider.publicKey
+      );
 
       await expectError(
         program.methods
-          .castVote(true, new BN(0))
+          .castVote(true)
           .accounts({
             proposal: proposalKey,
-            vote: voteKey3,
-            voter: voter3.publicKey,
-            voterTokenAccount: voter3Join.memberTokenAccount,
+            vote: voteKey,
+            voter: outsider.publicKey,
+            voterMember: outsiderMemberKey,
+            trustScore: null,
+            delegateVote: null,
             systemProgram: web3.SystemProgram.programId,
           })
-          .signers([voter3])
+          .signers([outsider])
           .rpc(),
-        "InsufficientVotingPower"
+        "AccountNotInitialized"
+      );
+    });
+
+    it("counts delegated power and lets the delegator override it", async () => {
+      const delegator = web3.Keypair.generate();
+      const delegate = web3.Keypair.generate();
+      await airdropSol(connection, delegator.publicKey);
+      await airdropSol(connection, delegate.publicKey);
+      const delegatorJoin = await joinCircle(
+        program,
+        voteCircle,
+        delegator,
+        new BN(2_000_000)
+      );
+      const delegateJoin = await joinCircle(
+        program,
+        voteCircle,
+        delegate,
+        new BN(2_000_000)
+      );
+
+      const [delegationKey] = findDelegationPDA(
+        voteCircle.circleKey,
+        delegator.publicKey
+      );
+      await program.methods
+        .delegateVote(delegate.publicKey)
+        .accounts({
+          delegation: delegationKey,
+          circle: voteCircle.circleKey,
+          delegatorMember: delegatorJoin.memberKey,
+          delegateMember: delegateJoin.memberKey,
+          delegator: delegator.publicKey,
+          systemProgram: web3.SystemProgram.programId,
+        })
+        .signers([delegator])
+        .rpc();
+
+      const before = await program.account.governanceProposal.fetch(
+        proposalKey
+      );
+      const [delegateVoteKey] = findVotePDA(proposalKey, delegate.publicKey);
+      const [delegatorVoteKey] = findVotePDA(proposalKey, delegator.publicKey);
+
+      // The delegate votes with their own and the delegator's stake
+      await program.methods
+        .castVote(true)
+        .accounts({
+          proposal: proposalKey,
+          vote: delegateVoteKey,
+          voter: delegate.publicKey,
+          voterMember: delegateJoin.memberKey,
+          trustScore: null,
+          delegateVote: null,
+          systemProgram: web3.SystemProgram.programId,
+        })
+        .remainingAccounts([
+          { pubkey: delegationKey, isSigner: false, isWritable: false },
+          { pubkey: delegatorJoin.memberKey, isSigner: false, isWritable: false },
+          { pubkey: delegatorVoteKey, isSigner: false, isWritable: false },
+        ])
+        .signers([delegate])
+        .rpc();
+
+      let delegateVote = await program.account.vote.fetch(delegateVoteKey);
+      expect(delegateVote.votingPower.toNumber()).to.equal(4_000_000);
+      // Quadratic weight is per member: 1414 + 1414
+      expect(delegateVote.quadraticWeight.toNumber()).to.equal(2828);
+      expect(delegateVote.delegations.length).to.equal(1);
+
+      let proposalAccount = await program.account.governanceProposal.fetch(
+        proposalKey
       );
+      expect(proposalAccount.voterCount).to.equal(before.voterCount + 2);
+      expect(proposalAccount.votesFor.toNumber()).to.equal(
+        before.votesFor.toNumber() + 4_000_000
+      );
+
+      // The delegator's own vote replaces what the delegate cast for them
+      await program.methods
+        .castVote(false)
+        .accounts({
+          proposal: proposalKey,
+          vote: delegatorVoteKey,
+          voter: delegator.publicKey,
+          voterMember: delegatorJoin.memberKey,
+          trustScore: null,
+          delegateVote: delegateVoteKey,
+          systemProgram: web3.SystemProgram.programId,
+        })
+        .signers([delegator])
+        .rpc();
+
+      delegateVote = await program.account.vote.fetch(delegateVoteKey);
+      expect(delegateVote.votingPower.toNumber()).to.equal(2_000_000);
+      expect(delegateVote.delegations.length).to.equal(0);
+
+      proposalAccount = await program.account.governanceProposal.fetch(
+        proposalKey
+      );
+      expect(proposalAccount.voterCount).to.equal(before.voterCount + 2);
+      expect(proposalAccount.votesFor.toNumber()).to.equal(
+        before.votesFor.toNumber() + 2_000_000
+      );
+      expect(proposalAccount.votesAgainst.toNumber()).to.equal(
+        before.votesAgainst.toNumber() + 2_000_000
+      );
+
+      await program.methods
+        .revokeDelegation()
+        .accounts({
+          delegation: delegationKey,
+          delegator: delegator.publicKey,
+        })
+        .signers([delegator])
+        .rpc();
+      expect(await connection.getAccountInfo(delegationKey)).to.be.null;
+    });
+
+    it("changes a vote and moves its linear and quadratic weight", async () => {
+      const [voteKey] = findVotePDA(proposalKey, voter.publicKey);
+      const before = await program.account.governanceProposal.fetch(
+        proposalKey
+      );
+
+      await program.methods
+        .changeVote(false)
+        .accounts({
+          proposal: proposalKey,
+          vote: voteKey,
+          voter: voter.publicKey,
+        })
+        .signers([voter])
+        .rpc();
+
+      const voteAccount = await program.account.vote.fetch(voteKey);
+      expect(voteAccount.support).to.be.false;
+
+      const after = await program.account.governanceProposal.fetch(
+        proposalKey
+      );
+      expect(after.votesFor.toNumber()).to.equal(
+        before.votesFor.toNumber() - 2_000_000
+      );
+      expect(after.votesAgainst.toNumber()).to.equal(
+        before.votesAgainst.toNumber() + 2_000_000
+      );
+      expect(after.quadraticVotesFor.toNumber()).to.equal(
+        before.quadraticVotesFor.toNumber() - 1414
+      );
+      expect(after.quadraticVotesAgainst.toNumber()).to.equal(
+        before.quadraticVotesAgainst.toNumber() + 1414
+      );
+      expect(after.voterCount).to.equal(before.voterCount);
     });
   });
 
@@ -444,9 +806,14 @@ describe("halo-protocol: governance and auctions", () => {
 
       const execMember = web3.Keypair.generate();
       await airdropSol(connection, execMember.publicKey);
-      await joinCircle(program, execCircle, execMember, new BN(2_000_000));
+      const execJoin = await joinCircle(
+        program,
+        execCircle,
+        execMember,
+        new BN(2_000_000)
+      );
 
-      const [proposalKey] = findProposalPDA(execCircle.circleKey);
+      const [proposalKey] = findProposalPDA(execCircle.circleKey, new BN(0));
 
       await program.methods
         .createProposal(
@@ -455,7 +822,7 @@ describe("halo-protocol: governance and auctions", () => {
           0,
           168, // 7 days
           new BN(10),
-          200
+          { setPenaltyRate: { rate: 200 } }
         )
         .accounts({
           proposal: proposalKey,
@@ -468,45 +835,260 @@ describe("halo-protocol: governance and auctions", () => {
 
       // Vote to meet threshold
       const [voteKey] = findVotePDA(proposalKey, execMember.publicKey);
-      const execMemberToken = await createTokenAccount(
-        connection,
-        creator,
-        mint,
-        execMember.publicKey
-      );
-      await mintTokens(connection, creator, mint, execMemberToken, 1_000_000);
 
       await program.methods
-        .castVote(true, new BN(100))
+        .castVote(true)
         .accounts({
           proposal: proposalKey,
           vote: voteKey,
           voter: execMember.publicKey,
-          voterTokenAccount: execMemberToken,
+          voterMember: execJoin.memberKey,
+          trustScore: null,
+          delegateVote: null,
           systemProgram: web3.SystemProgram.programId,
         })
         .signers([execMember])
         .rpc();
 
-      // Try to execute immediately -- voting period has not ended
-      // Note: execute_proposal checks voting_ended(clock.unix_timestamp)
-      // The error message depends on whether the condition evaluates as
-      // "VotingPeriodEnded" (the require uses this error name even though
-      // semantically it means "voting must have ended to execute").
-      const executor = web3.Keypair.generate();
-      await airdropSol(connection, executor.publicKey);
+      // Voting is still open, so the outcome cannot be finalized yet
+      await expectError(
+        program.methods
+          .finalizeProposal()
+          .accounts({
+            proposal: proposalKey,
+            authority: execMember.publicKey,
+          })
+          .signers([execMember])
+          .rpc(),
+        "VotingPeriodActive"
+      );
 
+      // Execution requires a finalized, succeeded proposal
       await expectError(
         program.methods
           .executeProposal()
           .accounts({
             proposal: proposalKey,
             circle: execCircle.circleKey,
-            executor: executor.publicKey,
+            executorMember: execJoin.memberKey,
+            circleAutomation: null,
+            automationState: null,
+            targetMember: null,
+            escrow: null,
+            escrowTokenAccount: null,
+            targetTokenAccount: null,
+            tokenProgram: null,
+            executor: execMember.publicKey,
           })
-          .signers([executor])
+          .signers([execMember])
           .rpc(),
-        "VotingPeriodEnded"
+        "ProposalNotSucceeded"
+      );
+    });
+
+    it("snapshots quorum, tally mode and timelock on the proposal", async () => {
+      const proposalAccount = await program.account.governanceProposal.fetch(
+        findProposalPDA(circleCtx.circleKey, new BN(0))[0]
+      );
+      expect(proposalAccount.quorumBps).to.equal(5000);
+      expect(JSON.stringify(proposalAccount.tallyMode)).to.include("quadratic");
+      expect(proposalAccount.timelockSeconds.toNumber()).to.equal(24 * 60 * 60);
+      expect(proposalAccount.eligibleVoters).to.equal(2);
+      expect(proposalAccount.executableAt.toNumber()).to.equal(0);
+    });
+  });
+
+  // =========================================================================
+  // protocol realm
+  // =========================================================================
+
+  describe("protocol realm", () => {
+    const [realmKey] = findProtocolRealmPDA();
+
+    before(async () => {
+      await program.methods
+        .initializeProtocolRealm(5000, new BN(0))
+        .accounts({
+          realm: realmKey,
+          treasury: treasuryKey,
+          authority: creator.publicKey,
+          systemProgram: web3.SystemProgram.programId,
+        })
+        .signers([creator])
+        .rpc();
+
+      await program.methods
+        .registerProtocolVoter(member1.publicKey, new BN(10))
+        .accounts({
+          protocolVoter: findProtocolVoterPDA(member1.publicKey)[0],
+          realm: realmKey,
+          authority: creator.publicKey,
+          systemProgram: web3.SystemProgram.programId,
+        })
+        .signers([creator])
+        .rpc();
+    });
+
+    it("creates and votes on a trust-tier threshold proposal", async () => {
+      const [proposalKey] = findProposalPDA(realmKey, new BN(0));
+      const [voterKey] = findProtocolVoterPDA(member1.publicKey);
+
+      // Thresholds must be strictly increasing
+      await expectError(
+        program.methods
+          .createProtocolProposal(
+            "Bad thresholds",
+            "Gold below Silver",
+            { setTrustTierThresholds: { thresholds: { silver: 500, gold: 400, platinum: 700 } } },
+            24
+          )
+          .accounts({
+            proposal: proposalKey,
+            realm: realmKey,
+            proposerVoter: voterKey,
+            proposer: member1.publicKey,
+            systemProgram: web3.SystemProgram.programId,
+          })
+          .signers([member1])
+          .rpc(),
+        "InvalidProposalAction"
+      );
+
+      await program.methods
+        .createProtocolProposal(
+          "Lower tier thresholds",
+          "Make Silver reachable at 200",
+          { setTrustTierThresholds: { thresholds: { silver: 200, gold: 450, platinum: 700 } } },
+          24
+        )
+        .accounts({
+          proposal: proposalKey,
+          realm: realmKey,
+          proposerVoter: voterKey,
+          proposer: member1.publicKey,
+          systemProgram: web3.SystemProgram.programId,
+        })
+        .signers([member1])
+        .rpc();
+
+      await program.methods
+        .castProtocolVote(true)
+        .accounts({
+          proposal: proposalKey,
+          realm: realmKey,
+          vote: findVotePDA(proposalKey, member1.publicKey)[0],
+          protocolVoter: voterKey,
+          voter: member1.publicKey,
+          systemProgram: web3.SystemProgram.programId,
+        })
+        .signers([member1])
+        .rpc();
+
+      const proposalAccount = await program.account.governanceProposal.fetch(
+        proposalKey
+      );
+      expect(JSON.stringify(proposalAccount.proposalType)).to.include(
+        "protocol"
+      );
+      expect(proposalAccount.votesFor.toNumber()).to.equal(10);
+      expect(proposalAccount.eligibleVoters).to.equal(1);
+
+      // Execution waits for finalization after the voting window
+      await expectError(
+        program.methods
+          .executeProtocolProposal()
+          .accounts({
+            proposal: proposalKey,
+            realm: realmKey,
+            revenueParams: null,
+            automationState: null,
+            executorVoter: voterKey,
+            executor: member1.publicKey,
+          })
+          .signers([member1])
+          .rpc(),
+        "ProposalNotSucceeded"
+      );
+    });
+
+    describe("SetFeeSchedule", () => {
+      const discount = (bps: number) => ({
+        distributionBps: bps,
+        managementBps: bps,
+      });
+
+      function proposeSchedule(schedule: any) {
+        const [proposalKey] = findProposalPDA(realmKey, new BN(1));
+        return program.methods
+          .createProtocolProposal(
+            "Fee schedule",
+            "Update trust tier fee discounts",
+            { setFeeSchedule: { schedule } },
+            24
+          )
+          .accounts({
+            proposal: proposalKey,
+            realm: realmKey,
+            proposerVoter: findProtocolVoterPDA(member1.publicKey)[0],
+            proposer: member1.publicKey,
+            systemProgram: web3.SystemProgram.programId,
+          })
+          .signers([member1])
+          .rpc();
+      }
+
+      it("rejects discounts that shrink as tiers rise", async () => {
+        await expectError(
+          proposeSchedule({
+            newcomer: discount(0),
+            silver: discount(3000),
+            gold: discount(2000),
+            platinum: discount(5000),
+          }),
+          "InvalidFeeRate"
+        );
+      });
+
+      it("rejects discounts above 100%", async () => {
+        await expectError(
+          proposeSchedule({
+            newcomer: discount(0),
+            silver: discount(1000),
+            gold: discount(2500),
+            platinum: discount(10_001),
+          }),
+          "InvalidFeeRate"
+        );
+      });
+    });
+
+    it("lets anyone sync the realm's thresholds to a trust score", async () => {
+      const [trustScoreKey] = findTrustScorePDA(member1.publicKey);
+      await program.methods
+        .initializeTrustScore()
+        .accounts({
+          trustScore: trustScoreKey,
+          authority: member1.publicKey,
+          systemProgram: web3.SystemProgram.programId,
+        })
+        .signers([member1])
+        .rpc();
+
+      await program.methods
+        .syncTrustTierThresholds()
+        .accounts({
+          trustScore: trustScoreKey,
+          realm: realmKey,
+        })
+        .rpc();
+
+      const realm = await program.account.protocolRealm.fetch(realmKey);
+      const trustScore = await program.account.trustScore.fetch(trustScoreKey);
+      expect(trustScore.tierThresholds.silver).to.equal(
+        realm.trustTierThresholds.silver
+      );
+      expect(trustScore.tierThresholds.platinum).to.equal(
+        realm.trustTierThresholds.platinum
       );
     });
   });
@@ -7,6 +7,7 @@ import {
   getAccount,
   TOKEN_PROGRAM_ID,
 } from "@solana/spl-token";
+import { createHash } from "crypto";
 
 // Program ID from Anchor.toml
 export const PROGRAM_ID = new web3.PublicKey(
@@ -142,6 +143,112 @@ export function findTrustScorePDA(
   );
 }
 
+/**
+ * Derive the TrustCredential PDA.
+ *
+ * Seeds: [b"trust_credential", authority.key().as_ref()]
+ */
+export function findTrustCredentialPDA(
+  authority: web3.PublicKey
+): [web3.PublicKey, number] {
+  return web3.PublicKey.findProgramAddressSync(
+    [Buffer.from("trust_credential"), authority.toBuffer()],
+    PROGRAM_ID
+  );
+}
+
+/**
+ * Derive the TrustDispute PDA.
+ *
+ * Seeds: [b"trust_dispute", disputant.key().as_ref(), penalty_sequence.to_le_bytes().as_ref()]
+ */
+export function findTrustDisputePDA(
+  disputant: web3.PublicKey,
+  penaltySequence: number
+): [web3.PublicKey, number] {
+  return web3.PublicKey.findProgramAddressSync(
+    [
+      Buffer.from("trust_dispute"),
+      disputant.toBuffer(),
+      new BN(penaltySequence).toArrayLike(Buffer, "le", 4),
+    ],
+    PROGRAM_ID
+  );
+}
+
+/**
+ * Derive the Vouch PDA.
+ *
+ * Seeds: [b"vouch", circle.key().as_ref(), newcomer.as_ref(), voucher.as_ref()]
+ */
+export function findVouchPDA(
+  circle: web3.PublicKey,
+  newcomer: web3.PublicKey,
+  voucher: web3.PublicKey
+): [web3.PublicKey, number] {
+  return web3.PublicKey.findProgramAddressSync(
+    [
+      Buffer.from("vouch"),
+      circle.toBuffer(),
+      newcomer.toBuffer(),
+      voucher.toBuffer(),
+    ],
+    PROGRAM_ID
+  );
+}
+
+/**
+ * Derive the EncryptedTrustScore PDA.
+ *
+ * Seeds: [b"encrypted_trust_score", authority.key().as_ref()]
+ */
+export function findEncryptedTrustScorePDA(
+  authority: web3.PublicKey
+): [web3.PublicKey, number] {
+  return web3.PublicKey.findProgramAddressSync(
+    [Buffer.from("encrypted_trust_score"), authority.toBuffer()],
+    PROGRAM_ID
+  );
+}
+
+/**
+ * Derive the PrivacyConfig PDA.
+ *
+ * Seeds: [b"privacy_config"]
+ */
+export function findPrivacyConfigPDA(): [web3.PublicKey, number] {
+  return web3.PublicKey.findProgramAddressSync(
+    [Buffer.from("privacy_config")],
+    PROGRAM_ID
+  );
+}
+
+/**
+ * Derive the ProtocolRealm PDA.
+ *
+ * Seeds: [b"protocol_realm"]
+ */
+export function findProtocolRealmPDA(): [web3.PublicKey, number] {
+  return web3.PublicKey.findProgramAddressSync(
+    [Buffer.from("protocol_realm")],
+    PROGRAM_ID
+  );
+}
+
+/**
+ * Derive the ProtocolVoter PDA.
+ *
+ * Seeds: [b"protocol_voter", voter.as_ref()]
+ */
+export function findProtocolVoterPDA(
+  voter: web3.PublicKey
+): [web3.PublicKey, number] {
+  return web3.PublicKey.findProgramAddressSync(
+    [Buffer.from("protocol_voter"), voter.toBuffer()],
+    PROGRAM_ID
+  );
+}
+
 /**
  * Derive the InsurancePool PDA.
  *
@@ -183,13 +290,15 @@ export function findRevenueParamsPDA(): [web3.PublicKey, number] {
 /**
  * Derive the GovernanceProposal PDA.
  *
- * Seeds: [b"proposal", circle.key().as_ref()]
+ * Seeds: [b"proposal", circle.key().as_ref(), &id.to_le_bytes()]
+ * where id = circle.proposal_count at creation time.
  */
 export function findProposalPDA(
-  circle: web3.PublicKey
+  circle: web3.PublicKey,
+  id: BN
 ): [web3.PublicKey, number] {
   return web3.PublicKey.findProgramAddressSync(
-    [Buffer.from("proposal"), circle.toBuffer()],
+    [Buffer.from("proposal"), circle.toBuffer(), id.toArrayLike(Buffer, "le", 8)],
     PROGRAM_ID
   );
 }
@@ -209,6 +318,21 @@ export function findVotePDA(
   );
 }
 
+/**
+ * Derive the Delegation PDA.
+ *
+ * Seeds: [b"delegation", circle.key().as_ref(), delegator.key().as_ref()]
+ */
+export function findDelegationPDA(
+  circle: web3.PublicKey,
+  delegator: web3.PublicKey
+): [web3.PublicKey, number] {
+  return web3.PublicKey.findProgramAddressSync(
+    [Buffer.from("delegation"), circle.toBuffer(), delegator.toBuffer()],
+    PROGRAM_ID
+  );
+}
+
 /**
  * Derive the Auction PDA.
  *
@@ -283,6 +407,48 @@ export function findCircleAutomationPDA(
   );
 }
 
+/**
+ * Derive the AutomationLog PDA.
+ *
+ * Seeds: [b"automation_log", circle.key().as_ref()]
+ */
+export function findAutomationLogPDA(
+  circle: web3.PublicKey
+): [web3.PublicKey, number] {
+  return web3.PublicKey.findProgramAddressSync(
+    [Buffer.from("automation_log"), circle.toBuffer()],
+    PROGRAM_ID
+  );
+}
+
+/**
+ * Derive the Keeper PDA.
+ *
+ * Seeds: [b"keeper", authority.key().as_ref()]
+ */
+export function findKeeperPDA(
+  authority: web3.PublicKey
+): [web3.PublicKey, number] {
+  return web3.PublicKey.findProgramAddressSync(
+    [Buffer.from("keeper"), authority.toBuffer()],
+    PROGRAM_ID
+  );
+}
+
+/**
+ * Derive the KeeperBudget PDA.
+ *
+ * Seeds: [b"keeper_budget", circle.key().as_ref()]
+ */
+export function findKeeperBudgetPDA(
+  circle: web3.PublicKey
+): [web3.PublicKey, number] {
+  return web3.PublicKey.findProgramAddressSync(
+    [Buffer.from("keeper_budget"), circle.toBuffer()],
+    PROGRAM_ID
+  );
+}
+
 // ---------------------------------------------------------------------------
 // High-level test helpers
 // ---------------------------------------------------------------------------
@@ -429,6 +595,10 @@ export async function joinCircle(
       escrow: circleCtx.escrowKey,
       memberAuthority: memberAuthority.publicKey,
       trustScore: trustScoreKey ?? null,
+      vouch: null,
+      encryptedTrustScore: null,
+      privacyConfig: null,
+      instructionsSysvar: null,
       memberTokenAccount: memberTokenAccount,
       escrowTokenAccount: circleCtx.escrowTokenAccount,
       systemProgram: web3.SystemProgram.programId,
@@ -440,6 +610,79 @@ export async function joinCircle(
   return { memberKey, memberBump, memberTokenAccount };
 }
 
+/**
+ * Build the ed25519 instruction carrying an attested trust tier claim.
+ * The attestor keypair stands in for the off-chain MPC/attestor service.
+ *
+ * Message: b"halo:trust-tier-claim:v1" || borsh(TrustTierClaim)
+ */
+export function buildTierClaimInstruction(
+  attestor: web3.Keypair,
+  authority: web3.PublicKey,
+  encryptedScore: Buffer,
+  minTier: number,
+  expiresAt: BN
+): web3.TransactionInstruction {
+  const message = Buffer.concat([
+    Buffer.from("halo:trust-tier-claim:v1"),
+    authority.toBuffer(),
+    createHash("sha256").update(encryptedScore).digest(),
+    Buffer.from([minTier]),
+    expiresAt.toArrayLike(Buffer, "le", 8),
+  ]);
+
+  return web3.Ed25519Program.createInstructionWithPrivateKey({
+    privateKey: attestor.secretKey,
+    message,
+  });
+}
+
+/**
+ * Read a circle's automation log, oldest record first. The log is a ring
+ * buffer: once full, `head` points at the oldest record.
+ */
+export async function fetchAutomationLog(
+  program: Program<any>,
+  circle: web3.PublicKey
+): Promise<{ totalRecords: number; records: any[] }> {
+  const [logKey] = findAutomationLogPDA(circle);
+  const log = await program.account.automationLog.fetch(logKey);
+  const records: any[] = log.records;
+  const head: number = log.head;
+  return {
+    totalRecords: log.totalRecords.toNumber(),
+    records: records.slice(head).concat(records.slice(0, head)),
+  };
+}
+
+/**
+ * Build the ed25519 instruction carrying an oracle automation callback.
+ * The enclave keypair stands in for the oracle queue's signing enclave.
+ *
+ * Message: b"halo:automation-callback:v1" || borsh(AutomationCallback)
+ * task: 0 = ContributionCollection, 1 = PayoutDistribution, 2 = PenaltyEnforcement
+ */
+export function buildAutomationCallbackInstruction(
+  enclave: web3.Keypair,
+  circle: web3.PublicKey,
+  task: number,
+  recipient: web3.PublicKey,
+  issuedAt: BN
+): web3.TransactionInstruction {
+  const message = Buffer.concat([
+    Buffer.from("halo:automation-callback:v1"),
+    circle.toBuffer(),
+    Buffer.from([task]),
+    recipient.toBuffer(),
+    issuedAt.toArrayLike(Buffer, "le", 8),
+  ]);
+
+  return web3.Ed25519Program.createInstructionWithPrivateKey({
+    privateKey: enclave.secretKey,
+    message,
+  });
+}
+
 /**
  * Initialize the treasury and revenue params. Many instructions (distribute_pot
  * etc.) require these accounts to exist.
@@ -477,6 +720,29 @@ export async function initializeRevenueAccounts(
   return { treasuryKey, revenueParamsKey };
 }
 
+/**
+ * Create a user's trust score. Automated tasks that update trust scores
+ * require one for every member they process.
+ */
+export async function initializeTrustScore(
+  program: Program<any>,
+  authority: web3.Keypair
+): Promise<web3.PublicKey> {
+  const [trustScoreKey] = findTrustScorePDA(authority.publicKey);
+
+  await program.methods
+    .initializeTrustScore()
+    .accounts({
+      trustScore: trustScoreKey,
+      authority: authority.publicKey,
+      systemProgram: web3.SystemProgram.programId,
+    })
+    .signers([authority])
+    .rpc();
+
+  return trustScoreKey;
+}
+
 // ---------------------------------------------------------------------------
 // Error assertion helpers
 // ---------------------------------------------------------------------------
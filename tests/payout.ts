@@ -34,6 +34,9 @@ describe("halo-protocol: ROSCA payout", () => {
 
   let creator: web3.Keypair;
   let mint: web3.PublicKey;
+  let treasuryKey: web3.PublicKey;
+  let revenueParamsKey: web3.PublicKey;
+  let treasuryTokenAccount: web3.PublicKey;
 
   before(async () => {
     creator = web3.Keypair.generate();
@@ -41,7 +44,16 @@ describe("halo-protocol: ROSCA payout", () => {
     mint = await createTestMint(connection, creator);
 
     // Initialize treasury + revenue params (needed for some downstream calls)
-    await initializeRevenueAccounts(program, creator);
+    ({ treasuryKey, revenueParamsKey } = await initializeRevenueAccounts(
+      program,
+      creator
+    ));
+    treasuryTokenAccount = await createTokenAccount(
+      connection,
+      creator,
+      mint,
+      treasuryKey
+    );
   });
 
   // =========================================================================
@@ -89,6 +101,10 @@ describe("halo-protocol: ROSCA payout", () => {
             memberTokenAccount: memberTokenAccount,
             escrowTokenAccount: payoutCircle.escrowTokenAccount,
             tokenProgram: TOKEN_PROGRAM_ID,
+            treasury: treasuryKey,
+            revenueParams: revenueParamsKey,
+            treasuryTokenAccount: treasuryTokenAccount,
+            trustScore: null,
           })
           .signers([memberKp])
           .rpc(),
@@ -176,6 +192,10 @@ describe("halo-protocol: ROSCA payout", () => {
             memberTokenAccount: claimerJoin.memberTokenAccount,
             escrowTokenAccount: claimCircle.escrowTokenAccount,
             tokenProgram: TOKEN_PROGRAM_ID,
+            treasury: treasuryKey,
+            revenueParams: revenueParamsKey,
+            treasuryTokenAccount: treasuryTokenAccount,
+            trustScore: null,
           })
           .signers([claimer])
           .rpc(),
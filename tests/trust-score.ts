@@ -9,6 +9,7 @@ import {
   createTokenAccount,
   mintTokens,
   findTrustScorePDA,
+  findTrustCredentialPDA,
   findCirclePDA,
   findEscrowPDA,
   findMemberPDA,
@@ -142,6 +143,8 @@ describe("halo-protocol: trust score system", () => {
         .updateTrustScore()
         .accounts({
           trustScore: trustScoreKey,
+          trustCredential: null,
+          protocolRealm: null,
           authority: user.publicKey,
         })
         .signers([user])
@@ -160,6 +163,136 @@ describe("halo-protocol: trust score system", () => {
     });
   });
 
+  // =========================================================================
+  // trust credential
+  // =========================================================================
+
+  describe("trust credential", () => {
+    let user: web3.Keypair;
+    let trustScoreKey: web3.PublicKey;
+    let credentialKey: web3.PublicKey;
+
+    before(async () => {
+      user = web3.Keypair.generate();
+      await airdropSol(connection, user.publicKey);
+
+      [trustScoreKey] = findTrustScorePDA(user.publicKey);
+      [credentialKey] = findTrustCredentialPDA(user.publicKey);
+
+      await program.methods
+        .initializeTrustScore()
+        .accounts({
+          trustScore: trustScoreKey,
+          authority: user.publicKey,
+          systemProgram: web3.SystemProgram.programId,
+        })
+        .signers([user])
+        .rpc();
+    });
+
+    it("issues a credential mirroring the trust score", async () => {
+      await program.methods
+        .issueTrustCredential()
+        .accounts({
+          trustCredential: credentialKey,
+          trustScore: trustScoreKey,
+          authority: user.publicKey,
+          systemProgram: web3.SystemProgram.programId,
+        })
+        .signers([user])
+        .rpc();
+
+      const credential = await program.account.trustCredential.fetch(
+        credentialKey
+      );
+      expect(credential.schemaVersion).to.equal(1);
+      expect(credential.authority.toBase58()).to.equal(
+        user.publicKey.toBase58()
+      );
+      expect(credential.tier).to.equal(0);
+      expect(credential.score).to.equal(0);
+    });
+
+    it("keeps the credential in sync on update_trust_score", async () => {
+      await program.methods
+        .addSocialProof("twitter", "@halo_user")
+        .accounts({ trustScore: trustScoreKey, authority: user.publicKey })
+        .signers([user])
+        .rpc();
+      await program.methods
+        .verifySocialProof("twitter", "@halo_user")
+        .accounts({ trustScore: trustScoreKey, verifier: user.publicKey })
+        .signers([user])
+        .rpc();
+
+      await program.methods
+        .updateTrustScore()
+        .accounts({
+          trustScore: trustScoreKey,
+          trustCredential: credentialKey,
+          protocolRealm: null,
+          authority: user.publicKey,
+        })
+        .signers([user])
+        .rpc();
+
+      const trustScore = await program.account.trustScore.fetch(trustScoreKey);
+      const credential = await program.account.trustCredential.fetch(
+        credentialKey
+      );
+      expect(credential.score).to.equal(trustScore.score);
+      expect(credential.score).to.equal(20);
+    });
+
+    it("serves the tier through the get_trust_tier view", async () => {
+      const view = await program.methods
+        .getTrustTier()
+        .accounts({ trustCredential: credentialKey, trustScore: trustScoreKey })
+        .view();
+
+      expect(view.schemaVersion).to.equal(1);
+      expect(view.authority.toBase58()).to.equal(user.publicKey.toBase58());
+      expect(view.tier).to.equal(0);
+      expect(view.score).to.equal(20);
+    });
+
+    it("serves the live score when the credential was not synced", async () => {
+      await program.methods
+        .addSocialProof("discord", "halo_user#1")
+        .accounts({ trustScore: trustScoreKey, authority: user.publicKey })
+        .signers([user])
+        .rpc();
+      await program.methods
+        .verifySocialProof("discord", "halo_user#1")
+        .accounts({ trustScore: trustScoreKey, verifier: user.publicKey })
+        .signers([user])
+        .rpc();
+      await program.methods
+        .updateTrustScore()
+        .accounts({
+          trustScore: trustScoreKey,
+          trustCredential: null,
+          protocolRealm: null,
+          authority: user.publicKey,
+        })
+        .signers([user])
+        .rpc();
+
+      const trustScore = await program.account.trustScore.fetch(trustScoreKey);
+      const credential = await program.account.trustCredential.fetch(
+        credentialKey
+      );
+      const view = await program.methods
+        .getTrustTier()
+        .accounts({ trustCredential: credentialKey, trustScore: trustScoreKey })
+        .view();
+
+      expect(credential.score).to.equal(20);
+      expect(view.score).to.equal(trustScore.score);
+      expect(view.score).to.be.greaterThan(20);
+    });
+  });
+
   // =========================================================================
   // add_social_proof
   // =========================================================================